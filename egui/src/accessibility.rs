@@ -0,0 +1,159 @@
+//! An accessibility tree describing the widgets of a frame, for screen readers and other assistive technology.
+//!
+//! Turn it on with [`Context::set_accessibility`] and you will get an [`AccessibilityTree`]
+//! in [`Output::accessibility_tree`] each frame.
+//! It is up to the integration to forward it to the accessibility API of the platform.
+
+use crate::*;
+
+/// A widget or window as seen by a screen reader.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessNode {
+    /// The [`Id`] of the widget. Stable between frames for most widgets.
+    pub id: Id,
+    /// Button, checkbox, slider, text field, window, heading, …
+    pub role: WidgetType,
+    /// e.g. the text of a button or the title of a window.
+    pub label: Option<String>,
+    /// e.g. the value of a slider or the contents of a text field.
+    pub value: Option<String>,
+    /// Where on screen the widget is, in points.
+    pub bounds: Rect,
+    /// Does this widget have keyboard focus?
+    pub focused: bool,
+    /// For checkboxes, radio buttons and selectable labels.
+    pub checked: Option<bool>,
+    /// For collapsing headers, combo boxes and windows.
+    pub expanded: Option<bool>,
+    /// The widgets inside a window, in the order they were laid out.
+    pub children: Vec<AccessNode>,
+}
+
+impl AccessNode {
+    fn from_record(record: &WidgetRecord) -> Self {
+        let info = record
+            .info
+            .clone()
+            .unwrap_or_else(|| WidgetInfo::new(WidgetType::Other));
+        let number = info.value;
+        let value = info
+            .text_value
+            .or_else(|| number.map(|number| number.to_string()));
+        Self {
+            id: record.id,
+            role: info.typ,
+            label: info.label,
+            value,
+            bounds: record.rect,
+            focused: record.has_kb_focus,
+            checked: info.selected,
+            expanded: info.expanded,
+            children: vec![],
+        }
+    }
+
+    /// This node and all its descendants, depth first.
+    pub fn iter(&self) -> impl Iterator<Item = &AccessNode> {
+        let descendants: Box<dyn Iterator<Item = &AccessNode>> =
+            Box::new(self.children.iter().flat_map(|child| child.iter()));
+        std::iter::once(self).chain(descendants)
+    }
+}
+
+/// All the [`AccessNode`]s of one frame.
+///
+/// The top level contains the windows (and other areas, like popups)
+/// and the widgets in panels, in back-to-front order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityTree {
+    pub nodes: Vec<AccessNode>,
+}
+
+impl AccessibilityTree {
+    /// All nodes, depth first.
+    pub fn iter(&self) -> impl Iterator<Item = &AccessNode> {
+        self.nodes.iter().flat_map(|node| node.iter())
+    }
+
+    /// The node with keyboard focus, if any.
+    pub fn focused(&self) -> Option<&AccessNode> {
+        self.iter().find(|node| node.focused)
+    }
+
+    /// The first node with the given label, if any.
+    pub fn find(&self, label: &str) -> Option<&AccessNode> {
+        self.iter()
+            .find(|node| node.label.as_deref() == Some(label))
+    }
+
+    /// Build the tree from the widgets recorded during a frame.
+    ///
+    /// `layer_order` is the back-to-front order of the areas (windows etc).
+    pub(crate) fn from_widgets(widgets: &[WidgetRecord], layer_order: &[LayerId]) -> Self {
+        // Group by layer, keeping the order the widgets were laid out in:
+        let mut layers: Vec<(LayerId, Vec<&WidgetRecord>)> = vec![];
+        for record in widgets {
+            let is_relevant = record.info.is_some() || record.sense.focusable;
+            if !is_relevant {
+                continue; // e.g. the background of a panel
+            }
+            if let Some((_, records)) = layers.iter_mut().find(|(l, _)| *l == record.layer_id) {
+                records.push(record);
+            } else {
+                layers.push((record.layer_id, vec![record]));
+            }
+        }
+
+        let layer_index = |layer_id: &LayerId| layer_order.iter().position(|l| l == layer_id);
+        layers.sort_by_key(|(layer_id, _)| (layer_id.order, layer_index(layer_id)));
+
+        let mut nodes = vec![];
+        for (_, records) in layers {
+            let window = records.iter().find(
+                |record| matches!(&record.info, Some(info) if info.typ == WidgetType::Window),
+            );
+            let children = records
+                .iter()
+                .filter(|record| Some(record.id) != window.map(|w| w.id))
+                .map(|record| AccessNode::from_record(record));
+
+            if let Some(window) = window {
+                let mut window_node = AccessNode::from_record(window);
+                window_node.children = children.collect();
+                nodes.push(window_node);
+            } else {
+                nodes.extend(children);
+            }
+        }
+
+        Self { nodes }
+    }
+}
+
+#[test]
+fn test_accessibility_tree() {
+    let mut checked = true;
+    let mut app = |ctx: &CtxRef| {
+        Window::new("Settings").show(ctx, |ui| {
+            ui.heading("General");
+            ui.checkbox(&mut checked, "Dark mode");
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.ctx().set_accessibility(true);
+    harness.run(&mut app);
+    let tree = harness.output().accessibility_tree.clone().unwrap();
+
+    let window = tree.find("Settings").unwrap();
+    assert_eq!(window.role, WidgetType::Window);
+    assert!(tree.nodes.iter().any(|node| node.id == window.id));
+    assert_eq!(tree.find("General").unwrap().role, WidgetType::Heading);
+    let checkbox = window
+        .children
+        .iter()
+        .find(|node| node.label.as_deref() == Some("Dark mode"))
+        .unwrap();
+    assert_eq!(checkbox.role, WidgetType::Checkbox);
+    assert_eq!(checkbox.checked, Some(true));
+}
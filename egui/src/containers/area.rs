@@ -45,6 +45,7 @@ pub struct Area {
     pub(crate) id: Id,
     movable: bool,
     interactable: bool,
+    modal: bool,
     order: Order,
     default_pos: Option<Pos2>,
     fixed_pos: Option<Pos2>,
@@ -56,6 +57,7 @@ impl Area {
             id: Id::new(id_source),
             movable: true,
             interactable: true,
+            modal: false,
             order: Order::Middle,
             default_pos: None,
             fixed_pos: None,
@@ -90,6 +92,23 @@ impl Area {
         self
     }
 
+    /// A modal area blocks all input to the layers beneath it, and dims them.
+    ///
+    /// This also puts the area in [`Order::Foreground`], on top of all normal windows.
+    /// Use it for dialogs that the user must deal with before doing anything else.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        if modal {
+            self.interactable = true;
+            self.order = Order::Foreground;
+        }
+        self
+    }
+
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
     /// `order(Order::Foreground)` for an Area that should always be on top
     pub fn order(mut self, order: Order) -> Self {
         self.order = order;
@@ -124,12 +143,24 @@ impl Area {
             movable,
             order,
             interactable,
+            modal,
             default_pos,
             fixed_pos,
         } = self;
 
         let layer_id = LayerId::new(order, id);
 
+        if modal {
+            ctx.memory().areas.set_modal(layer_id);
+            // Dim everything behind us:
+            let screen_rect = ctx.screen_rect();
+            Painter::new(ctx.clone(), layer_id, screen_rect).rect_filled(
+                screen_rect,
+                0.0,
+                ctx.style().visuals.modal_backdrop_color,
+            );
+        }
+
         let state = ctx.memory().areas.get(id).cloned();
         let mut state = state.unwrap_or_else(|| State {
             pos: default_pos.unwrap_or_else(|| automatic_area_position(ctx)),
@@ -169,7 +200,7 @@ impl Prepared {
         let mut clip_rect = max_rect
             .expand(ctx.style().visuals.clip_rect_margin)
             .expand(shadow_radius)
-            .intersect(ctx.input().screen_rect);
+            .intersect(ctx.screen_rect());
 
         // Windows are constrained to central area,
         // (except in rare cases where they don't fit).
@@ -201,9 +232,9 @@ impl Prepared {
 
         let interact_id = layer_id.id.with("move");
         let sense = if movable {
-            Sense::click_and_drag()
+            Sense::click_and_drag().not_focusable()
         } else {
-            Sense::click() // allow clicks to bring to front
+            Sense::click().not_focusable() // allow clicks to bring to front
         };
 
         let move_response = ctx.interact(
@@ -228,7 +259,8 @@ impl Prepared {
             ctx.memory().areas.move_to_top(layer_id);
             ctx.request_repaint();
         }
-        ctx.memory().areas.set_state(layer_id, state);
+        let viewport_id = ctx.viewport_id();
+        ctx.memory().areas.set_state(layer_id, viewport_id, state);
 
         move_response
     }
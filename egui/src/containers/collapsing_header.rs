@@ -10,7 +10,7 @@ use crate::{
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub(crate) struct State {
-    open: bool,
+    pub(crate) open: bool,
 
     /// Height of the region when open. Used for animations
     open_height: Option<f32>,
@@ -202,6 +202,10 @@ impl CollapsingHeader {
         if header_response.clicked {
             state.toggle(ui);
         }
+        header_response.widget_info(|| WidgetInfo {
+            expanded: Some(state.open),
+            ..WidgetInfo::labeled(WidgetType::CollapsingHeader, &galley.text)
+        });
 
         let bg_index = ui.painter().add(PaintCmd::Noop);
 
@@ -25,7 +25,13 @@ pub fn combo_box_with_label(
     let button_id = ui.make_persistent_id(label.text());
 
     ui.horizontal(|ui| {
-        let mut response = combo_box(ui, button_id, selected, menu_contents);
+        let mut response = combo_box_impl(
+            ui,
+            button_id,
+            Some(label.text().to_owned()),
+            selected,
+            menu_contents,
+        );
         response |= ui.add(label);
         response
     })
@@ -53,11 +59,22 @@ pub fn combo_box(
     button_id: Id,
     selected: impl Into<Label>,
     menu_contents: impl FnOnce(&mut Ui),
+) -> Response {
+    combo_box_impl(ui, button_id, None, selected, menu_contents)
+}
+
+fn combo_box_impl(
+    ui: &mut Ui,
+    button_id: Id,
+    label: Option<String>,
+    selected: impl Into<Label>,
+    menu_contents: impl FnOnce(&mut Ui),
 ) -> Response {
     const MAX_COMBO_HEIGHT: f32 = 128.0;
 
     let popup_id = button_id.with("popup");
     let selected = selected.into();
+    let selected_text = selected.text().to_owned();
 
     let button_active = ui.memory().is_popup_open(popup_id);
     let button_response = button_frame(ui, button_id, button_active, Sense::click(), |ui| {
@@ -81,6 +98,13 @@ pub fn combo_box(
     if button_response.clicked {
         ui.memory().toggle_popup(popup_id);
     }
+    let expanded = ui.memory().is_popup_open(popup_id);
+    button_response.widget_info(|| WidgetInfo {
+        label,
+        text_value: Some(selected_text),
+        expanded: Some(expanded),
+        ..WidgetInfo::new(WidgetType::ComboBox)
+    });
 
     if ui.memory().is_popup_open(popup_id) {
         let parent_clip_rect = ui.clip_rect();
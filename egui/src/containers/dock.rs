@@ -0,0 +1,693 @@
+//! Docking: tabbed groups of panels in a tree of horizontal and vertical splits.
+
+use crate::{
+    paint::{PaintCmd, Rgba, TextStyle},
+    *,
+};
+
+/// Tells a [`Dock`] how to show your tabs.
+pub trait TabViewer {
+    /// Whatever identifies a tab, e.g. an enum of all your tool windows.
+    type Tab;
+
+    /// The text on the tab. Should be unique within the dock.
+    fn title(&mut self, tab: &Self::Tab) -> String;
+
+    /// Show the contents of the tab.
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab);
+}
+
+/// Some tabs, of which one is shown at a time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabGroup<Tab> {
+    pub tabs: Vec<Tab>,
+    /// Index of the tab that is shown.
+    pub active: usize,
+}
+
+impl<Tab> TabGroup<Tab> {
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self { tabs, active: 0 }
+    }
+
+    fn remove(&mut self, index: usize) -> Tab {
+        let tab = self.tabs.remove(index);
+        if self.active > index || self.active == self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        tab
+    }
+}
+
+/// In which direction a [`DockNode::Split`] splits its space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SplitDirection {
+    /// The children are side by side.
+    Horizontal,
+    /// The children are on top of each other.
+    Vertical,
+}
+
+/// A node in the layout tree of a [`Dock`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DockNode<Tab> {
+    Tabs(TabGroup<Tab>),
+    Split {
+        direction: SplitDirection,
+        /// How much of the space goes to the first child, in the range `0.0..=1.0`.
+        fraction: f32,
+        /// Left and right, or top and bottom.
+        children: Box<[DockNode<Tab>; 2]>,
+    },
+}
+
+impl<Tab> DockNode<Tab> {
+    pub fn tabs(tabs: Vec<Tab>) -> Self {
+        Self::Tabs(TabGroup::new(tabs))
+    }
+
+    pub fn split(direction: SplitDirection, fraction: f32, first: Self, second: Self) -> Self {
+        Self::Split {
+            direction,
+            fraction,
+            children: Box::new([first, second]),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Tabs(group) => group.tabs.is_empty(),
+            Self::Split { .. } => false,
+        }
+    }
+
+    /// Remove empty groups, and splits with only one child left.
+    fn simplify(&mut self) {
+        if let Self::Split { children, .. } = self {
+            children[0].simplify();
+            children[1].simplify();
+            if children[0].is_empty() {
+                *self = std::mem::replace(&mut children[1], Self::tabs(vec![]));
+            } else if children[1].is_empty() {
+                *self = std::mem::replace(&mut children[0], Self::tabs(vec![]));
+            }
+        }
+    }
+
+    fn collect_tabs<'a>(&'a self, tabs: &mut Vec<&'a Tab>) {
+        match self {
+            Self::Tabs(group) => tabs.extend(&group.tabs),
+            Self::Split { children, .. } => {
+                children[0].collect_tabs(tabs);
+                children[1].collect_tabs(tabs);
+            }
+        }
+    }
+}
+
+/// A [`TabGroup`] that has been torn out of the dock into a floating [`Window`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FloatingTabs<Tab> {
+    pub group: TabGroup<Tab>,
+    /// Where the window was first shown.
+    pub pos: Pos2,
+    /// Unique within the [`DockLayout`], so that the window keeps its [`Id`].
+    id: u64,
+}
+
+/// The tabs of a [`Dock`] and how they are laid out.
+///
+/// This is your data: keep it around between frames, and (with the `serde` feature)
+/// save it with the rest of your app state to restore the layout on the next run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DockLayout<Tab> {
+    pub root: DockNode<Tab>,
+    pub floating: Vec<FloatingTabs<Tab>>,
+    next_floating_id: u64,
+}
+
+impl<Tab> DockLayout<Tab> {
+    pub fn new(root: DockNode<Tab>) -> Self {
+        Self {
+            root,
+            floating: vec![],
+            next_floating_id: 0,
+        }
+    }
+
+    /// All tabs, docked and floating.
+    pub fn tabs(&self) -> Vec<&Tab> {
+        let mut tabs = vec![];
+        self.root.collect_tabs(&mut tabs);
+        for floating in &self.floating {
+            tabs.extend(&floating.group.tabs);
+        }
+        tabs
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> &mut DockNode<Tab> {
+        let mut node = &mut self.root;
+        for &child in path {
+            node = match node {
+                DockNode::Split { children, .. } => &mut children[child],
+                DockNode::Tabs(_) => unreachable!("Bad path into the dock"),
+            };
+        }
+        node
+    }
+
+    fn group_mut(&mut self, group: &GroupRef) -> &mut TabGroup<Tab> {
+        match group {
+            GroupRef::Docked(path) => match self.node_mut(path) {
+                DockNode::Tabs(group) => group,
+                DockNode::Split { .. } => unreachable!("Bad path into the dock"),
+            },
+            GroupRef::Floating(index) => &mut self.floating[*index].group,
+        }
+    }
+
+    fn move_tab(&mut self, from: &GroupRef, index: usize, to: DropTarget) {
+        // Leave the source group in place (even if empty) so that the paths stay valid:
+        let tab = self.group_mut(from).remove(index);
+
+        match to {
+            DropTarget::Group { group, tab_index } => {
+                let target = self.group_mut(&group);
+                let mut tab_index = tab_index.unwrap_or(target.tabs.len());
+                if group == *from && tab_index > index {
+                    tab_index -= 1; // We removed a tab before it
+                }
+                target.tabs.insert(tab_index, tab);
+                target.active = tab_index;
+            }
+            DropTarget::Split {
+                path,
+                direction,
+                first,
+            } => {
+                let node = self.node_mut(&path);
+                let old = std::mem::replace(node, DockNode::tabs(vec![]));
+                let new = DockNode::tabs(vec![tab]);
+                *node = if first {
+                    DockNode::split(direction, 0.5, new, old)
+                } else {
+                    DockNode::split(direction, 0.5, old, new)
+                };
+            }
+            DropTarget::Float(pos) => {
+                self.floating.push(FloatingTabs {
+                    group: TabGroup::new(vec![tab]),
+                    pos,
+                    id: self.next_floating_id,
+                });
+                self.next_floating_id += 1;
+            }
+        }
+
+        self.root.simplify();
+        self.floating
+            .retain(|floating| !floating.group.tabs.is_empty());
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Which tab group, as a path of child indices through the splits, or a floating window.
+#[derive(Clone, Debug, Hash, PartialEq)]
+enum GroupRef {
+    Docked(Vec<usize>),
+    Floating(usize),
+}
+
+/// Where a dragged tab will go.
+enum DropTarget {
+    /// Into a group, at the given position in the tab bar (or last).
+    Group {
+        group: GroupRef,
+        tab_index: Option<usize>,
+    },
+    /// Split the docked group at `path` in two, putting the tab in the `first` or second half.
+    Split {
+        path: Vec<usize>,
+        direction: SplitDirection,
+        first: bool,
+    },
+    /// Tear out into a new floating window.
+    Float(Pos2),
+}
+
+/// A tab group as shown this frame.
+struct ShownGroup {
+    group: GroupRef,
+    layer_id: LayerId,
+    tab_bar: Rect,
+    tab_rects: Vec<Rect>,
+    body: Rect,
+}
+
+struct DockState {
+    id: Id,
+    shown: Vec<ShownGroup>,
+    /// The tab being dragged (if any): which group and the index in it.
+    dragged: Option<(GroupRef, usize)>,
+}
+
+/// A docking area: tabbed groups of panels in a tree of horizontal and vertical splits.
+///
+/// The user can resize the splits, and drag tabs into other groups,
+/// to the side of a group to split it, or out of the dock into a floating [`Window`].
+///
+/// The layout is kept in a [`DockLayout`] that you own.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// use egui::{Dock, DockLayout, DockNode, SplitDirection, TabViewer};
+///
+/// struct MyTabs;
+///
+/// impl TabViewer for MyTabs {
+///     type Tab = String;
+///
+///     fn title(&mut self, tab: &String) -> String {
+///         tab.clone()
+///     }
+///
+///     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut String) {
+///         ui.label(format!("Contents of {}", tab));
+///     }
+/// }
+///
+/// let mut layout = DockLayout::new(DockNode::split(
+///     SplitDirection::Horizontal,
+///     0.3,
+///     DockNode::tabs(vec!["Outliner".to_owned()]),
+///     DockNode::tabs(vec!["Viewport".to_owned(), "Log".to_owned()]),
+/// ));
+/// Dock::new("my_dock").show(ui, &mut layout, &mut MyTabs);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dock {
+    id_source: Id,
+}
+
+impl Dock {
+    /// Create a new `Dock` with a locally unique identifier.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+        }
+    }
+
+    /// Fill the available space of `ui` with the docked tabs, and show the floating ones in windows.
+    pub fn show<V: TabViewer>(
+        self,
+        ui: &mut Ui,
+        layout: &mut DockLayout<V::Tab>,
+        viewer: &mut V,
+    ) -> Response {
+        let id = ui.make_persistent_id(self.id_source);
+        let rect = ui.available_rect_before_wrap_finite();
+        let response = ui.allocate_response(rect.size(), Sense::hover());
+
+        let mut state = DockState {
+            id,
+            shown: vec![],
+            dragged: None,
+        };
+        show_node(ui, rect, &mut layout.root, &mut vec![], viewer, &mut state);
+
+        for (index, floating) in layout.floating.iter_mut().enumerate() {
+            let group = &mut floating.group;
+            let title = group
+                .tabs
+                .get(group.active)
+                .map(|tab| viewer.title(tab))
+                .unwrap_or_default();
+            Window::new(title)
+                .id(id.with("floating").with(floating.id))
+                .title_bar(false)
+                .default_pos(floating.pos)
+                .default_size(vec2(300.0, 200.0))
+                .show(ui.ctx(), |ui| {
+                    let rect = ui.available_rect_before_wrap_finite();
+                    ui.allocate_space(rect.size());
+                    let group_ref = GroupRef::Floating(index);
+                    show_group(ui, rect, group, group_ref, viewer, &mut state);
+                });
+        }
+
+        if let Some((from, index)) = state.dragged.take() {
+            let title = viewer.title(&layout.group_mut(&from).tabs[index]);
+            if let Some(target) = drag_tab(ui.ctx(), &state, title) {
+                layout.move_tab(&from, index, target);
+            }
+        }
+
+        response
+    }
+}
+
+fn show_node<V: TabViewer>(
+    ui: &mut Ui,
+    rect: Rect,
+    node: &mut DockNode<V::Tab>,
+    path: &mut Vec<usize>,
+    viewer: &mut V,
+    state: &mut DockState,
+) {
+    match node {
+        DockNode::Tabs(group) => {
+            let group_ref = GroupRef::Docked(path.clone());
+            show_group(ui, rect, group, group_ref, viewer, state);
+        }
+        DockNode::Split {
+            direction,
+            fraction,
+            children,
+        } => {
+            let d = match direction {
+                SplitDirection::Horizontal => 0,
+                SplitDirection::Vertical => 1,
+            };
+            let gap = ui.style().spacing.item_spacing[d];
+
+            // The handle between the children:
+            let split_at = |fraction: f32| lerp(rect.min[d]..=rect.max[d], fraction);
+            let handle_rect = |split: f32| {
+                let mut handle = rect;
+                handle.min[d] = split - 0.5 * gap;
+                handle.max[d] = split + 0.5 * gap;
+                handle
+            };
+            let response = ui.interact(
+                handle_rect(split_at(*fraction)),
+                state.id.with("split").with(&path),
+                Sense::drag(),
+            );
+            if response.active {
+                if let Some(mouse_pos) = ui.input().mouse.pos {
+                    // Don't let either child get smaller than a tab bar:
+                    let min_size = 2.0 * ui.style().spacing.interact_size.y;
+                    let min_fraction = (min_size / rect.size()[d]).at_most(0.5);
+                    *fraction = remap(mouse_pos[d], rect.min[d]..=rect.max[d], 0.0..=1.0)
+                        .max(min_fraction)
+                        .min(1.0 - min_fraction);
+                }
+            }
+            if response.hovered || response.active {
+                ui.output().cursor_icon = if d == 0 {
+                    CursorIcon::ResizeHorizontal
+                } else {
+                    CursorIcon::ResizeVertical
+                };
+            }
+
+            let split = split_at(*fraction);
+            let mut first = rect;
+            first.max[d] = split - 0.5 * gap;
+            let mut second = rect;
+            second.min[d] = split + 0.5 * gap;
+
+            let stroke = if response.hovered || response.active {
+                ui.style().interact(&response).fg_stroke
+            } else {
+                ui.style().visuals.widgets.noninteractive.bg_stroke
+            };
+            let handle = handle_rect(split);
+            let line = if d == 0 {
+                [handle.center_top(), handle.center_bottom()]
+            } else {
+                [handle.left_center(), handle.right_center()]
+            };
+            ui.painter().line_segment(line, stroke);
+
+            let child_rects = [first, second];
+            for (index, child) in children.iter_mut().enumerate() {
+                path.push(index);
+                show_node(ui, child_rects[index], child, path, viewer, state);
+                path.pop();
+            }
+        }
+    }
+}
+
+fn show_group<V: TabViewer>(
+    ui: &mut Ui,
+    rect: Rect,
+    group: &mut TabGroup<V::Tab>,
+    group_ref: GroupRef,
+    viewer: &mut V,
+    state: &mut DockState,
+) {
+    let group_id = state.id.with(&group_ref);
+    let padding = ui.style().spacing.button_padding;
+    let tab_bar_height = (ui.fonts()[TextStyle::Button].row_height() + 2.0 * padding.y)
+        .at_least(ui.style().spacing.interact_size.y);
+    let tab_bar = Rect::from_min_size(rect.min, vec2(rect.width(), tab_bar_height));
+    let body = Rect::from_min_max(pos2(rect.left(), tab_bar.bottom()), rect.max);
+
+    let visuals = ui.style().visuals.clone();
+    let painter = ui.painter().sub_region(tab_bar);
+    painter.rect_filled(tab_bar, 0.0, visuals.faint_bg_color);
+    painter.line_segment(
+        [tab_bar.left_bottom(), tab_bar.right_bottom()],
+        visuals.widgets.noninteractive.bg_stroke,
+    );
+
+    let mut tab_rects = vec![];
+    let mut x = tab_bar.left();
+    for index in 0..group.tabs.len() {
+        let title = viewer.title(&group.tabs[index]);
+        let galley = ui.fonts()[TextStyle::Button].layout_single_line(title.clone());
+        let tab_rect = Rect::from_min_size(
+            pos2(x, tab_bar.top()),
+            vec2(galley.size.x + 2.0 * padding.x, tab_bar_height),
+        );
+        x = tab_rect.right();
+
+        let tab_id = group_id.with(index);
+        let response = ui.interact(tab_rect, tab_id, Sense::click_and_drag());
+        let is_active = index == group.active;
+        response
+            .widget_info(|| WidgetInfo::selected(WidgetType::SelectableLabel, is_active, &title));
+        if response.clicked {
+            group.active = index;
+        }
+        if ui.memory().is_being_dragged(tab_id) {
+            state.dragged = Some((group_ref.clone(), index));
+        }
+
+        let fill = if index == group.active {
+            Some(visuals.widgets.inactive.bg_fill)
+        } else if response.hovered {
+            Some(visuals.widgets.hovered.bg_fill)
+        } else {
+            None
+        };
+        if let Some(fill) = fill {
+            painter.rect_filled(tab_rect.shrink2(vec2(1.0, 0.0)), 2.0, fill);
+        }
+        let text_pos = pos2(
+            tab_rect.left() + padding.x,
+            tab_rect.center().y - 0.5 * galley.size.y,
+        );
+        let text_color = ui.style().interact(&response).text_color();
+        painter.galley(text_pos, galley, TextStyle::Button, text_color);
+        tab_rects.push(tab_rect);
+    }
+
+    if let Some(tab) = group.tabs.get_mut(group.active) {
+        // Each tab gets its own `Id`, so that its widgets keep their state when the tab is moved:
+        let title = viewer.title(tab);
+        let clip_rect = body.intersect(ui.clip_rect());
+        let content_rect = body.shrink(ui.style().spacing.window_padding.x);
+        let mut content_ui = Ui::new(
+            ui.ctx().clone(),
+            ui.layer_id(),
+            state.id.with("tab").with(title),
+            content_rect,
+            clip_rect,
+        );
+        viewer.ui(&mut content_ui, tab);
+    }
+
+    state.shown.push(ShownGroup {
+        group: group_ref,
+        layer_id: ui.layer_id(),
+        tab_bar,
+        tab_rects,
+        body,
+    });
+}
+
+/// Show where the dragged tab would go, and where it should go if it was just dropped.
+fn drag_tab(ctx: &CtxRef, state: &DockState, title: String) -> Option<DropTarget> {
+    if ctx.input().mouse.could_be_click {
+        return None; // Not dragged far enough yet.
+    }
+    let mouse_pos = ctx.input().mouse.pos?;
+    ctx.output().cursor_icon = CursorIcon::Grabbing;
+    show_tooltip_text(ctx, title);
+
+    let (target, preview_rect) = drop_target(ctx, state, mouse_pos)
+        .unwrap_or_else(|| (DropTarget::Float(mouse_pos), Rect::nothing()));
+
+    let preview_layer = LayerId::new(Order::Foreground, state.id.with("drop_preview"));
+    let painter = Painter::new(ctx.clone(), preview_layer, ctx.screen_rect());
+    let selection = ctx.style().visuals.selection.bg_fill;
+    painter.add(PaintCmd::Rect {
+        rect: preview_rect,
+        corner_radius: 2.0,
+        fill: Rgba::from(selection).multiply(0.5).into(),
+        stroke: Stroke::new(1.0, selection),
+    });
+
+    if ctx.input().mouse.released {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+/// Where would the dragged tab go if dropped at `mouse_pos`, and what to highlight to show it.
+///
+/// `None` means outside of any group.
+fn drop_target(ctx: &CtxRef, state: &DockState, mouse_pos: Pos2) -> Option<(DropTarget, Rect)> {
+    let top_layer = ctx.layer_id_at(mouse_pos);
+    let shown = state
+        .shown
+        .iter()
+        .filter(|shown| Some(shown.layer_id) == top_layer)
+        .find(|shown| shown.tab_bar.union(shown.body).contains(mouse_pos))?;
+
+    if shown.tab_bar.contains(mouse_pos) {
+        let tab_index = shown
+            .tab_rects
+            .iter()
+            .filter(|tab_rect| tab_rect.center().x < mouse_pos.x)
+            .count();
+        let x = match shown.tab_rects.get(tab_index) {
+            Some(tab_rect) => tab_rect.left(),
+            None => shown
+                .tab_rects
+                .last()
+                .map_or(shown.tab_bar.left(), |tab_rect| tab_rect.right()),
+        };
+        let marker = Rect::from_min_max(
+            pos2(x - 1.0, shown.tab_bar.top()),
+            pos2(x + 1.0, shown.tab_bar.bottom()),
+        );
+        let target = DropTarget::Group {
+            group: shown.group.clone(),
+            tab_index: Some(tab_index),
+        };
+        return Some((target, marker));
+    }
+
+    // Near an edge of a docked group: split it.
+    // Nothing to split if the body is collapsed (and `remap` would give us NaN).
+    let body = shown.body;
+    if let GroupRef::Docked(path) = &shown.group {
+        if body.width() > 0.0 && body.height() > 0.0 {
+            let t = vec2(
+                remap(mouse_pos.x, body.x_range(), 0.0..=1.0),
+                remap(mouse_pos.y, body.y_range(), 0.0..=1.0),
+            );
+            let edges = [
+                (t.x, SplitDirection::Horizontal, true),
+                (1.0 - t.x, SplitDirection::Horizontal, false),
+                (t.y, SplitDirection::Vertical, true),
+                (1.0 - t.y, SplitDirection::Vertical, false),
+            ];
+            let (distance, direction, first) = edges
+                .iter()
+                .copied()
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+                .unwrap();
+            if distance < 0.25 {
+                let mut half = body;
+                match (direction, first) {
+                    (SplitDirection::Horizontal, true) => half.max.x = body.center().x,
+                    (SplitDirection::Horizontal, false) => half.min.x = body.center().x,
+                    (SplitDirection::Vertical, true) => half.max.y = body.center().y,
+                    (SplitDirection::Vertical, false) => half.min.y = body.center().y,
+                }
+                let target = DropTarget::Split {
+                    path: path.clone(),
+                    direction,
+                    first,
+                };
+                return Some((target, half));
+            }
+        }
+    }
+
+    let target = DropTarget::Group {
+        group: shown.group.clone(),
+        tab_index: None,
+    };
+    Some((target, body))
+}
+
+#[test]
+fn test_dock() {
+    struct Viewer;
+    impl TabViewer for Viewer {
+        type Tab = &'static str;
+        fn title(&mut self, tab: &&'static str) -> String {
+            tab.to_string()
+        }
+        fn ui(&mut self, ui: &mut Ui, tab: &mut &'static str) {
+            ui.label(format!("Contents of {}", tab));
+        }
+    }
+
+    let mut layout = DockLayout::new(DockNode::split(
+        SplitDirection::Horizontal,
+        0.5,
+        DockNode::tabs(vec!["A", "B"]),
+        DockNode::tabs(vec!["C"]),
+    ));
+
+    let mut harness = testing::Harness::new(vec2(600.0, 400.0));
+    let mut drag = |harness: &mut testing::Harness, tab: &str, to: Pos2| {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                Dock::new("dock").show(ui, &mut layout, &mut Viewer);
+            });
+        };
+        harness.run(&mut app);
+        harness.move_mouse_to(harness.expect_widget(tab).rect.center());
+        harness.set_mouse_down(true);
+        harness.run(&mut app);
+        harness.move_mouse_to(to);
+        harness.run(&mut app);
+        harness.set_mouse_down(false);
+        harness.run(&mut app);
+    };
+
+    // Drag tab "B" into the middle of the right group:
+    drag(&mut harness, "B", pos2(450.0, 200.0));
+    // Then drag "C" to the bottom edge of the right group, splitting it:
+    drag(&mut harness, "C", pos2(450.0, 390.0));
+
+    assert_eq!(
+        layout,
+        DockLayout::new(DockNode::split(
+            SplitDirection::Horizontal,
+            0.5,
+            DockNode::tabs(vec!["A"]),
+            DockNode::split(
+                SplitDirection::Vertical,
+                0.5,
+                DockNode::tabs(vec!["B"]),
+                DockNode::tabs(vec!["C"]),
+            ),
+        ))
+    );
+}
@@ -0,0 +1,385 @@
+//! Grid container: lay out widgets in rows and columns that line up.
+
+use crate::{layout::Region, *};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    col_widths: Vec<f32>,
+    row_heights: Vec<f32>,
+}
+
+impl State {
+    fn set_min_col_width(&mut self, col: usize, width: f32) {
+        self.col_widths
+            .resize(self.col_widths.len().max(col + 1), 0.0);
+        self.col_widths[col] = self.col_widths[col].max(width);
+    }
+
+    fn set_min_row_height(&mut self, row: usize, height: f32) {
+        self.row_heights
+            .resize(self.row_heights.len().max(row + 1), 0.0);
+        self.row_heights[row] = self.row_heights[row].max(height);
+    }
+
+    fn col_width(&self, col: usize) -> Option<f32> {
+        self.col_widths.get(col).copied()
+    }
+
+    fn row_height(&self, row: usize) -> Option<f32> {
+        self.row_heights.get(row).copied()
+    }
+
+    fn full_width(&self, col_spacing: impl Fn(usize) -> f32) -> f32 {
+        let num_cols = self.col_widths.len();
+        self.col_widths.iter().sum::<f32>()
+            + (0..num_cols.at_least(1) - 1).map(col_spacing).sum::<f32>()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The layout state of a [`Grid`] while it is being filled in.
+///
+/// Lives inside of the [`Ui`] that the `Grid` contents are added to.
+pub(crate) struct GridLayout {
+    ctx: CtxRef,
+    id: Id,
+
+    /// State previous frame (if any).
+    /// This is used to align the cells of this frame.
+    prev_state: State,
+
+    /// State accumulated during the current frame.
+    curr_state: State,
+
+    spacing: Vec2,
+    /// Overrides `spacing.x` to the right of some columns.
+    col_spacing: Vec<Option<f32>>,
+    striped: bool,
+    initial_x: f32,
+    min_cell_size: Vec2,
+    max_cell_size: Vec2,
+    col: usize,
+    row: usize,
+}
+
+impl GridLayout {
+    fn new(ui: &Ui, id: Id) -> Self {
+        let prev_state = ui.memory().grid.get(&id).cloned().unwrap_or_default();
+
+        Self {
+            ctx: ui.ctx().clone(),
+            id,
+            prev_state,
+            curr_state: State::default(),
+            spacing: ui.style().spacing.item_spacing,
+            col_spacing: vec![],
+            striped: false,
+            initial_x: ui.cursor().x,
+            min_cell_size: ui.style().spacing.interact_size,
+            max_cell_size: Vec2::infinity(),
+            col: 0,
+            row: 0,
+        }
+    }
+
+    fn prev_col_width(&self, col: usize) -> f32 {
+        self.prev_state
+            .col_width(col)
+            .unwrap_or(self.min_cell_size.x)
+    }
+
+    fn prev_row_height(&self, row: usize) -> f32 {
+        self.prev_state
+            .row_height(row)
+            .unwrap_or(self.min_cell_size.y)
+    }
+
+    /// The horizontal space between this column and the next.
+    fn col_spacing(&self, col: usize) -> f32 {
+        self.col_spacing
+            .get(col)
+            .copied()
+            .flatten()
+            .unwrap_or(self.spacing.x)
+    }
+
+    /// The space available to the next cell.
+    pub(crate) fn available_rect(&self, region: &Region) -> Rect {
+        self.available_rect_within(region.cursor, region.max_rect, region.max_rect_finite())
+    }
+
+    /// Like `available_rect`, but never infinite.
+    pub(crate) fn available_rect_finite(&self, region: &Region) -> Rect {
+        let max_rect = region.max_rect_finite();
+        self.available_rect_within(region.cursor, max_rect, max_rect)
+    }
+
+    fn available_rect_within(&self, cursor: Pos2, max_rect: Rect, max_rect_finite: Rect) -> Rect {
+        // Let the last column (or a column with a max width) wrap text:
+        let width = if self.max_cell_size.x.is_finite() {
+            self.max_cell_size.x
+        } else {
+            (max_rect.right() - cursor.x).at_least(self.prev_col_width(self.col))
+        };
+        let height = max_rect_finite.max.y - cursor.y;
+        let height = height
+            .at_least(self.min_cell_size.y)
+            .at_most(self.max_cell_size.y);
+        Rect::from_min_size(cursor, vec2(width, height))
+    }
+
+    /// The rectangle of the next cell, given what the widget in it wants.
+    /// The cell is as wide as the widest cell of its column was last frame,
+    /// and as high as the highest cell of its row was last frame.
+    pub(crate) fn next_cell(&self, cursor: Pos2, child_size: Vec2) -> Rect {
+        let width = self.prev_state.col_width(self.col).unwrap_or(0.0);
+        let height = self.prev_row_height(self.row);
+        let size = child_size.max(vec2(width, height));
+        Rect::from_min_size(cursor, size)
+    }
+
+    /// Where to put a widget of the given size within a cell.
+    pub(crate) fn align_size_within_rect(&self, size: Vec2, frame: Rect) -> Rect {
+        // Left-aligned, vertically centered:
+        Layout::top_down(Align::Min).align_size_within_rect(size, frame)
+    }
+
+    /// Move to the next cell, remembering the size of the widget we just added.
+    pub(crate) fn advance(&mut self, cursor: &mut Pos2, frame_rect: Rect, widget_rect: Rect) {
+        self.curr_state
+            .set_min_col_width(self.col, widget_rect.width().at_least(self.min_cell_size.x));
+        self.curr_state.set_min_row_height(
+            self.row,
+            widget_rect.height().at_least(self.min_cell_size.y),
+        );
+
+        cursor.x += frame_rect.width() + self.col_spacing(self.col);
+        self.col += 1;
+    }
+
+    /// Move to the first cell of the next row.
+    pub(crate) fn end_row(&mut self, cursor: &mut Pos2, painter: &Painter) {
+        let row_height = self
+            .prev_row_height(self.row)
+            .max(self.curr_state.row_height(self.row).unwrap_or_default());
+
+        cursor.x = self.initial_x;
+        cursor.y += row_height + self.spacing.y;
+        self.col = 0;
+        self.row += 1;
+
+        self.paint_row(*cursor, painter);
+    }
+
+    /// Paint the background of the row we are about to start.
+    fn paint_row(&self, cursor: Pos2, painter: &Painter) {
+        if self.striped && self.row % 2 == 1 {
+            if let Some(height) = self.prev_state.row_height(self.row) {
+                let size = vec2(
+                    self.prev_state.full_width(|col| self.col_spacing(col)),
+                    height,
+                );
+                let rect = Rect::from_min_size(cursor, size);
+                // Cover the spacing between the rows, and some extra on the sides (looks better):
+                let rect = rect.expand2(vec2(2.0, 0.5 * self.spacing.y));
+                let color = painter.ctx().style().visuals.faint_bg_color;
+                painter.rect_filled(rect, 2.0, color);
+            }
+        }
+    }
+
+    /// Store the column widths and row heights for next frame.
+    pub(crate) fn save(&self) {
+        if self.curr_state != self.prev_state {
+            self.ctx
+                .memory()
+                .grid
+                .insert(self.id, self.curr_state.clone());
+            // The cells will line up differently next frame:
+            self.ctx.request_repaint();
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A simple grid layout.
+///
+/// The contents of each cell will be aligned to the left and center.
+///
+/// If you want to add multiple widgets to a cell you need to group them with
+/// [`Ui::horizontal`], [`Ui::vertical`] etc.
+///
+/// Each column will be as wide as its widest cell was the previous frame,
+/// so there may be a one-frame delay before things line up.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// # let mut name = String::new();
+/// # let mut age = 42;
+/// egui::Grid::new("some_unique_id").striped(true).show(ui, |ui| {
+///     ui.label("Name:");
+///     ui.text_edit_singleline(&mut name);
+///     ui.end_row();
+///
+///     ui.label("Age:");
+///     ui.add(egui::DragValue::i32(&mut age));
+///     ui.end_row();
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Grid {
+    id_source: Id,
+    striped: bool,
+    min_col_width: Option<f32>,
+    min_row_height: Option<f32>,
+    max_cell_size: Vec2,
+    spacing: Option<Vec2>,
+    col_spacing: Vec<Option<f32>>,
+}
+
+impl Grid {
+    /// Create a new `Grid` with a locally unique identifier.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            striped: false,
+            min_col_width: None,
+            min_row_height: None,
+            max_cell_size: Vec2::infinity(),
+            spacing: None,
+            col_spacing: vec![],
+        }
+    }
+
+    /// If `true`, add a subtle background color to every other row.
+    ///
+    /// This can make a table easier to read.
+    /// Default: `false`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Set minimum width of each column.
+    /// Default: [`Spacing::interact_size`](crate::style::Spacing::interact_size)`.x`.
+    pub fn min_col_width(mut self, min_col_width: f32) -> Self {
+        self.min_col_width = Some(min_col_width);
+        self
+    }
+
+    /// Set minimum height of each row.
+    /// Default: [`Spacing::interact_size`](crate::style::Spacing::interact_size)`.y`.
+    pub fn min_row_height(mut self, min_row_height: f32) -> Self {
+        self.min_row_height = Some(min_row_height);
+        self
+    }
+
+    /// Set soft maximum width (wrapping width) of each column.
+    pub fn max_col_width(mut self, max_col_width: f32) -> Self {
+        self.max_cell_size.x = max_col_width;
+        self
+    }
+
+    /// Set spacing between columns (`x`) and between rows (`y`).
+    /// Default: [`Spacing::item_spacing`](crate::style::Spacing::item_spacing).
+    pub fn spacing(mut self, spacing: impl Into<Vec2>) -> Self {
+        self.spacing = Some(spacing.into());
+        self
+    }
+
+    /// Set the spacing between column `col` and the next one,
+    /// overriding the `x` of [`Self::spacing`] for just that column.
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// // Keep the labels close to their values, but the pairs far apart:
+    /// egui::Grid::new("pairs")
+    ///     .column_spacing(0, 4.0)
+    ///     .column_spacing(1, 32.0)
+    ///     .column_spacing(2, 4.0)
+    ///     .show(ui, |ui| {
+    ///         ui.label("Width:");
+    ///         ui.label("640");
+    ///         ui.label("Height:");
+    ///         ui.label("480");
+    ///         ui.end_row();
+    ///     });
+    /// ```
+    pub fn column_spacing(mut self, col: usize, spacing: f32) -> Self {
+        if self.col_spacing.len() <= col {
+            self.col_spacing.resize(col + 1, None);
+        }
+        self.col_spacing[col] = Some(spacing);
+        self
+    }
+}
+
+impl Grid {
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
+        let Self {
+            id_source,
+            striped,
+            min_col_width,
+            min_row_height,
+            max_cell_size,
+            spacing,
+            col_spacing,
+        } = self;
+
+        ui.wrap(|ui| {
+            let id = ui.make_persistent_id(id_source);
+            let mut grid = GridLayout::new(ui, id);
+            grid.striped = striped;
+            grid.max_cell_size = max_cell_size;
+            if let Some(min_col_width) = min_col_width {
+                grid.min_cell_size.x = min_col_width;
+            }
+            if let Some(min_row_height) = min_row_height {
+                grid.min_cell_size.y = min_row_height;
+            }
+            if let Some(spacing) = spacing {
+                grid.spacing = spacing;
+            }
+            grid.col_spacing = col_spacing;
+
+            ui.set_grid(grid);
+            let r = add_contents(ui);
+            ui.save_grid();
+            r
+        })
+    }
+}
+
+#[test]
+fn test_grid_columns_line_up() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            Grid::new("grid").column_spacing(0, 30.0).show(ui, |ui| {
+                ui.label("Short");
+                ui.label("A");
+                ui.label("X");
+                ui.end_row();
+
+                ui.label("A much longer label");
+                ui.label("B is wider");
+                ui.label("Y");
+                ui.end_row();
+            });
+        });
+    };
+    harness.run(&mut app);
+    harness.run(&mut app); // columns line up from the second frame on
+
+    let rect = |label: &str| harness.expect_widget(label).rect;
+    assert_eq!(rect("A").left(), rect("B is wider").left());
+    assert_eq!(rect("X").left(), rect("Y").left());
+    assert_eq!(rect("Short").left(), rect("A much longer label").left());
+    assert_eq!(rect("A").left() - rect("A much longer label").right(), 30.0);
+    let item_spacing = harness.ctx().style().spacing.item_spacing.x;
+    assert_eq!(rect("X").left() - rect("B is wider").right(), item_spacing);
+}
@@ -5,21 +5,29 @@
 pub(crate) mod area;
 pub(crate) mod collapsing_header;
 mod combo_box;
+pub(crate) mod dock;
 pub(crate) mod frame;
+pub(crate) mod grid;
 pub(crate) mod panel;
 pub(crate) mod popup;
 pub(crate) mod resize;
 pub(crate) mod scroll_area;
+pub(crate) mod table;
+pub(crate) mod tree_view;
 pub(crate) mod window;
 
 pub use {
     area::Area,
     collapsing_header::*,
     combo_box::*,
+    dock::{Dock, DockLayout, DockNode, FloatingTabs, SplitDirection, TabGroup, TabViewer},
     frame::Frame,
-    panel::{CentralPanel, SidePanel, TopPanel},
+    grid::Grid,
+    panel::{BottomPanel, CentralPanel, SidePanel, TopPanel},
     popup::*,
     resize::Resize,
     scroll_area::ScrollArea,
+    table::{SortRequest, Table, TableColumn, TableResponse},
+    tree_view::{DropPosition, TreeAction, TreeView, TreeViewBuilder, TreeViewResponse},
     window::Window,
 };
@@ -2,11 +2,250 @@
 //! Together with `Window` and `Area`:s they are
 //! the only places where you can put you widgets.
 
+use std::ops::RangeInclusive;
+
 use crate::*;
 
 // ----------------------------------------------------------------------------
 
-/// A panel that covers the entire left side of the screen.
+/// The state of a resizable panel, kept in [`Memory`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// Width of a side panel, or height of a top or bottom panel (including the frame margins).
+    size: f32,
+}
+
+/// Which side of the screen a panel covers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Side {
+    /// Along which axis we can resize the panel: 0 for side panels, 1 for top and bottom panels.
+    fn axis(self) -> usize {
+        match self {
+            Side::Left | Side::Right => 0,
+            Side::Top | Side::Bottom => 1,
+        }
+    }
+
+    /// The part of `available_rect` covered by a panel of the given size.
+    fn panel_rect(self, available_rect: Rect, size: f32) -> Rect {
+        let mut rect = available_rect;
+        match self {
+            Side::Left => rect.max.x = rect.min.x + size,
+            Side::Right => rect.min.x = rect.max.x - size,
+            Side::Top => rect.max.y = rect.min.y + size,
+            Side::Bottom => rect.min.y = rect.max.y - size,
+        }
+        rect
+    }
+
+    /// The edge of the panel facing the rest of the screen.
+    fn inner_edge(self, panel_rect: Rect) -> f32 {
+        match self {
+            Side::Left => panel_rect.right(),
+            Side::Right => panel_rect.left(),
+            Side::Top => panel_rect.bottom(),
+            Side::Bottom => panel_rect.top(),
+        }
+    }
+
+    /// How big the panel would be if its inner edge was at `pos`.
+    fn size_at(self, available_rect: Rect, pos: Pos2) -> f32 {
+        match self {
+            Side::Left => pos.x - available_rect.left(),
+            Side::Right => available_rect.right() - pos.x,
+            Side::Top => pos.y - available_rect.top(),
+            Side::Bottom => available_rect.bottom() - pos.y,
+        }
+    }
+}
+
+/// What [`SidePanel`], [`TopPanel`] and [`BottomPanel`] have in common.
+struct Panel {
+    side: Side,
+    id: Id,
+    /// `None` means the height of a button.
+    default_size: Option<f32>,
+    resizable: bool,
+    size_range: RangeInclusive<f32>,
+    frame: Option<Frame>,
+}
+
+impl Panel {
+    fn new(side: Side, id_source: impl std::hash::Hash, default_size: Option<f32>) -> Self {
+        let size_range = match side {
+            Side::Left | Side::Right => 96.0..=f32::INFINITY,
+            Side::Top | Side::Bottom => 20.0..=f32::INFINITY,
+        };
+        Self {
+            side,
+            id: Id::new(id_source),
+            default_size,
+            resizable: false,
+            size_range,
+            frame: None,
+        }
+    }
+
+    fn show_animated<R>(
+        self,
+        ctx: &CtxRef,
+        is_expanded: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<(R, Response)> {
+        let how_expanded = ctx.animate_bool(self.id.with("animation"), is_expanded);
+        if how_expanded == 0.0 {
+            None
+        } else {
+            Some(self.show(ctx, how_expanded, add_contents))
+        }
+    }
+
+    /// `how_expanded` goes from `0.0` (hidden) to `1.0` (fully shown).
+    fn show<R>(
+        self,
+        ctx: &CtxRef,
+        how_expanded: f32,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        let Self {
+            side,
+            id,
+            default_size,
+            resizable,
+            size_range,
+            frame,
+        } = self;
+        let d = side.axis();
+        let frame = frame.unwrap_or_else(|| Frame::panel(&ctx.style()));
+        let default_size = default_size
+            .unwrap_or_else(|| ctx.style().spacing.interact_size.y + 2.0 * frame.margin.y);
+
+        let available_rect = ctx.available_rect();
+        let size = if resizable {
+            ctx.memory()
+                .panels
+                .get(&id)
+                .map_or(default_size, |state| state.size)
+        } else {
+            default_size
+        };
+        let mut size = clamp(size, size_range.clone());
+
+        let layer_id = ctx.viewport_id().background_layer();
+        let clip_rect = ctx.screen_rect();
+        let is_fully_expanded = how_expanded >= 1.0;
+
+        // Handle resizing before adding the contents, so that we get the drag and not them:
+        let mut resize_response = None;
+        if resizable && is_fully_expanded {
+            let edge = side.inner_edge(side.panel_rect(available_rect, size));
+            let grab_radius = ctx.style().interaction.resize_grab_radius_side;
+            let mut handle_rect = available_rect;
+            handle_rect.min[d] = edge - grab_radius;
+            handle_rect.max[d] = edge + grab_radius;
+            let response = ctx.interact(
+                clip_rect,
+                Vec2::zero(),
+                layer_id,
+                id.with("resize"),
+                handle_rect,
+                Sense::drag(),
+            );
+            if response.active {
+                if let Some(mouse_pos) = ctx.input().mouse.pos {
+                    size = clamp(side.size_at(available_rect, mouse_pos), size_range.clone());
+                }
+            }
+            if response.hovered || response.active {
+                ctx.output().cursor_icon = if d == 0 {
+                    CursorIcon::ResizeHorizontal
+                } else {
+                    CursorIcon::ResizeVertical
+                };
+            }
+            resize_response = Some(response);
+        }
+
+        // When animating, the contents slide in from the edge of the screen:
+        let panel_rect = side.panel_rect(available_rect, how_expanded * size);
+        let contents_rect = side.panel_rect(available_rect, size);
+        let mut slide = Vec2::zero();
+        slide[d] = match side {
+            Side::Left | Side::Top => panel_rect.max[d] - contents_rect.max[d],
+            Side::Right | Side::Bottom => panel_rect.min[d] - contents_rect.min[d],
+        };
+        let contents_rect = contents_rect.translate(slide);
+
+        let panel_clip_rect = if is_fully_expanded {
+            clip_rect
+        } else {
+            panel_rect.intersect(clip_rect)
+        };
+        let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, contents_rect, panel_clip_rect);
+        let r = frame.show(&mut panel_ui, |ui| {
+            let r = add_contents(ui);
+            ui.expand_to_include_rect(ui.max_rect_finite()); // Make sure the frame fills the panel
+            r
+        });
+
+        let panel_rect = if is_fully_expanded {
+            let used_rect = panel_ui.min_rect();
+            if resizable {
+                // The contents may need more space than we gave them:
+                let used_size = used_rect.size()[d];
+                if (used_size - size).abs() > 0.5 {
+                    ctx.request_repaint();
+                }
+                ctx.memory().panels.insert(id, State { size: used_size });
+            }
+            used_rect
+        } else {
+            panel_rect
+        };
+        let response = panel_ui.interact(panel_rect, id, Sense::hover());
+
+        if let Some(resize_response) = resize_response {
+            if resize_response.hovered || resize_response.active {
+                let stroke = ctx.style().interact(&resize_response).bg_stroke;
+                let edge = side.inner_edge(panel_rect);
+                let line = if d == 0 {
+                    [
+                        pos2(edge, panel_rect.top()),
+                        pos2(edge, panel_rect.bottom()),
+                    ]
+                } else {
+                    [
+                        pos2(panel_rect.left(), edge),
+                        pos2(panel_rect.right(), edge),
+                    ]
+                };
+                panel_ui.painter().line_segment(line, stroke);
+            }
+        }
+
+        let mut frame_state = ctx.frame_state();
+        match side {
+            Side::Left => frame_state.allocate_left_panel(panel_rect),
+            Side::Right => frame_state.allocate_right_panel(panel_rect),
+            Side::Top => frame_state.allocate_top_panel(panel_rect),
+            Side::Bottom => frame_state.allocate_bottom_panel(panel_rect),
+        }
+
+        (r, response)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A panel that covers the entire left or right side of the screen.
 ///
 /// `SidePanel`s should be added before adding any [`Window`]s.
 ///
@@ -14,54 +253,75 @@ use crate::*;
 /// # let mut ctx = egui::CtxRef::default();
 /// # ctx.begin_frame(Default::default());
 /// # let ctx = &ctx;
-/// egui::SidePanel::left("my_side_panel", 0.0).show(ctx, |ui| {
-///    ui.label("Hello World!");
-/// });
+/// egui::SidePanel::left("my_side_panel")
+///     .default_width(150.0)
+///     .resizable(true)
+///     .show(ctx, |ui| {
+///         ui.label("Hello World!");
+///     });
 /// ```
 pub struct SidePanel {
-    id: Id,
-    max_width: f32,
+    panel: Panel,
 }
 
 impl SidePanel {
     /// `id_source`: Something unique, e.g. `"my_side_panel"`.
-    /// The given `max_width` is a soft maximum (as always), and the actual panel may be smaller or larger.
-    pub fn left(id_source: impl std::hash::Hash, max_width: f32) -> Self {
+    /// The panel starts out [`Self::default_width`] wide, but will expand if the contents need more space.
+    pub fn left(id_source: impl std::hash::Hash) -> Self {
         Self {
-            id: Id::new(id_source),
-            max_width,
+            panel: Panel::new(Side::Left, id_source, Some(200.0)),
         }
     }
-}
 
-impl SidePanel {
-    pub fn show<R>(self, ctx: &CtxRef, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
-        let Self { id, max_width } = self;
-
-        let mut panel_rect = ctx.available_rect();
-        panel_rect.max.x = panel_rect.max.x.at_most(panel_rect.min.x + max_width);
+    /// `id_source`: Something unique, e.g. `"my_side_panel"`.
+    /// The panel starts out [`Self::default_width`] wide, but will expand if the contents need more space.
+    pub fn right(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            panel: Panel::new(Side::Right, id_source, Some(200.0)),
+        }
+    }
 
-        let layer_id = LayerId::background();
+    /// The width of the panel before the user has resized it. Default: `200.0`.
+    pub fn default_width(mut self, default_width: f32) -> Self {
+        self.panel.default_size = Some(default_width);
+        self
+    }
 
-        let clip_rect = ctx.input().screen_rect();
-        let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, panel_rect, clip_rect);
+    /// Can the user resize the panel by dragging its inner edge? Default: `false`.
+    /// The width is remembered in [`Memory`].
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
 
-        let frame = Frame::panel(&ctx.style());
-        let (r, used_space) = frame.show(&mut panel_ui, |ui| {
-            let r = add_contents(ui);
-            let used_space = ui.min_rect();
-            ui.set_min_height(ui.max_rect_finite().height()); // Make sure the frame fills the full height
-            (r, used_space)
-        });
+    /// How narrow or wide the panel can be, also when the user resizes it. Default: `96.0..=f32::INFINITY`.
+    pub fn width_range(mut self, width_range: RangeInclusive<f32>) -> Self {
+        self.panel.size_range = width_range;
+        self
+    }
 
-        let panel_rect = panel_ui.min_rect();
-        let response = panel_ui.interact(panel_rect, id, Sense::hover());
+    /// Change the background color, margins, etc.
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.panel.frame = Some(frame);
+        self
+    }
+}
 
-        // Only inform ctx about what we actually used, so we can shrink the native window to fit.
-        ctx.frame_state()
-            .allocate_left_panel(used_space.expand2(frame.margin));
+impl SidePanel {
+    pub fn show<R>(self, ctx: &CtxRef, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
+        self.panel.show(ctx, 1.0, add_contents)
+    }
 
-        (r, response)
+    /// Show the panel if `is_expanded`, sliding it in and out from the side of the screen when this changes.
+    ///
+    /// Returns `None` when the panel is completely hidden.
+    pub fn show_animated<R>(
+        self,
+        ctx: &CtxRef,
+        is_expanded: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<(R, Response)> {
+        self.panel.show_animated(ctx, is_expanded, add_contents)
     }
 }
 
@@ -80,8 +340,7 @@ impl SidePanel {
 /// });
 /// ```
 pub struct TopPanel {
-    id: Id,
-    max_height: Option<f32>,
+    panel: Panel,
 }
 
 impl TopPanel {
@@ -90,41 +349,125 @@ impl TopPanel {
     /// but the panel will expand as needed.
     pub fn top(id_source: impl std::hash::Hash) -> Self {
         Self {
-            id: Id::new(id_source),
-            max_height: None,
+            panel: Panel::new(Side::Top, id_source, None),
         }
     }
+
+    /// The height of the panel before the user has resized it.
+    /// Default: that of `interact_size.y` (i.e. a button).
+    pub fn default_height(mut self, default_height: f32) -> Self {
+        self.panel.default_size = Some(default_height);
+        self
+    }
+
+    /// Can the user resize the panel by dragging its bottom edge? Default: `false`.
+    /// The height is remembered in [`Memory`].
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
+
+    /// How low or high the panel can be, also when the user resizes it. Default: `20.0..=f32::INFINITY`.
+    pub fn height_range(mut self, height_range: RangeInclusive<f32>) -> Self {
+        self.panel.size_range = height_range;
+        self
+    }
+
+    /// Change the background color, margins, etc.
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.panel.frame = Some(frame);
+        self
+    }
 }
 
 impl TopPanel {
     pub fn show<R>(self, ctx: &CtxRef, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
-        let Self { id, max_height } = self;
-        let max_height = max_height.unwrap_or_else(|| ctx.style().spacing.interact_size.y);
+        self.panel.show(ctx, 1.0, add_contents)
+    }
 
-        let mut panel_rect = ctx.available_rect();
-        panel_rect.max.y = panel_rect.max.y.at_most(panel_rect.min.y + max_height);
+    /// Show the panel if `is_expanded`, sliding it in and out from the top of the screen when this changes.
+    ///
+    /// Returns `None` when the panel is completely hidden.
+    pub fn show_animated<R>(
+        self,
+        ctx: &CtxRef,
+        is_expanded: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<(R, Response)> {
+        self.panel.show_animated(ctx, is_expanded, add_contents)
+    }
+}
 
-        let layer_id = LayerId::background();
+// ----------------------------------------------------------------------------
 
-        let clip_rect = ctx.input().screen_rect();
-        let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, panel_rect, clip_rect);
+/// A panel that covers the entire bottom side of the screen.
+///
+/// `BottomPanel`s should be added before adding any [`Window`]s.
+///
+/// ```
+/// # let mut ctx = egui::CtxRef::default();
+/// # ctx.begin_frame(Default::default());
+/// # let ctx = &ctx;
+/// egui::BottomPanel::bottom("my_status_bar").show(ctx, |ui| {
+///    ui.label("Ready");
+/// });
+/// ```
+pub struct BottomPanel {
+    panel: Panel,
+}
 
-        let frame = Frame::panel(&ctx.style());
-        let (r, used_space) = frame.show(&mut panel_ui, |ui| {
-            let r = add_contents(ui);
-            let used_space = ui.min_rect();
-            ui.set_min_width(ui.max_rect_finite().width()); // Make the frame fill full width
-            (r, used_space)
-        });
+impl BottomPanel {
+    /// `id_source`: Something unique, e.g. `"my_bottom_panel"`.
+    /// Default height is that of `interact_size.y` (i.e. a button),
+    /// but the panel will expand as needed.
+    pub fn bottom(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            panel: Panel::new(Side::Bottom, id_source, None),
+        }
+    }
 
-        let panel_rect = panel_ui.min_rect();
-        let response = panel_ui.interact(panel_rect, id, Sense::hover());
+    /// The height of the panel before the user has resized it.
+    /// Default: that of `interact_size.y` (i.e. a button).
+    pub fn default_height(mut self, default_height: f32) -> Self {
+        self.panel.default_size = Some(default_height);
+        self
+    }
 
-        // Only inform ctx about what we actually used, so we can shrink the native window to fit.
-        ctx.frame_state()
-            .allocate_top_panel(used_space.expand2(frame.margin));
+    /// Can the user resize the panel by dragging its top edge? Default: `false`.
+    /// The height is remembered in [`Memory`].
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
 
-        (r, response)
+    /// How low or high the panel can be, also when the user resizes it. Default: `20.0..=f32::INFINITY`.
+    pub fn height_range(mut self, height_range: RangeInclusive<f32>) -> Self {
+        self.panel.size_range = height_range;
+        self
+    }
+
+    /// Change the background color, margins, etc.
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.panel.frame = Some(frame);
+        self
+    }
+}
+
+impl BottomPanel {
+    pub fn show<R>(self, ctx: &CtxRef, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
+        self.panel.show(ctx, 1.0, add_contents)
+    }
+
+    /// Show the panel if `is_expanded`, sliding it in and out from the bottom of the screen when this changes.
+    ///
+    /// Returns `None` when the panel is completely hidden.
+    pub fn show_animated<R>(
+        self,
+        ctx: &CtxRef,
+        is_expanded: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<(R, Response)> {
+        self.panel.show_animated(ctx, is_expanded, add_contents)
     }
 }
 
@@ -162,10 +505,14 @@ impl CentralPanel {
 
         let panel_rect = ctx.available_rect();
 
-        let layer_id = LayerId::background();
-        let id = Id::new("central_panel");
+        let viewport_id = ctx.viewport_id();
+        let layer_id = viewport_id.background_layer();
+        let mut id = Id::new("central_panel");
+        if viewport_id != crate::viewport::ViewportId::root() {
+            id = id.with(viewport_id); // Each viewport has its own central panel
+        }
 
-        let clip_rect = ctx.input().screen_rect();
+        let clip_rect = ctx.screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, panel_rect, clip_rect);
 
         let frame = frame.unwrap_or_else(|| Frame::central_panel(&ctx.style()));
@@ -187,3 +534,80 @@ impl CentralPanel {
         (r, response)
     }
 }
+
+#[test]
+fn test_panels() {
+    let mut harness = testing::Harness::new(vec2(800.0, 600.0));
+    let mut show_right = true;
+    let mut available_rect = Rect::nothing();
+    let app = |ctx: &CtxRef, show_right: bool, available_rect: &mut Rect| {
+        BottomPanel::bottom("bottom").show(ctx, |ui| ui.label("Status"));
+        SidePanel::right("right")
+            .resizable(true)
+            .width_range(150.0..=300.0)
+            .show_animated(ctx, show_right, |ui| ui.label("Right"));
+        TopPanel::top("top").show(ctx, |ui| ui.label("Top"));
+        SidePanel::left("left")
+            .default_width(100.0)
+            .show(ctx, |ui| ui.label("Left"));
+        *available_rect = ctx.available_rect();
+    };
+
+    harness.run(|ctx| app(ctx, show_right, &mut available_rect));
+    let bottom_height = 600.0 - available_rect.bottom();
+    assert!(bottom_height > 0.0);
+    assert_eq!(available_rect.left(), 100.0);
+    assert_eq!(available_rect.right(), 600.0);
+    assert!(available_rect.top() > 0.0);
+
+    // Drag the inner edge of the right panel, past its max width:
+    harness.move_mouse_to(pos2(600.0, 300.0));
+    harness.set_mouse_down(true);
+    harness.run(|ctx| app(ctx, show_right, &mut available_rect));
+    harness.move_mouse_to(pos2(400.0, 300.0));
+    harness.run(|ctx| app(ctx, show_right, &mut available_rect));
+    harness.set_mouse_down(false);
+    harness.run(|ctx| app(ctx, show_right, &mut available_rect));
+    assert_eq!(available_rect.right(), 500.0);
+
+    // Collapse it:
+    show_right = false;
+    harness.run_until_settled(|ctx| app(ctx, show_right, &mut available_rect), 100);
+    assert_eq!(available_rect.right(), 800.0);
+    assert_eq!(600.0 - available_rect.bottom(), bottom_height);
+
+    // It remembers its width when expanded again:
+    show_right = true;
+    harness.run_until_settled(|ctx| app(ctx, show_right, &mut available_rect), 100);
+    assert_eq!(available_rect.right(), 500.0);
+}
+
+#[test]
+fn test_fixed_panel_size() {
+    let mut harness = testing::Harness::new(vec2(800.0, 600.0));
+    let mut available_rect = Rect::nothing();
+    let app = |ctx: &CtxRef, width: f32, content_width: f32, available_rect: &mut Rect| {
+        SidePanel::left("left")
+            .default_width(width)
+            .show(ctx, |ui| {
+                ui.allocate_space(vec2(content_width, 10.0));
+            });
+        *available_rect = ctx.available_rect();
+    };
+
+    // Wide contents make the panel wider:
+    harness.run(|ctx| app(ctx, 100.0, 300.0, &mut available_rect));
+    assert!(available_rect.left() > 300.0);
+
+    // …but only for as long as they are there:
+    harness.run(|ctx| app(ctx, 100.0, 10.0, &mut available_rect));
+    assert_eq!(available_rect.left(), 100.0);
+
+    // A new default width is used right away:
+    harness.run(|ctx| app(ctx, 150.0, 10.0, &mut available_rect));
+    assert_eq!(available_rect.left(), 150.0);
+
+    // The width is kept within the `width_range`, so a panel can't end up empty:
+    harness.run(|ctx| app(ctx, 0.0, 10.0, &mut available_rect));
+    assert_eq!(available_rect.left(), 96.0);
+}
@@ -22,9 +22,8 @@ pub fn show_tooltip(ctx: &CtxRef, add_contents: impl FnOnce(&mut Ui)) {
     } else if let Some(mouse_pos) = ctx.input().mouse.pos {
         let expected_size = vec2(ctx.style().spacing.tooltip_width, 32.0);
         let position = mouse_pos + vec2(16.0, 16.0);
-        let position = position.min(ctx.input().screen_rect().right_bottom() - expected_size);
-        let position = position.max(ctx.input().screen_rect().left_top());
-        position
+        let position = position.min(ctx.screen_rect().right_bottom() - expected_size);
+        position.max(ctx.screen_rect().left_top())
     } else if ctx.memory().everything_is_visible() {
         Pos2::default()
     } else {
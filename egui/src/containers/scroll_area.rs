@@ -7,52 +7,97 @@ pub(crate) struct State {
     /// Positive offset means scrolling down/right
     offset: Vec2,
 
-    show_scroll: bool,
+    /// Were the scroll bars shown last frame? `[horizontal, vertical]`.
+    show_scroll: [bool; 2],
 
     /// Momentum, used for kinetic scrolling
     #[cfg_attr(feature = "serde", serde(skip))]
     pub vel: Vec2,
-    /// Mouse offset relative to the top of the handle when started moving the handle.
-    scroll_start_offset_from_top: Option<f32>,
+    /// Mouse offset relative to the top/left of the handle when started moving the handle.
+    scroll_start_offset_from_top_left: [Option<f32>; 2],
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
             offset: Vec2::zero(),
-            show_scroll: false,
+            show_scroll: [false; 2],
             vel: Vec2::zero(),
-            scroll_start_offset_from_top: None,
+            scroll_start_offset_from_top_left: [None; 2],
         }
     }
 }
 
-// TODO: rename VScroll
-/// Add vertical scrolling to a contained [`Ui`].
+/// Add vertical and/or horizontal scrolling to a contained [`Ui`].
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// egui::ScrollArea::both().max_height(200.0).show(ui, |ui| {
+///     ui.label("This label can be scrolled both horizontally and vertically");
+/// });
+/// ```
 #[derive(Clone, Debug)]
 pub struct ScrollArea {
-    max_height: f32,
+    /// Do we have horizontal/vertical scrolling? `[horizontal, vertical]`.
+    has_bar: [bool; 2],
+    max_size: Vec2,
     always_show_scroll: bool,
     id_source: Option<Id>,
-    offset: Option<Vec2>,
+    offset: [Option<f32>; 2],
 }
 
 impl ScrollArea {
     /// Will make the area be as high as it is allowed to be (i.e. fill the [`Ui`] it is in)
     pub fn auto_sized() -> Self {
-        Self::from_max_height(f32::INFINITY)
+        Self::vertical()
     }
 
     /// Use `f32::INFINITY` if you want the scroll area to expand to fit the surrounding Ui
     pub fn from_max_height(max_height: f32) -> Self {
+        Self::vertical().max_height(max_height)
+    }
+
+    /// Scroll vertically. The area will be as high as it is allowed to be.
+    pub fn vertical() -> Self {
+        Self::new([false, true])
+    }
+
+    /// Scroll horizontally. The area will be as wide as it is allowed to be.
+    pub fn horizontal() -> Self {
+        Self::new([true, false])
+    }
+
+    /// Scroll both horizontally and vertically.
+    pub fn both() -> Self {
+        Self::new([true, true])
+    }
+
+    fn new(has_bar: [bool; 2]) -> Self {
         Self {
-            max_height,
+            has_bar,
+            max_size: Vec2::infinity(),
             always_show_scroll: false,
             id_source: None,
-            offset: None,
+            offset: [None; 2],
         }
     }
 
+    /// The maximum width of the outer frame of the scroll area.
+    ///
+    /// Use `f32::INFINITY` if you want the scroll area to expand to fit the surrounding `Ui` (default).
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_size.x = max_width;
+        self
+    }
+
+    /// The maximum height of the outer frame of the scroll area.
+    ///
+    /// Use `f32::INFINITY` if you want the scroll area to expand to fit the surrounding `Ui` (default).
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_size.y = max_height;
+        self
+    }
+
     /// If `false` (default), the scroll bar will be hidden when not needed/
     /// If `true`, the scroll bar will always be displayed even if not needed.
     pub fn always_show_scroll(mut self, always_show_scroll: bool) -> Self {
@@ -66,12 +111,29 @@ impl ScrollArea {
         self
     }
 
-    /// Set the vertical scroll offset position.
+    #[deprecated = "Use `ScrollArea::vertical_scroll_offset`, or `ScrollArea::scroll_offset_vec2` to set both offsets"]
+    pub fn scroll_offset(self, offset: f32) -> Self {
+        self.vertical_scroll_offset(offset)
+    }
+
+    /// Set the horizontal and vertical scroll offset position.
     ///
     /// See also: [`Ui::scroll_to_cursor`](crate::ui::Ui::scroll_to_cursor) and
     /// [`Response::scroll_to_me`](crate::types::Response::scroll_to_me)
-    pub fn scroll_offset(mut self, offset: f32) -> Self {
-        self.offset = Some(Vec2::new(0.0, offset));
+    pub fn scroll_offset_vec2(mut self, offset: Vec2) -> Self {
+        self.offset = [Some(offset.x), Some(offset.y)];
+        self
+    }
+
+    /// Set the horizontal scroll offset position, leaving the vertical one as it is.
+    pub fn horizontal_scroll_offset(mut self, offset: f32) -> Self {
+        self.offset[0] = Some(offset);
+        self
+    }
+
+    /// Set the vertical scroll offset position, leaving the horizontal one as it is.
+    pub fn vertical_scroll_offset(mut self, offset: f32) -> Self {
+        self.offset[1] = Some(offset);
         self
     }
 }
@@ -79,7 +141,10 @@ impl ScrollArea {
 struct Prepared {
     id: Id,
     state: State,
-    current_scroll_bar_width: f32,
+    has_bar: [bool; 2],
+    /// How much of the outer size the scroll bars are using, i.e.
+    /// `x` is the width of the vertical bar and `y` the height of the horizontal bar.
+    current_bar_use: Vec2,
     always_show_scroll: bool,
     inner_rect: Rect,
     content_ui: Ui,
@@ -88,7 +153,8 @@ struct Prepared {
 impl ScrollArea {
     fn begin(self, ui: &mut Ui) -> Prepared {
         let Self {
-            max_height,
+            has_bar,
+            max_size,
             always_show_scroll,
             id_source,
             offset,
@@ -105,8 +171,10 @@ impl ScrollArea {
             .cloned()
             .unwrap_or_default();
 
-        if let Some(offset) = offset {
-            state.offset = offset;
+        for d in 0..2 {
+            if let Some(offset) = offset[d] {
+                state.offset[d] = offset;
+            }
         }
 
         // content: size of contents (generally large; that's why we want scroll bars)
@@ -115,40 +183,56 @@ impl ScrollArea {
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        let current_scroll_bar_width = if always_show_scroll {
-            max_scroll_bar_width
-        } else {
-            max_scroll_bar_width * ui.ctx().animate_bool(id, state.show_scroll)
-        };
+        let mut current_bar_use = Vec2::zero();
+        for d in 0..2 {
+            if has_bar[d] {
+                // The vertical bar (d = 1) takes up width (x), and vice versa:
+                current_bar_use[1 - d] = if always_show_scroll {
+                    max_scroll_bar_width
+                } else {
+                    max_scroll_bar_width * ctx.animate_bool(id.with(d), state.show_scroll[d])
+                };
+            }
+        }
 
         let available_outer = ui.available_rect_before_wrap();
 
         let outer_size = vec2(
-            available_outer.width(),
-            available_outer.height().at_most(max_height),
+            available_outer.width().at_most(max_size.x),
+            available_outer.height().at_most(max_size.y),
         );
 
-        let inner_size = outer_size - vec2(current_scroll_bar_width, 0.0);
+        let inner_size = outer_size - current_bar_use;
         let inner_rect = Rect::from_min_size(available_outer.min, inner_size);
 
+        let mut content_max_size = inner_size;
+        for d in 0..2 {
+            if has_bar[d] {
+                content_max_size[d] = f32::INFINITY;
+            }
+        }
+
         let mut content_ui = ui.child_ui(
-            Rect::from_min_size(
-                inner_rect.min - state.offset,
-                vec2(inner_size.x, f32::INFINITY),
-            ),
+            Rect::from_min_size(inner_rect.min - state.offset, content_max_size),
             *ui.layout(),
         );
         let mut content_clip_rect = inner_rect.expand(ui.style().visuals.clip_rect_margin);
         content_clip_rect = content_clip_rect.intersect(ui.clip_rect());
-        content_clip_rect.max.x = ui.clip_rect().max.x - current_scroll_bar_width; // Nice handling of forced resizing beyond the possible
+        for d in 0..2 {
+            if !has_bar[d] {
+                // Nice handling of forced resizing beyond the possible:
+                content_clip_rect.max[d] = ui.clip_rect().max[d] - current_bar_use[d];
+            }
+        }
         content_ui.set_clip_rect(content_clip_rect);
 
         Prepared {
             id,
             state,
+            has_bar,
             always_show_scroll,
             inner_rect,
-            current_scroll_bar_width,
+            current_bar_use,
             content_ui,
         }
     }
@@ -159,62 +243,155 @@ impl ScrollArea {
         prepared.end(ui);
         ret
     }
+
+    /// Efficiently show only the visible part of a large number of rows.
+    ///
+    /// All rows must have the same height, `row_height_sans_spacing`
+    /// (the vertical `item_spacing` is added between the rows).
+    /// The full height of all rows is allocated, so the scroll bar works as usual,
+    /// but `add_row` is only called for the rows that are visible.
+    ///
+    /// Each row gets its own child `Ui`, with an `Id` made from the row index,
+    /// so the widgets in a row keep their state (e.g. that of a `TextEdit` or `CollapsingHeader`)
+    /// no matter how far you have scrolled.
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// let text_style = egui::TextStyle::Body;
+    /// let row_height = ui.fonts()[text_style].row_height();
+    /// let num_rows = 10_000;
+    /// egui::ScrollArea::auto_sized().show_rows(ui, row_height, num_rows, |ui, row| {
+    ///     let text = format!("Row {}/{}", row + 1, num_rows);
+    ///     ui.label(text);
+    /// });
+    /// ```
+    pub fn show_rows(
+        self,
+        ui: &mut Ui,
+        row_height_sans_spacing: f32,
+        total_rows: usize,
+        mut add_row: impl FnMut(&mut Ui, usize),
+    ) {
+        let mut prepared = self.begin(ui);
+        let viewport = prepared.viewport();
+        let content_ui = &mut prepared.content_ui;
+
+        let spacing = content_ui.style().spacing.item_spacing;
+        // Guard against zero-height rows, so we don't divide by zero below:
+        let row_height_with_spacing = (row_height_sans_spacing + spacing.y).at_least(f32::EPSILON);
+        content_ui.set_min_height(
+            (row_height_with_spacing * total_rows as f32 - spacing.y).at_least(0.0),
+        );
+
+        let min_row = (viewport.min.y / row_height_with_spacing)
+            .floor()
+            .at_least(0.0) as usize;
+        let max_row =
+            ((viewport.max.y / row_height_with_spacing).ceil() as usize).saturating_add(1);
+        let max_row = max_row.at_most(total_rows);
+        let min_row = min_row.at_most(max_row);
+
+        let max_rect = content_ui.max_rect();
+        let layout = *content_ui.layout();
+        for row in min_row..max_row {
+            let row_rect = Rect::from_min_size(
+                pos2(
+                    max_rect.left(),
+                    max_rect.top() + row as f32 * row_height_with_spacing,
+                ),
+                vec2(max_rect.width(), row_height_sans_spacing),
+            );
+            let mut row_ui = content_ui.child_ui_with_id_source(row_rect, layout, row);
+            add_row(&mut row_ui, row);
+            content_ui.expand_to_include_rect(row_ui.min_rect());
+        }
+
+        prepared.end(ui);
+    }
 }
 
 impl Prepared {
+    /// The part of the contents that is visible, relative to the top left of the contents.
+    fn viewport(&self) -> Rect {
+        Rect::from_min_size(
+            pos2(self.state.offset.x, self.state.offset.y),
+            self.inner_rect.size(),
+        )
+    }
+
     fn end(self, ui: &mut Ui) {
+        #![allow(clippy::needless_range_loop)] // We loop over the two axes, `d`.
+
         let Prepared {
             id,
             mut state,
+            has_bar,
             inner_rect,
             always_show_scroll,
-            mut current_scroll_bar_width,
+            mut current_bar_use,
             content_ui,
         } = self;
 
         let content_size = content_ui.min_size();
 
-        // We take the scroll target so only this ScrollArea will use it.
-        let scroll_target = content_ui.ctx().frame_state().scroll_target.take();
-        if let Some((scroll_y, align)) = scroll_target {
-            let center_factor = align.scroll_center_factor();
+        for d in 0..2 {
+            if has_bar[d] {
+                // We take the scroll target so only this ScrollArea will use it.
+                let scroll_target = content_ui.ctx().frame_state().scroll_target[d].take();
+                if let Some((scroll, align)) = scroll_target {
+                    let center_factor = align.scroll_center_factor();
 
-            let top = content_ui.min_rect().top();
-            let visible_range = top..=top + content_ui.clip_rect().height();
-            let offset_y = scroll_y - lerp(visible_range, center_factor);
+                    let min = content_ui.min_rect().min[d];
+                    let visible_range = min..=min + content_ui.clip_rect().size()[d];
+                    let offset = scroll - lerp(visible_range, center_factor);
 
-            let mut spacing = ui.style().spacing.item_spacing.y;
+                    let mut spacing = ui.style().spacing.item_spacing[d];
 
-            // Depending on the alignment we need to add or subtract the spacing
-            spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
+                    // Depending on the alignment we need to add or subtract the spacing
+                    spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
 
-            state.offset.y = offset_y + spacing;
+                    state.offset[d] = offset + spacing;
+                }
+            }
         }
 
-        let width = if inner_rect.width().is_finite() {
-            inner_rect.width().max(content_size.x) // Expand width to fit content
-        } else {
-            // ScrollArea is in an infinitely wide parent
-            content_size.x
+        let inner_rect = {
+            let mut inner_size = inner_rect.size();
+            for d in 0..2 {
+                if !has_bar[d] {
+                    inner_size[d] = if d == 0 && inner_size[d].is_finite() {
+                        inner_size[d].max(content_size[d]) // Expand width to fit content
+                    } else {
+                        // Follow the content, e.g. when the ScrollArea is in an infinitely wide parent
+                        content_size[d]
+                    };
+                }
+            }
+            Rect::from_min_size(inner_rect.min, inner_size)
         };
 
-        let inner_rect = Rect::from_min_size(inner_rect.min, vec2(width, inner_rect.height()));
-
-        let outer_rect = Rect::from_min_size(
-            inner_rect.min,
-            inner_rect.size() + vec2(current_scroll_bar_width, 0.0),
-        );
-
-        let content_is_too_small = content_size.y > inner_rect.height();
+        let mut content_is_too_large = [false; 2];
+        for d in 0..2 {
+            content_is_too_large[d] = has_bar[d] && content_size[d] > inner_rect.size()[d];
+        }
 
-        if content_is_too_small {
+        if content_is_too_large[0] || content_is_too_large[1] {
             // Drag contents to scroll (for touch screens mostly):
             let content_response = ui.interact(inner_rect, id.with("area"), Sense::drag());
 
             let input = ui.input();
-            if content_response.active {
-                state.offset.y -= input.mouse.delta.y;
-                state.vel = input.mouse.velocity;
+            if content_response.active && input.touch.multi_touch().is_some() {
+                // Panning with two fingers scrolls via `scroll_delta` instead.
+                state.vel = Vec2::zero();
+            } else if content_response.active {
+                for d in 0..2 {
+                    if has_bar[d] {
+                        state.offset[d] -= input.mouse.delta[d];
+                        state.vel[d] = input.mouse.velocity[d];
+                    } else {
+                        state.vel[d] = 0.0;
+                    }
+                }
             } else {
                 let stop_speed = 20.0; // Pixels per second.
                 let friction_coeff = 1000.0; // Pixels per second squared.
@@ -227,100 +404,128 @@ impl Prepared {
                     state.vel -= friction * state.vel.normalized();
                     // Offset has an inverted coordinate system compared to
                     // the velocity, so we subtract it instead of adding it
-                    state.offset.y -= state.vel.y * dt;
+                    state.offset -= state.vel * dt;
                     ui.ctx().request_repaint();
                 }
             }
         }
 
-        let max_offset = content_size.y - inner_rect.height();
+        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
+
+        let max_offset = content_size - inner_rect.size();
         if ui.rect_contains_mouse(outer_rect) {
             let mut frame_state = ui.ctx().frame_state();
-            let scroll_delta = frame_state.scroll_delta;
-
-            let scrolling_up = state.offset.y > 0.0 && scroll_delta.y > 0.0;
-            let scrolling_down = state.offset.y < max_offset && scroll_delta.y < 0.0;
-
-            if scrolling_up || scrolling_down {
-                state.offset.y -= scroll_delta.y;
-                // Clear scroll delta so no parent scroll will use it.
-                frame_state.scroll_delta = Vec2::zero();
+            for d in 0..2 {
+                if has_bar[d] {
+                    let scroll_delta = frame_state.scroll_delta[d];
+
+                    let scrolling_up = state.offset[d] > 0.0 && scroll_delta > 0.0;
+                    let scrolling_down = state.offset[d] < max_offset[d] && scroll_delta < 0.0;
+
+                    if scrolling_up || scrolling_down {
+                        state.offset[d] -= scroll_delta;
+                        // Clear scroll delta so no parent scroll will use it.
+                        frame_state.scroll_delta[d] = 0.0;
+                    }
+                }
             }
         }
 
-        let show_scroll_this_frame = content_is_too_small || always_show_scroll;
+        let mut show_scroll_this_frame = [false; 2];
+        for d in 0..2 {
+            show_scroll_this_frame[d] =
+                content_is_too_large[d] || (always_show_scroll && has_bar[d]);
+        }
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        if show_scroll_this_frame && current_scroll_bar_width <= 0.0 {
-            // Avoid frame delay; start showing scroll bar right away:
-            current_scroll_bar_width = max_scroll_bar_width * ui.ctx().animate_bool(id, true);
+        for d in 0..2 {
+            if show_scroll_this_frame[d] && current_bar_use[1 - d] <= 0.0 {
+                // Avoid frame delay; start showing scroll bar right away:
+                current_bar_use[1 - d] =
+                    max_scroll_bar_width * ui.ctx().animate_bool(id.with(d), true);
+            }
         }
 
-        if current_scroll_bar_width > 0.0 {
-            let animation_t = current_scroll_bar_width / max_scroll_bar_width;
+        for d in 0..2 {
+            let bar_width = current_bar_use[1 - d];
+            if bar_width <= 0.0 {
+                continue;
+            }
+
+            let animation_t = bar_width / max_scroll_bar_width;
             // margin between contents and scroll bar
             let margin = animation_t * ui.style().spacing.item_spacing.x;
-            let left = inner_rect.right() + margin;
-            let right = outer_rect.right();
-            let corner_radius = (right - left) / 2.0;
-            let top = inner_rect.top();
-            let bottom = inner_rect.bottom();
-
-            let outer_scroll_rect = Rect::from_min_max(
-                pos2(left, inner_rect.top()),
-                pos2(right, inner_rect.bottom()),
-            );
+            // The bar goes to the right of (d = 1) or below (d = 0) the contents:
+            let cross_min = inner_rect.max[1 - d] + margin;
+            let cross_max = outer_rect.max[1 - d];
+            let corner_radius = (cross_max - cross_min) / 2.0;
+            let main_min = inner_rect.min[d];
+            let main_max = inner_rect.max[d];
+
+            let bar_rect = |main_start: f32, main_end: f32| {
+                if d == 0 {
+                    Rect::from_min_max(pos2(main_start, cross_min), pos2(main_end, cross_max))
+                } else {
+                    Rect::from_min_max(pos2(cross_min, main_start), pos2(cross_max, main_end))
+                }
+            };
+
+            let outer_scroll_rect = bar_rect(main_min, main_max);
 
             let from_content =
-                |content_y| remap_clamp(content_y, 0.0..=content_size.y, top..=bottom);
+                |content| remap_clamp(content, 0.0..=content_size[d], main_min..=main_max);
 
-            let handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
+            let handle_rect = bar_rect(
+                from_content(state.offset[d]),
+                from_content(state.offset[d] + inner_rect.size()[d]),
             );
 
-            let interact_id = id.with("vertical");
-            let response = ui.interact(outer_scroll_rect, interact_id, Sense::click_and_drag());
+            let interact_id = id.with(if d == 0 { "horizontal" } else { "vertical" });
+            let response = ui.interact(
+                outer_scroll_rect,
+                interact_id,
+                Sense::click_and_drag().not_focusable(),
+            );
 
             if response.active {
                 if let Some(mouse_pos) = ui.input().mouse.pos {
-                    let scroll_start_offset_from_top =
-                        state.scroll_start_offset_from_top.get_or_insert_with(|| {
+                    let scroll_start_offset_from_top_left =
+                        state.scroll_start_offset_from_top_left[d].get_or_insert_with(|| {
                             if handle_rect.contains(mouse_pos) {
-                                mouse_pos.y - handle_rect.top()
+                                mouse_pos[d] - handle_rect.min[d]
                             } else {
-                                let handle_top_pos_at_bottom = bottom - handle_rect.height();
+                                let handle_top_pos_at_bottom = main_max - handle_rect.size()[d];
                                 // Calculate the new handle top position, centering the handle on the mouse.
                                 let new_handle_top_pos = clamp(
-                                    mouse_pos.y - handle_rect.height() / 2.0,
-                                    top..=handle_top_pos_at_bottom,
+                                    mouse_pos[d] - handle_rect.size()[d] / 2.0,
+                                    main_min..=handle_top_pos_at_bottom,
                                 );
-                                mouse_pos.y - new_handle_top_pos
+                                mouse_pos[d] - new_handle_top_pos
                             }
                         });
 
-                    let new_handle_top = mouse_pos.y - *scroll_start_offset_from_top;
-                    state.offset.y = remap(new_handle_top, top..=bottom, 0.0..=content_size.y);
+                    let new_handle_top = mouse_pos[d] - *scroll_start_offset_from_top_left;
+                    state.offset[d] =
+                        remap(new_handle_top, main_min..=main_max, 0.0..=content_size[d]);
                 }
             } else {
-                state.scroll_start_offset_from_top = None;
+                state.scroll_start_offset_from_top_left[d] = None;
             }
 
-            state.offset.y = state.offset.y.max(0.0);
-            state.offset.y = state.offset.y.min(max_offset);
+            state.offset[d] = state.offset[d].max(0.0);
+            state.offset[d] = state.offset[d].min(max_offset[d]);
 
             // Avoid frame-delay by calculating a new handle rect:
-            let mut handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
+            let mut handle_rect = bar_rect(
+                from_content(state.offset[d]),
+                from_content(state.offset[d] + inner_rect.size()[d]),
             );
-            let min_handle_height = (2.0 * corner_radius).max(8.0);
-            if handle_rect.size().y < min_handle_height {
-                handle_rect = Rect::from_center_size(
-                    handle_rect.center(),
-                    vec2(handle_rect.size().x, min_handle_height),
-                );
+            let min_handle_size = (2.0 * corner_radius).max(8.0);
+            if handle_rect.size()[d] < min_handle_size {
+                let mut size = handle_rect.size();
+                size[d] = min_handle_size;
+                handle_rect = Rect::from_center_size(handle_rect.center(), size);
             }
 
             let visuals = ui.style().interact(&response);
@@ -342,24 +547,152 @@ impl Prepared {
             });
         }
 
-        let size = vec2(
-            outer_rect.size().x,
-            outer_rect.size().y.min(content_size.y), // shrink if content is so small that we don't need scroll bars
-        );
+        let mut size = outer_rect.size();
+        for d in 0..2 {
+            if has_bar[d] {
+                // shrink if content is so small that we don't need scroll bars
+                size[d] = size[d].min(content_size[d] + current_bar_use[d]);
+            }
+        }
         ui.advance_cursor_after_rect(Rect::from_min_size(outer_rect.min, size));
 
         if show_scroll_this_frame != state.show_scroll {
             ui.ctx().request_repaint();
         }
 
-        state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
-        state.offset.y = state.offset.y.max(0.0);
+        for d in 0..2 {
+            state.offset[d] = state.offset[d].min(content_size[d] - inner_rect.size()[d]);
+            state.offset[d] = state.offset[d].max(0.0);
+        }
         state.show_scroll = show_scroll_this_frame;
 
         ui.memory().scroll_areas.insert(id, state);
     }
 }
 
-fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
+pub(crate) fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
     ui.style().spacing.item_spacing.x + 16.0
 }
+
+#[test]
+fn test_show_rows() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut rows = vec![];
+    let mut empty_rows = vec![];
+    harness.run(|ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let row_height = 20.0 - ui.style().spacing.item_spacing.y;
+            ScrollArea::from_max_height(100.0)
+                .vertical_scroll_offset(200.0)
+                .show_rows(ui, row_height, 1000, |ui, row| {
+                    ui.label(format!("Row {}", row));
+                    rows.push(row);
+                });
+
+            // Rows without any height must not make us divide by zero:
+            ui.style_mut().spacing.item_spacing.y = 0.0;
+            ScrollArea::from_max_height(100.0)
+                .id_source("empty rows")
+                .show_rows(ui, 0.0, 10, |_ui, row| empty_rows.push(row));
+        });
+    });
+    // Rows 10-15 are visible, and one extra is laid out below:
+    assert_eq!(rows, (10..16).collect::<Vec<_>>());
+    assert!(harness.widget("Row 9").is_none());
+    assert_eq!(
+        harness.expect_widget("Row 11").rect.top() - harness.expect_widget("Row 10").rect.top(),
+        20.0
+    );
+    assert_eq!(empty_rows, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_show_rows_ids_are_stable() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let app = |offset: f32| {
+        move |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                let row_height = ui.style().spacing.interact_size.y;
+                ScrollArea::from_max_height(100.0)
+                    .vertical_scroll_offset(offset)
+                    .show_rows(ui, row_height, 1000, |ui, row| {
+                        // Two widgets per row, without wrapping them in a single child `Ui`:
+                        let _ = ui.button(format!("First {}", row));
+                        let _ = ui.button(format!("Second {}", row));
+                    });
+            });
+        }
+    };
+    harness.run(app(200.0));
+    let id = harness.expect_widget("Second 12").id;
+    harness.run(app(240.0));
+    assert_eq!(harness.expect_widget("Second 12").id, id);
+}
+
+#[test]
+fn test_horizontal_and_two_axis_scrolling() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            for &(id_source, offset) in &[("both", None), ("fixed", Some(vec2(10.0, 20.0)))] {
+                let mut scroll_area = ScrollArea::both()
+                    .max_width(100.0)
+                    .max_height(60.0)
+                    .id_source(id_source);
+                if let Some(offset) = offset {
+                    scroll_area = scroll_area.scroll_offset_vec2(offset);
+                }
+                scroll_area.show(ui, |ui| {
+                    for row in 0..20 {
+                        ui.horizontal(|ui| {
+                            for col in 0..20 {
+                                ui.label(format!("{} {},{}", id_source, col, row));
+                            }
+                        });
+                    }
+                });
+            }
+            ScrollArea::horizontal()
+                .max_width(100.0)
+                .id_source("horizontal")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for col in 0..20 {
+                            ui.label(format!("H{}", col));
+                        }
+                    });
+                });
+        });
+    };
+    harness.run(&mut app);
+    harness.run(&mut app); // the scroll bars take up space from the second frame on
+    let rect = |harness: &crate::testing::Harness, label: &str| harness.expect_widget(label).rect;
+    let horizontal_start = rect(&harness, "H0");
+    let both_start = rect(&harness, "both 0,0");
+
+    // Shift turns the mouse wheel into horizontal scrolling:
+    harness.move_mouse_to(horizontal_start.center());
+    harness.set_modifiers(Modifiers {
+        shift: true,
+        ..Default::default()
+    });
+    harness.scroll(vec2(0.0, -25.0));
+    harness.run(&mut app);
+    harness.set_modifiers(Default::default());
+    harness.run(&mut app);
+    assert_eq!(
+        rect(&harness, "H0"),
+        horizontal_start.translate(vec2(-25.0, 0.0))
+    );
+
+    harness.move_mouse_to(both_start.center());
+    harness.scroll(vec2(-15.0, -25.0));
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert_eq!(
+        rect(&harness, "both 0,0"),
+        both_start.translate(vec2(-15.0, -25.0))
+    );
+
+    assert_eq!(rect(&harness, "fixed 0,0").left(), both_start.left() - 10.0);
+}
@@ -0,0 +1,512 @@
+//! Table container: rows of data under a header that stays put while the rows scroll.
+
+use crate::{
+    paint::{PaintCmd, TextStyle},
+    *,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    /// The width of each column, not including the spacing between them.
+    column_widths: Vec<f32>,
+}
+
+impl State {
+    fn total_width(&self, x_spacing: f32) -> f32 {
+        self.column_widths.iter().sum::<f32>()
+            + (self.column_widths.len().at_least(1) - 1) as f32 * x_spacing
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A column of a [`Table`].
+#[derive(Clone, Debug)]
+pub struct TableColumn {
+    header: String,
+    initial_width: Option<f32>,
+    min_width: Option<f32>,
+    resizable: bool,
+}
+
+impl TableColumn {
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            initial_width: None,
+            min_width: None,
+            resizable: true,
+        }
+    }
+
+    /// The width of the column until the user resizes it.
+    /// Default: share the available width with the other columns.
+    pub fn initial_width(mut self, initial_width: f32) -> Self {
+        self.initial_width = Some(initial_width);
+        self
+    }
+
+    /// The user can not make the column narrower than this.
+    /// Default: [`Spacing::interact_size`](crate::style::Spacing::interact_size)`.x`.
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// Can the user resize the column by dragging the right border of its header?
+    /// Default: `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+impl From<&str> for TableColumn {
+    fn from(header: &str) -> Self {
+        Self::new(header)
+    }
+}
+
+impl From<String> for TableColumn {
+    fn from(header: String) -> Self {
+        Self::new(header)
+    }
+}
+
+/// The user clicked a column header of a [`Table`], asking for the rows to be sorted by that column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SortRequest {
+    pub column: usize,
+    /// `true` if this column was not sorted, or sorted in descending order.
+    pub ascending: bool,
+}
+
+/// The response from showing a [`Table`].
+pub struct TableResponse {
+    /// The whole table, header and rows.
+    pub response: Response,
+    /// Set when a column header was clicked. It is up to you to sort the rows.
+    pub sort_request: Option<SortRequest>,
+    /// The row that was clicked this frame (if any).
+    pub clicked_row: Option<usize>,
+}
+
+/// A table of rows and columns with a header that stays visible while the rows scroll.
+///
+/// The user can resize the columns by dragging the borders in the header,
+/// and ask for the rows to be sorted by clicking a column header.
+/// Each cell is clipped to the width of its column.
+///
+/// All rows have the same height, and only the rows that are visible are laid out,
+/// so a table can have a great many rows.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// # let mut selected = None;
+/// let people = [("Ada", 36), ("Grace", 85)];
+/// let table = egui::Table::new("people")
+///     .column("Name")
+///     .column(egui::TableColumn::new("Age").initial_width(60.0))
+///     .selected_row(selected);
+/// let response = table.show(ui, people.len(), |ui, row, col| {
+///     let (name, age) = people[row];
+///     if col == 0 {
+///         ui.label(name);
+///     } else {
+///         ui.label(age.to_string());
+///     }
+/// });
+/// if let Some(row) = response.clicked_row {
+///     selected = Some(row);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Table {
+    id_source: Id,
+    columns: Vec<TableColumn>,
+    row_height: Option<f32>,
+    max_height: f32,
+    striped: bool,
+    sorted_by: Option<SortRequest>,
+    selected_row: Option<usize>,
+}
+
+impl Table {
+    /// Create a new `Table` with a locally unique identifier.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            columns: vec![],
+            row_height: None,
+            max_height: f32::INFINITY,
+            striped: false,
+            sorted_by: None,
+            selected_row: None,
+        }
+    }
+
+    /// Add a column, e.g. `.column("Name")`.
+    pub fn column(mut self, column: impl Into<TableColumn>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// The height of each row, excluding the spacing between rows.
+    /// Default: [`Spacing::interact_size`](crate::style::Spacing::interact_size)`.y`.
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// The maximum height of the table, including the header.
+    ///
+    /// Default: `f32::INFINITY`, i.e. fill the surrounding `Ui`.
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    /// If `true`, add a subtle background color to every other row.
+    /// Default: `false`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Show an arrow in the header of the column the rows are currently sorted by.
+    ///
+    /// This is also what the next [`SortRequest`] is based on:
+    /// clicking this column again will ask for the opposite order.
+    pub fn sorted_by(mut self, column: usize, ascending: bool) -> Self {
+        self.sorted_by = Some(SortRequest { column, ascending });
+        self
+    }
+
+    /// Highlight this row with [`Visuals::selection`](crate::style::Visuals::selection).
+    pub fn selected_row(mut self, selected_row: Option<usize>) -> Self {
+        self.selected_row = selected_row;
+        self
+    }
+}
+
+impl Table {
+    /// Show the table with `num_rows` rows.
+    ///
+    /// `add_cell` is called with the `Ui` of a cell, the row and the column,
+    /// for each cell of each visible row.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        num_rows: usize,
+        mut add_cell: impl FnMut(&mut Ui, usize, usize),
+    ) -> TableResponse {
+        let Self {
+            id_source,
+            columns,
+            row_height,
+            max_height,
+            striped,
+            sorted_by,
+            selected_row,
+        } = self;
+
+        let ((sort_request, clicked_row), response) = ui.wrap(|ui| {
+            let id = ui.make_persistent_id(id_source);
+            let spacing = ui.style().spacing.item_spacing;
+            let min_widths: Vec<f32> = columns
+                .iter()
+                .map(|column| {
+                    column
+                        .min_width
+                        .unwrap_or(ui.style().spacing.interact_size.x)
+                })
+                .collect();
+
+            let mut state = ui.memory().table.get(&id).cloned().unwrap_or_default();
+            if state.column_widths.len() != columns.len() {
+                // Leave room for the scroll bar:
+                let available_width = ui.available_size_before_wrap_finite().x
+                    - scroll_area::max_scroll_bar_width_with_margin(ui);
+                state.column_widths =
+                    initial_column_widths(&columns, &min_widths, available_width, spacing.x);
+            }
+
+            let header_height = ui.fonts()[TextStyle::Button].row_height()
+                + 2.0 * ui.style().spacing.button_padding.y;
+            let header_height = header_height.at_least(ui.style().spacing.interact_size.y);
+            let header_top_left = ui.available_rect_before_wrap().min;
+
+            // Resize the columns first, so that the header and the rows use the new widths right away:
+            let mut border_responses = vec![];
+            let mut left = header_top_left.x;
+            for (col, column) in columns.iter().enumerate() {
+                let border_x = left + state.column_widths[col] + 0.5 * spacing.x;
+                if column.resizable {
+                    let radius = ui.style().interaction.resize_grab_radius_side;
+                    let border_rect = Rect::from_min_max(
+                        pos2(border_x - radius, header_top_left.y),
+                        pos2(border_x + radius, header_top_left.y + header_height),
+                    );
+                    let response =
+                        ui.interact(border_rect, id.with("border").with(col), Sense::drag());
+                    if response.active {
+                        if let Some(mouse_pos) = ui.input().mouse.pos {
+                            state.column_widths[col] =
+                                (mouse_pos.x - 0.5 * spacing.x - left).at_least(min_widths[col]);
+                        }
+                    }
+                    if response.hovered || response.active {
+                        ui.output().cursor_icon = CursorIcon::ResizeHorizontal;
+                    }
+                    border_responses.push((col, response));
+                }
+                left += state.column_widths[col] + spacing.x;
+            }
+
+            let total_width = state.total_width(spacing.x);
+            let (_, header_rect) = ui.allocate_space(vec2(total_width, header_height));
+            let border_hovered = border_responses
+                .iter()
+                .any(|(_, response)| response.hovered || response.active);
+
+            // Header:
+            let mut sort_request = None;
+            let mut left = header_rect.left();
+            for (col, column) in columns.iter().enumerate() {
+                let cell_rect = Rect::from_min_size(
+                    pos2(left, header_rect.top()),
+                    vec2(state.column_widths[col], header_height),
+                );
+                let response = ui.interact(cell_rect, id.with("header").with(col), Sense::click());
+                response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, &column.header));
+                if response.clicked && !border_hovered {
+                    let ascending = match sorted_by {
+                        Some(sorted_by) if sorted_by.column == col => !sorted_by.ascending,
+                        _ => true,
+                    };
+                    sort_request = Some(SortRequest {
+                        column: col,
+                        ascending,
+                    });
+                }
+                paint_header_cell(ui, &response, &column.header, sorted_by, col);
+                left += state.column_widths[col] + spacing.x;
+            }
+
+            let line_y = header_rect.bottom() + 0.5 * spacing.y;
+            ui.painter().line_segment(
+                [
+                    pos2(header_rect.left(), line_y),
+                    pos2(header_rect.right(), line_y),
+                ],
+                ui.style().visuals.widgets.noninteractive.bg_stroke,
+            );
+            for (col, response) in &border_responses {
+                let stroke = if response.hovered || response.active {
+                    ui.style().interact(response).fg_stroke
+                } else {
+                    ui.style().visuals.widgets.noninteractive.bg_stroke
+                };
+                let x = response.rect.center().x;
+                let margin = if *col + 1 == columns.len() { 0.0 } else { 2.0 };
+                ui.painter().line_segment(
+                    [
+                        pos2(x, header_rect.top() + margin),
+                        pos2(x, header_rect.bottom() - margin),
+                    ],
+                    stroke,
+                );
+            }
+
+            // Rows:
+            let row_height = row_height.unwrap_or(ui.style().spacing.interact_size.y);
+            let body_max_height = max_height - header_height - spacing.y;
+            let column_widths = &state.column_widths;
+            let mut clicked_row = None;
+            ScrollArea::vertical()
+                .id_source(id.with("body"))
+                .max_height(body_max_height)
+                .show_rows(ui, row_height, num_rows, |ui, row| {
+                    let (_, row_rect) = ui.allocate_space(vec2(total_width, row_height));
+                    let bg_index = ui.painter().add(PaintCmd::Noop);
+
+                    let mut left = row_rect.left();
+                    for (col, width) in column_widths.iter().enumerate() {
+                        let cell_rect = Rect::from_min_size(
+                            pos2(left, row_rect.top()),
+                            vec2(*width, row_height),
+                        );
+                        let mut cell_ui = ui.child_ui(
+                            cell_rect,
+                            Layout::left_to_right().with_cross_align(Align::Center),
+                        );
+                        cell_ui.set_clip_rect(cell_rect.intersect(ui.clip_rect()));
+                        add_cell(&mut cell_ui, row, col);
+                        left += width + spacing.x;
+                    }
+
+                    // After the cells, so that widgets in the cells get the clicks first.
+                    // Clicking such a widget still selects the row though:
+                    let row_response =
+                        ui.interact(row_rect, id.with("row").with(row), Sense::click());
+                    if row_response.clicked || (row_response.hovered && ui.input().mouse.click) {
+                        clicked_row = Some(row);
+                    }
+
+                    let visuals = &ui.style().visuals;
+                    let fill = if selected_row == Some(row) {
+                        Some(visuals.selection.bg_fill)
+                    } else if row_response.hovered {
+                        Some(visuals.widgets.hovered.bg_fill)
+                    } else if striped && row % 2 == 1 {
+                        Some(visuals.faint_bg_color)
+                    } else {
+                        None
+                    };
+                    if let Some(fill) = fill {
+                        // Cover the spacing between the rows, and some extra on the sides (looks better):
+                        let rect = row_rect.expand2(vec2(2.0, 0.5 * spacing.y));
+                        ui.painter()
+                            .set(bg_index, PaintCmd::rect_filled(rect, 2.0, fill));
+                    }
+                });
+
+            ui.memory().table.insert(id, state);
+            (sort_request, clicked_row)
+        });
+
+        TableResponse {
+            response,
+            sort_request,
+            clicked_row,
+        }
+    }
+}
+
+fn initial_column_widths(
+    columns: &[TableColumn],
+    min_widths: &[f32],
+    available_width: f32,
+    x_spacing: f32,
+) -> Vec<f32> {
+    let fixed_width: f32 = columns
+        .iter()
+        .filter_map(|column| column.initial_width)
+        .sum();
+    let num_shared = columns
+        .iter()
+        .filter(|column| column.initial_width.is_none())
+        .count();
+    let spacing = (columns.len().at_least(1) - 1) as f32 * x_spacing;
+    let shared_width = (available_width - spacing - fixed_width) / num_shared.at_least(1) as f32;
+
+    columns
+        .iter()
+        .zip(min_widths)
+        .map(|(column, min_width)| {
+            column
+                .initial_width
+                .unwrap_or(shared_width)
+                .at_least(*min_width)
+        })
+        .collect()
+}
+
+fn paint_header_cell(
+    ui: &Ui,
+    response: &Response,
+    header: &str,
+    sorted_by: Option<SortRequest>,
+    col: usize,
+) {
+    let visuals = ui.style().interact(response);
+    let rect = response.rect;
+    if response.hovered || response.active {
+        ui.painter()
+            .rect_filled(rect, visuals.corner_radius, visuals.bg_fill);
+    }
+
+    let painter = ui.painter().sub_region(rect);
+    let padding = ui.style().spacing.button_padding;
+    let galley = ui.fonts()[TextStyle::Button].layout_single_line(header.to_owned());
+    let text_pos = pos2(
+        rect.left() + padding.x,
+        rect.center().y - 0.5 * galley.size.y,
+    );
+    painter.galley(text_pos, galley, TextStyle::Button, visuals.text_color());
+
+    if let Some(sorted_by) = sorted_by.filter(|sorted_by| sorted_by.column == col) {
+        // A small triangle, pointing up for ascending and down for descending:
+        let size = 0.5 * ui.style().spacing.icon_width;
+        let center = pos2(rect.right() - padding.x - 0.5 * size, rect.center().y);
+        let half = 0.5 * size;
+        let (tip_y, base_y) = if sorted_by.ascending {
+            (center.y - half, center.y + half)
+        } else {
+            (center.y + half, center.y - half)
+        };
+        let points = vec![
+            pos2(center.x, tip_y),
+            pos2(center.x + half, base_y),
+            pos2(center.x - half, base_y),
+        ];
+        painter.add(PaintCmd::polygon(
+            points,
+            visuals.text_color(),
+            Stroke::none(),
+        ));
+    }
+}
+
+#[test]
+fn test_table() {
+    let mut sort_request = None;
+    let mut clicked_row = None;
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Table::new("table")
+                .column("Name")
+                .column("Value")
+                .sorted_by(0, true)
+                .show(ui, 1000, |ui, row, col| {
+                    ui.label(format!("cell {} {}", row, col));
+                });
+            sort_request = sort_request.or(response.sort_request);
+            clicked_row = clicked_row.or(response.clicked_row);
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+
+    // Only the visible rows are laid out:
+    assert!(harness.widget("cell 0 0").is_some());
+    assert!(harness.widget("cell 999 0").is_none());
+
+    harness.click("Name", &mut app);
+    harness.click("cell 2 1", &mut app);
+
+    // Drag the border between the columns to make the first column wider:
+    let name_width = harness.expect_widget("Name").rect.width();
+    let x_spacing = harness.ctx().style().spacing.item_spacing.x;
+    let border = harness.expect_widget("Name").rect.right_center() + vec2(0.5 * x_spacing, 0.0);
+    harness.move_mouse_to(border);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(border + vec2(50.0, 0.0));
+    harness.run(&mut app);
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    let new_name_width = harness.expect_widget("Name").rect.width();
+    assert!((new_name_width - (name_width + 50.0)).abs() < 1.0);
+
+    // Clicking the column we are sorted by asks for the opposite order:
+    let expected = SortRequest {
+        column: 0,
+        ascending: false,
+    };
+    assert_eq!(sort_request, Some(expected));
+    assert_eq!(clicked_row, Some(2));
+}
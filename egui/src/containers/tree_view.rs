@@ -0,0 +1,623 @@
+//! Tree view: a hierarchy of nodes that the user can open and close, select, and drag around.
+
+use std::hash::Hash;
+
+use crate::{
+    collapsing_header::{self, paint_icon},
+    layers::PaintCmdIdx,
+    paint::{PaintCmd, TextStyle},
+    *,
+};
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    selected: Vec<Id>,
+
+    /// Shift-clicking selects everything between this node and the clicked one.
+    anchor: Option<Id>,
+
+    /// The node that Tab gives keyboard focus to, and that the arrow keys move from.
+    cursor: Option<Id>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Where to drop dragged nodes, relative to the node they were dropped on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropPosition {
+    /// Just above the target, as a sibling.
+    Before,
+    /// Just below the target, as a sibling.
+    After,
+    /// As the last child of the target.
+    Inside,
+}
+
+/// Something the user did in a [`TreeView`], for you to apply to your own data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TreeAction<NodeId> {
+    /// The selection changed. These are all the selected nodes, top to bottom.
+    ///
+    /// Nodes hidden inside a closed node are deselected whenever the selection changes.
+    SetSelected(Vec<NodeId>),
+
+    /// The user dragged these nodes (top to bottom) and dropped them on `target`.
+    ///
+    /// The nodes are never dropped on themselves or inside one of their own descendants.
+    Move {
+        nodes: Vec<NodeId>,
+        target: NodeId,
+        position: DropPosition,
+    },
+}
+
+/// The response from showing a [`TreeView`].
+pub struct TreeViewResponse<NodeId> {
+    /// The whole tree.
+    pub response: Response,
+    /// What the user did this frame, in order.
+    pub actions: Vec<TreeAction<NodeId>>,
+}
+
+/// A hierarchy of nodes, like the files in a file browser or the objects of a scene.
+///
+/// Nodes with children can be opened and closed, just like a [`CollapsingHeader`].
+/// The user can select nodes (use ctrl/cmd and shift to select several),
+/// move the selection with the arrow keys,
+/// and drag nodes onto other nodes to move them.
+///
+/// The tree doesn't change your data: instead you get a list of [`TreeAction`]s to apply.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// use egui::{TreeAction, TreeView};
+///
+/// let response = TreeView::new("outliner").show(ui, |tree| {
+///     tree.node(0, "Scene", |tree| {
+///         tree.leaf(1, "Camera");
+///         tree.leaf(2, "Light");
+///     });
+/// });
+/// for action in response.actions {
+///     match action {
+///         TreeAction::SetSelected(selected) => { /* … */ }
+///         TreeAction::Move { nodes, target, position } => { /* … */ }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TreeView {
+    id_source: Id,
+    default_open: bool,
+}
+
+impl TreeView {
+    /// Create a new `TreeView` with a locally unique identifier.
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            default_open: false,
+        }
+    }
+
+    /// Are nodes open the first time they are shown? Default: `false`.
+    pub fn default_open(mut self, default_open: bool) -> Self {
+        self.default_open = default_open;
+        self
+    }
+
+    /// Show the tree. Add the nodes with [`TreeViewBuilder::node`] and [`TreeViewBuilder::leaf`].
+    ///
+    /// `NodeId` is whatever you use to identify your nodes. It must be unique within the tree.
+    pub fn show<NodeId: Clone + Hash>(
+        self,
+        ui: &mut Ui,
+        add_nodes: impl FnOnce(&mut TreeViewBuilder<'_, NodeId>),
+    ) -> TreeViewResponse<NodeId> {
+        let Self {
+            id_source,
+            default_open,
+        } = self;
+
+        let (actions, response) = ui.wrap(|ui| {
+            let id = ui.make_persistent_id(id_source);
+            let state = ui.memory().tree_view.get(&id).cloned().unwrap_or_default();
+            let mut tree = Tree {
+                id,
+                default_open,
+                state,
+                rows: vec![],
+            };
+
+            add_nodes(&mut TreeViewBuilder {
+                ui,
+                tree: &mut tree,
+                depth: 0,
+                parent: None,
+            });
+
+            let actions = tree.end(ui);
+            ui.memory().tree_view.insert(id, tree.state);
+            actions
+        });
+
+        TreeViewResponse { response, actions }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A node as laid out this frame.
+struct Row<NodeId> {
+    id: Id,
+    node_id: NodeId,
+    label: String,
+    rect: Rect,
+    depth: usize,
+    /// Index of the parent row.
+    parent: Option<usize>,
+    /// `None` for leaves.
+    open: Option<bool>,
+    response: Response,
+    /// Where to paint the selection highlight.
+    bg_index: PaintCmdIdx,
+}
+
+struct Tree<NodeId> {
+    id: Id,
+    default_open: bool,
+    state: State,
+    rows: Vec<Row<NodeId>>,
+}
+
+/// Adds the nodes of a [`TreeView`]. See [`TreeView::show`].
+pub struct TreeViewBuilder<'a, NodeId> {
+    ui: &'a mut Ui,
+    tree: &'a mut Tree<NodeId>,
+    depth: usize,
+    parent: Option<usize>,
+}
+
+impl<'a, NodeId: Clone + Hash> TreeViewBuilder<'a, NodeId> {
+    /// A node without children.
+    pub fn leaf(&mut self, node_id: NodeId, label: impl Into<String>) {
+        self.add_row(node_id, label.into(), None);
+    }
+
+    /// A node with children, which the user can open and close.
+    /// `add_children` is only called when the node is open.
+    pub fn node(
+        &mut self,
+        node_id: NodeId,
+        label: impl Into<String>,
+        add_children: impl FnOnce(&mut TreeViewBuilder<'_, NodeId>),
+    ) {
+        let id = self.tree.id.with(&node_id);
+        let mut state = collapsing_header::State::from_memory_with_default_open(
+            self.ui.ctx(),
+            id,
+            self.tree.default_open,
+        );
+        let index = self.add_row(node_id, label.into(), Some(&mut state));
+        let row_rect = self.tree.rows[index].rect;
+
+        let depth = self.depth;
+        let tree = &mut *self.tree;
+        let children = state.add_contents(self.ui, id, |ui| {
+            add_children(&mut TreeViewBuilder {
+                ui,
+                tree,
+                depth: depth + 1,
+                parent: Some(index),
+            })
+        });
+        self.ui.memory().collapsing_headers.insert(id, state);
+
+        if let Some(((), response)) = children {
+            // Indentation guide, from the node down to its last child:
+            let x = row_rect.left() + (depth as f32 + 0.5) * self.ui.style().spacing.indent;
+            let stroke = self.ui.style().visuals.widgets.noninteractive.bg_stroke;
+            self.ui.painter().line_segment(
+                [pos2(x, row_rect.bottom()), pos2(x, response.rect.bottom())],
+                stroke,
+            );
+        }
+    }
+
+    fn add_row(
+        &mut self,
+        node_id: NodeId,
+        label: String,
+        mut open_state: Option<&mut collapsing_header::State>,
+    ) -> usize {
+        let ui = &mut *self.ui;
+        let tree = &mut *self.tree;
+        let id = tree.id.with(&node_id);
+        let indent = ui.style().spacing.indent;
+
+        let row_size = vec2(
+            ui.available_size_before_wrap_finite().x,
+            ui.style().spacing.interact_size.y,
+        );
+        let (_, rect) = ui.allocate_space(row_size);
+        let bg_index = ui.painter().add(PaintCmd::Noop);
+        let content_left = rect.left() + self.depth as f32 * indent;
+
+        // Before the row, so that the icon gets the clicks:
+        let mut icon_response = None;
+        if let Some(state) = &mut open_state {
+            let (mut icon_rect, _) = ui.style().spacing.icon_rectangles(rect);
+            icon_rect.set_center(pos2(content_left + 0.5 * indent, rect.center().y));
+            let response =
+                ui.interact(icon_rect, id.with("toggle"), Sense::click().not_focusable());
+            if response.clicked {
+                state.toggle(ui);
+            }
+            icon_response = Some(response);
+        }
+
+        // Only one node at a time can be reached with Tab. From there, use the arrow keys.
+        let is_cursor = match tree.state.cursor {
+            Some(cursor) => cursor == id,
+            None => tree.rows.is_empty(),
+        };
+        let sense = if is_cursor {
+            Sense::click_and_drag()
+        } else {
+            Sense::click_and_drag().not_focusable()
+        };
+        let response = ui.interact(rect, id, sense);
+
+        let mut open = None;
+        if let Some(state) = &mut open_state {
+            if response.double_clicked {
+                state.toggle(ui);
+            }
+            open = Some(state.open);
+        }
+
+        let selected = tree.state.selected.contains(&id);
+        response.widget_info(|| WidgetInfo {
+            selected: Some(selected),
+            expanded: open,
+            ..WidgetInfo::labeled(WidgetType::TreeNode, &label)
+        });
+
+        if let (Some(state), Some(icon_response)) = (&open_state, &icon_response) {
+            let openness = state.openness(ui.ctx(), id);
+            paint_icon(ui, openness, icon_response);
+        }
+
+        let galley = ui.fonts()[TextStyle::Body].layout_single_line(label.clone());
+        let text_pos = pos2(content_left + indent, rect.center().y - 0.5 * galley.size.y);
+        let text_color = ui.style().interact(&response).text_color();
+        ui.painter()
+            .galley(text_pos, galley, TextStyle::Body, text_color);
+
+        tree.rows.push(Row {
+            id,
+            node_id,
+            label,
+            rect,
+            depth: self.depth,
+            parent: self.parent,
+            open,
+            response,
+            bg_index,
+        });
+        tree.rows.len() - 1
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<NodeId: Clone> Tree<NodeId> {
+    /// Handle selection, keyboard navigation and dragging, now that we know all the visible rows.
+    fn end(&mut self, ui: &mut Ui) -> Vec<TreeAction<NodeId>> {
+        let mut actions = vec![];
+        let selected_before = self.state.selected.clone();
+
+        let modifiers = ui.input().modifiers;
+        for index in 0..self.rows.len() {
+            if self.rows[index].response.clicked {
+                if modifiers.command {
+                    self.toggle_selected(index);
+                } else if modifiers.shift {
+                    self.select_range_to(index);
+                } else {
+                    self.select(index);
+                }
+                ui.memory().request_kb_focus(self.rows[index].id);
+            }
+        }
+
+        self.arrow_keys(ui);
+
+        if let Some(action) = self.drag_and_drop(ui) {
+            actions.push(action);
+        }
+
+        if self.state.selected != selected_before {
+            // Forget the nodes we can't see:
+            let rows = &self.rows;
+            self.state
+                .selected
+                .retain(|id| rows.iter().any(|row| row.id == *id));
+            let selected = self
+                .rows
+                .iter()
+                .filter(|row| self.state.selected.contains(&row.id))
+                .map(|row| row.node_id.clone())
+                .collect();
+            actions.insert(0, TreeAction::SetSelected(selected));
+        }
+
+        let cursor_is_visible = self
+            .rows
+            .iter()
+            .any(|row| Some(row.id) == self.state.cursor);
+        if !cursor_is_visible {
+            self.state.cursor = self.rows.first().map(|row| row.id);
+        }
+
+        self.paint_backgrounds(ui);
+        actions
+    }
+
+    fn select(&mut self, index: usize) {
+        let id = self.rows[index].id;
+        self.state.selected = vec![id];
+        self.state.anchor = Some(id);
+        self.state.cursor = Some(id);
+    }
+
+    fn toggle_selected(&mut self, index: usize) {
+        let id = self.rows[index].id;
+        if let Some(position) = self.state.selected.iter().position(|s| *s == id) {
+            self.state.selected.remove(position);
+        } else {
+            self.state.selected.push(id);
+        }
+        self.state.anchor = Some(id);
+        self.state.cursor = Some(id);
+    }
+
+    /// Select everything between the anchor and this row.
+    fn select_range_to(&mut self, index: usize) {
+        let anchor = self
+            .rows
+            .iter()
+            .position(|row| Some(row.id) == self.state.anchor);
+        let anchor = match anchor {
+            Some(anchor) => anchor,
+            None => return self.select(index),
+        };
+        let range = anchor.min(index)..=anchor.max(index);
+        self.state.selected = self.rows[range].iter().map(|row| row.id).collect();
+        self.state.cursor = Some(self.rows[index].id);
+    }
+
+    fn arrow_keys(&mut self, ui: &Ui) {
+        let focused = self.rows.iter().position(|row| row.response.has_kb_focus);
+        let mut index = match focused {
+            Some(index) => index,
+            None => return,
+        };
+
+        let shift = ui.input().modifiers.shift;
+        let mut moved = false;
+        for event in ui.input().unconsumed_events() {
+            let key = match event {
+                Event::Key {
+                    key, pressed: true, ..
+                } => *key,
+                _ => continue,
+            };
+            let row = &self.rows[index];
+            let next_index = match key {
+                Key::ArrowUp => index.checked_sub(1),
+                Key::ArrowDown => Some(index + 1).filter(|next| *next < self.rows.len()),
+                Key::ArrowRight if row.open == Some(false) => {
+                    set_open(ui, row.id, true);
+                    None
+                }
+                Key::ArrowRight => {
+                    let first_child = index + 1;
+                    let has_children =
+                        self.rows.get(first_child).map(|child| child.parent) == Some(Some(index));
+                    Some(first_child).filter(|_| has_children)
+                }
+                Key::ArrowLeft if row.open == Some(true) => {
+                    set_open(ui, row.id, false);
+                    None
+                }
+                Key::ArrowLeft => row.parent,
+                _ => None,
+            };
+
+            if let Some(next_index) = next_index {
+                if shift {
+                    self.select_range_to(next_index);
+                } else {
+                    self.select(next_index);
+                }
+                index = next_index;
+                moved = true;
+            }
+        }
+
+        if moved {
+            let row = &self.rows[index];
+            ui.memory().request_kb_focus(row.id);
+            let clip_rect = ui.clip_rect();
+            if !clip_rect.contains(row.rect.min) || !clip_rect.contains(row.rect.max) {
+                row.response.scroll_to_me(Align::Center);
+            }
+        }
+    }
+
+    fn drag_and_drop(&self, ui: &Ui) -> Option<TreeAction<NodeId>> {
+        let dragged = self
+            .rows
+            .iter()
+            .position(|row| ui.memory().is_being_dragged(row.id))?;
+        if ui.input().mouse.could_be_click {
+            return None; // Not dragged far enough yet.
+        }
+
+        // Dragging a selected node drags the whole selection:
+        let dragged: Vec<usize> = if self.state.selected.contains(&self.rows[dragged].id) {
+            (0..self.rows.len())
+                .filter(|index| self.state.selected.contains(&self.rows[*index].id))
+                .collect()
+        } else {
+            vec![dragged]
+        };
+
+        ui.output().cursor_icon = CursorIcon::Grabbing;
+        let labels: Vec<&str> = dragged
+            .iter()
+            .map(|index| self.rows[*index].label.as_str())
+            .collect();
+        show_tooltip_text(ui.ctx(), labels.join("\n"));
+
+        let mouse_pos = ui.input().mouse.pos?;
+        let spacing = ui.style().spacing.item_spacing;
+        let target = self
+            .rows
+            .iter()
+            .position(|row| row.rect.expand2(0.5 * spacing).contains(mouse_pos))?;
+
+        let mut ancestor = Some(target);
+        while let Some(index) = ancestor {
+            if dragged.contains(&index) {
+                return None; // Can't drop something on itself.
+            }
+            ancestor = self.rows[index].parent;
+        }
+
+        let row = &self.rows[target];
+        let t = remap_clamp(mouse_pos.y, row.rect.y_range(), 0.0..=1.0);
+        let position = if row.open.is_some() {
+            if t < 0.25 {
+                DropPosition::Before
+            } else if t > 0.75 {
+                DropPosition::After
+            } else {
+                DropPosition::Inside
+            }
+        } else if t < 0.5 {
+            DropPosition::Before
+        } else {
+            DropPosition::After
+        };
+
+        // Show where the nodes will end up:
+        let stroke = Stroke::new(2.0, ui.style().visuals.selection.stroke.color);
+        let left = row.rect.left() + row.depth as f32 * ui.style().spacing.indent;
+        let line = |y: f32| [pos2(left, y), pos2(row.rect.right(), y)];
+        match position {
+            DropPosition::Before => ui.painter().line_segment(line(row.rect.top()), stroke),
+            DropPosition::After => ui.painter().line_segment(line(row.rect.bottom()), stroke),
+            DropPosition::Inside => ui.painter().rect_stroke(row.rect, 2.0, stroke),
+        }
+
+        if ui.input().mouse.released {
+            Some(TreeAction::Move {
+                nodes: dragged
+                    .iter()
+                    .map(|index| self.rows[*index].node_id.clone())
+                    .collect(),
+                target: row.node_id.clone(),
+                position,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn paint_backgrounds(&self, ui: &Ui) {
+        let visuals = &ui.style().visuals;
+        let is_dragging = self
+            .rows
+            .iter()
+            .any(|row| ui.memory().is_being_dragged(row.id));
+        for row in &self.rows {
+            let fill = if self.state.selected.contains(&row.id) {
+                visuals.selection.bg_fill
+            } else if row.response.hovered && !is_dragging {
+                visuals.widgets.hovered.bg_fill
+            } else {
+                continue;
+            };
+            ui.painter()
+                .set(row.bg_index, PaintCmd::rect_filled(row.rect, 2.0, fill));
+        }
+    }
+}
+
+fn set_open(ui: &Ui, id: Id, open: bool) {
+    let mut memory = ui.memory();
+    if let Some(state) = memory.collapsing_headers.get_mut(&id) {
+        state.open = open;
+    }
+    ui.ctx().request_repaint();
+}
+
+#[test]
+fn test_tree_view() {
+    let mut actions = vec![];
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = TreeView::new("tree").default_open(true).show(ui, |tree| {
+                tree.node("scene", "Scene", |tree| {
+                    tree.leaf("camera", "Camera");
+                    tree.leaf("light", "Light");
+                    tree.leaf("cube", "Cube");
+                });
+            });
+            actions.extend(response.actions);
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+
+    // Click, shift-click, then move down with the arrow keys:
+    harness.click("Camera", &mut app);
+    harness.set_modifiers(Modifiers {
+        shift: true,
+        ..Default::default()
+    });
+    harness.click("Light", &mut app);
+    harness.set_modifiers(Default::default());
+    harness.press_key(Key::ArrowDown);
+    harness.run(&mut app);
+
+    // Drag the cube to before the camera:
+    let cube = harness.expect_widget("Cube").rect.center();
+    let camera = harness.expect_widget("Camera").rect;
+    harness.move_mouse_to(cube);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(pos2(camera.center().x, camera.top() + 2.0));
+    harness.run(&mut app);
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+
+    assert_eq!(
+        actions,
+        vec![
+            TreeAction::SetSelected(vec!["camera"]),
+            TreeAction::SetSelected(vec!["camera", "light"]),
+            TreeAction::SetSelected(vec!["cube"]),
+            TreeAction::Move {
+                nodes: vec!["cube"],
+                target: "camera",
+                position: DropPosition::Before
+            },
+        ]
+    );
+}
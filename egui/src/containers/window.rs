@@ -29,6 +29,7 @@ pub struct Window<'open> {
     scroll: Option<ScrollArea>,
     collapsible: bool,
     with_title_bar: bool,
+    close_on_escape: bool,
 }
 
 impl<'open> Window<'open> {
@@ -53,6 +54,7 @@ impl<'open> Window<'open> {
             scroll: None,
             collapsible: true,
             with_title_bar: true,
+            close_on_escape: false,
         }
     }
 
@@ -149,6 +151,21 @@ impl<'open> Window<'open> {
         self.fixed_pos(rect.min).fixed_size(rect.size())
     }
 
+    /// A modal window blocks all input to everything beneath it (and dims it) until it is closed.
+    /// Tab only moves the keyboard focus between the widgets in the modal window.
+    /// See also [`Self::close_on_escape`].
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.area = self.area.modal(modal);
+        self
+    }
+
+    /// Close the window when the user presses Escape (unless a modal window is covering it).
+    /// Only has an effect together with [`Self::open`].
+    pub fn close_on_escape(mut self, close_on_escape: bool) -> Self {
+        self.close_on_escape = close_on_escape;
+        self
+    }
+
     /// Can the user resize the window by dragging its edges?
     /// Note that even if you set this to `false` the window may still auto-resize.
     pub fn resizable(mut self, resizable: bool) -> Self {
@@ -207,19 +224,32 @@ impl<'open> Window<'open> {
     ) -> Option<Response> {
         let Window {
             title_label,
-            open,
+            mut open,
             area,
             frame,
             resize,
             scroll,
             collapsible,
             with_title_bar,
+            close_on_escape,
         } = self;
 
         if matches!(open, Some(false)) && !ctx.memory().everything_is_visible() {
             return None;
         }
 
+        if let Some(open) = &mut open {
+            if close_on_escape
+                && ctx.input().key_pressed(Key::Escape)
+                && !ctx.memory().areas.is_blocked_by_modal(area.layer())
+            {
+                **open = false;
+                return None;
+            }
+        }
+
+        let title = title_label.text().to_owned();
+
         let area_id = area.id;
         let area_layer_id = area.layer();
         let resize_id = area_id.with("resize");
@@ -361,6 +391,10 @@ impl<'open> Window<'open> {
             }
         }
         let full_response = area.end(ctx, area_content_ui);
+        full_response.widget_info(|| WidgetInfo {
+            expanded: Some(is_maximized),
+            ..WidgetInfo::labeled(WidgetType::Window, title)
+        });
 
         Some(full_response)
     }
@@ -658,6 +692,10 @@ fn show_title_bar(
             if collapse_button_response.clicked {
                 collapsing.toggle(ui);
             }
+            collapse_button_response.widget_info(|| WidgetInfo {
+                expanded: Some(collapsing.open),
+                ..WidgetInfo::labeled(WidgetType::Button, "Collapse")
+            });
             let openness = collapsing.openness(ui.ctx(), collapsing_id);
             collapsing_header::paint_icon(ui, openness, &collapse_button_response);
         }
@@ -733,7 +771,7 @@ impl TitleBar {
         }
 
         if ui
-            .interact(self.rect, self.id, Sense::click())
+            .interact(self.rect, self.id, Sense::click().not_focusable())
             .double_clicked
             && collapsible
         {
@@ -758,6 +796,7 @@ impl TitleBar {
 fn close_button(ui: &mut Ui, rect: Rect) -> Response {
     let close_id = ui.auto_id_with("window_close_button");
     let response = ui.interact(rect, close_id, Sense::click());
+    response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, "Close"));
     ui.expand_to_include_rect(response.rect);
 
     let stroke = ui.style().interact(&response).fg_stroke;
@@ -767,3 +806,51 @@ fn close_button(ui: &mut Ui, rect: Rect) -> Response {
         .line_segment([rect.right_top(), rect.left_bottom()], stroke);
     response
 }
+
+#[test]
+fn test_modal_window() {
+    let mut show_dialog = true;
+    let mut clicked_behind = 0;
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Behind").clicked {
+                clicked_behind += 1;
+            }
+        });
+        Window::new("Discard unsaved changes?")
+            .open(&mut show_dialog)
+            .modal(true)
+            .close_on_escape(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let _ = ui.button("Discard");
+                    let _ = ui.button("Cancel");
+                });
+            });
+    };
+
+    let mut harness = testing::Harness::new(vec2(600.0, 400.0));
+    harness.run(&mut app);
+    harness.click("Behind", &mut app);
+
+    // Tab only cycles between the widgets of the modal window:
+    for _ in 0..4 {
+        harness.press_key(Key::Tab);
+        harness.run(&mut app);
+        let focused: Vec<_> = harness
+            .widgets()
+            .iter()
+            .filter(|w| w.has_kb_focus)
+            .collect();
+        assert_eq!(focused.len(), 1);
+        assert_eq!(focused[0].layer_id.order, Order::Foreground);
+    }
+
+    harness.press_key(Key::Escape);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    harness.click("Behind", &mut app);
+
+    assert!(!show_dialog);
+    assert_eq!(clicked_behind, 1);
+}
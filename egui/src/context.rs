@@ -6,9 +6,11 @@ use std::sync::{
 };
 
 use crate::{
+    accessibility::AccessibilityTree,
     animation_manager::AnimationManager,
     mutex::{Mutex, MutexGuard},
     paint::{stats::*, *},
+    viewport::{ViewportId, ViewportOutput, ViewportSettings},
     *,
 };
 
@@ -22,6 +24,15 @@ pub(crate) struct FrameState {
     /// Used to debug `Id` clashes of widgets.
     pub(crate) used_ids: ahash::AHashMap<Id, Pos2>,
 
+    /// The viewport we are currently showing things in.
+    viewport_id: ViewportId,
+
+    /// The screen rect of the current viewport.
+    screen_rect: Rect,
+
+    /// The viewports (other than the root one) shown so far this frame.
+    pub(crate) viewports: Vec<(ViewportId, ViewportSettings)>,
+
     /// Starts off as the screen_rect, shrinks as panels are added.
     /// The `CentralPanel` does not change this.
     /// This is the area available to Window's.
@@ -41,19 +52,46 @@ pub(crate) struct FrameState {
 
     /// Cleared by the first `ScrollArea` that makes use of it.
     pub(crate) scroll_delta: Vec2,
-    pub(crate) scroll_target: Option<(f32, Align)>,
+    /// Cleared by the first `ScrollArea` that makes use of it. `[horizontal, vertical]`.
+    pub(crate) scroll_target: [Option<(f32, Align)>; 2],
+
+    /// All widgets laid out this frame, if `Context::is_recording_widgets`.
+    pub(crate) widgets: Vec<WidgetRecord>,
+
+    /// Where to paint the keyboard focus: `(viewport, layer, clip_rect, widget_rect)`.
+    pub(crate) kb_focus_rect: Option<(ViewportId, LayerId, Rect, Rect)>,
+
+    /// Where the text cursor of the focused `TextEdit` is, for [`Output::text_cursor_rect`].
+    pub(crate) text_cursor_rect: Option<(ViewportId, Rect)>,
+}
+
+/// What [`FrameState::enter_viewport`] replaced, so that it can be put back.
+#[derive(Clone, Copy)]
+struct ParentViewport {
+    viewport_id: ViewportId,
+    screen_rect: Rect,
+    available_rect: Rect,
+    unused_rect: Rect,
+    used_by_panels: Rect,
+    tooltip_rect: Option<Rect>,
 }
 
 impl Default for FrameState {
     fn default() -> Self {
         Self {
             used_ids: Default::default(),
+            viewport_id: ViewportId::root(),
+            screen_rect: Rect::invalid(),
+            viewports: Default::default(),
             available_rect: Rect::invalid(),
             unused_rect: Rect::invalid(),
             used_by_panels: Rect::invalid(),
             tooltip_rect: None,
             scroll_delta: Vec2::zero(),
-            scroll_target: None,
+            scroll_target: [None; 2],
+            widgets: Default::default(),
+            kb_focus_rect: None,
+            text_cursor_rect: None,
         }
     }
 }
@@ -62,21 +100,73 @@ impl FrameState {
     fn begin_frame(&mut self, input: &InputState) {
         let Self {
             used_ids,
+            viewport_id,
+            screen_rect,
+            viewports,
             available_rect,
             unused_rect,
             used_by_panels,
             tooltip_rect,
             scroll_delta,
             scroll_target,
+            widgets,
+            kb_focus_rect,
+            text_cursor_rect,
         } = self;
 
         used_ids.clear();
+        *viewport_id = ViewportId::root();
+        *screen_rect = input.screen_rect();
+        viewports.clear();
         *available_rect = input.screen_rect();
         *unused_rect = input.screen_rect();
         *used_by_panels = Rect::nothing();
         *tooltip_rect = None;
         *scroll_delta = input.scroll_delta;
-        *scroll_target = None;
+        if let Some(multi_touch) = input.touch.multi_touch() {
+            // Panning with two fingers scrolls:
+            *scroll_delta += multi_touch.translation_delta;
+        }
+        *scroll_target = [None; 2];
+        widgets.clear();
+        *kb_focus_rect = None;
+        *text_cursor_rect = None;
+    }
+
+    /// Start showing things in another viewport, with its own panels.
+    fn enter_viewport(&mut self, viewport_id: ViewportId, screen_rect: Rect) -> ParentViewport {
+        let parent = ParentViewport {
+            viewport_id: self.viewport_id,
+            screen_rect: self.screen_rect,
+            available_rect: self.available_rect,
+            unused_rect: self.unused_rect,
+            used_by_panels: self.used_by_panels,
+            tooltip_rect: self.tooltip_rect,
+        };
+        self.viewport_id = viewport_id;
+        self.screen_rect = screen_rect;
+        self.available_rect = screen_rect;
+        self.unused_rect = screen_rect;
+        self.used_by_panels = Rect::nothing();
+        self.tooltip_rect = None;
+        parent
+    }
+
+    fn exit_viewport(&mut self, parent: ParentViewport) {
+        let ParentViewport {
+            viewport_id,
+            screen_rect,
+            available_rect,
+            unused_rect,
+            used_by_panels,
+            tooltip_rect,
+        } = parent;
+        self.viewport_id = viewport_id;
+        self.screen_rect = screen_rect;
+        self.available_rect = available_rect;
+        self.unused_rect = unused_rect;
+        self.used_by_panels = used_by_panels;
+        self.tooltip_rect = tooltip_rect;
     }
 
     /// How much space is still available after panels has been added.
@@ -112,6 +202,28 @@ impl FrameState {
         self.used_by_panels = self.used_by_panels.union(panel_rect);
     }
 
+    /// Shrink `available_rect`.
+    pub(crate) fn allocate_right_panel(&mut self, panel_rect: Rect) {
+        debug_assert!(
+            panel_rect.min.y == self.available_rect.min.y,
+            "Mismatching panels. You must not create a panel from within another panel."
+        );
+        self.available_rect.max.x = panel_rect.min.x;
+        self.unused_rect.max.x = panel_rect.min.x;
+        self.used_by_panels = self.used_by_panels.union(panel_rect);
+    }
+
+    /// Shrink `available_rect`.
+    pub(crate) fn allocate_bottom_panel(&mut self, panel_rect: Rect) {
+        debug_assert!(
+            panel_rect.min.x == self.available_rect.min.x,
+            "Mismatching panels. You must not create a panel from within another panel."
+        );
+        self.available_rect.max.y = panel_rect.min.y;
+        self.unused_rect.max.y = panel_rect.min.y;
+        self.used_by_panels = self.used_by_panels.union(panel_rect);
+    }
+
     pub(crate) fn allocate_central_panel(&mut self, panel_rect: Rect) {
         // Note: we do not shrink `available_rect`, because
         // we allow windows to cover the CentralPanel.
@@ -166,13 +278,88 @@ impl Default for CtxRef {
 
 impl CtxRef {
     /// Call at the start of every frame.
-    /// Put your widgets into a [`SidePanel`], [`TopPanel`], [`CentralPanel`], [`Window`] or [`Area`].
+    /// Put your widgets into a [`SidePanel`], [`TopPanel`], [`BottomPanel`], [`CentralPanel`], [`Window`] or [`Area`].
     pub fn begin_frame(&mut self, new_input: RawInput) {
         let mut self_: Context = (*self.0).clone();
         self_.begin_frame_mut(new_input);
         *self = Self(Arc::new(self_));
     }
 
+    /// Call at the end of each frame.
+    /// Returns what has happened this frame (`Output`) as well as what you need to paint.
+    /// You can transform the returned paint commands into triangles with a call to
+    /// `Context::tessellate`.
+    #[must_use]
+    pub fn end_frame(&self) -> (Output, Vec<(Rect, PaintCmd)>) {
+        if self.input.wants_repaint() {
+            self.request_repaint();
+        }
+
+        crate::toasts::show_toasts(self);
+        self.paint_kb_focus();
+        self.memory().end_frame(&self.frame_state().used_ids);
+
+        let mut output: Output = std::mem::take(&mut self.output());
+        if self.repaint_requests.load(SeqCst) > 0 {
+            self.repaint_requests.fetch_sub(1, SeqCst);
+            output.needs_repaint = true;
+        }
+
+        if self.memory().options.accessibility {
+            let memory = self.memory();
+            output.accessibility_tree = Some(AccessibilityTree::from_widgets(
+                &self.frame_state().widgets,
+                memory.areas.order(),
+            ));
+        }
+
+        let text_cursor_rect = self.frame_state().text_cursor_rect.take();
+        let text_cursor_rect_in = |viewport_id: ViewportId| {
+            text_cursor_rect
+                .filter(|(id, _)| *id == viewport_id)
+                .map(|(_, rect)| rect)
+        };
+        output.text_cursor_rect = text_cursor_rect_in(ViewportId::root());
+
+        let viewports = std::mem::take(&mut self.frame_state().viewports);
+        for (id, settings) in viewports {
+            output.viewports.push(ViewportOutput {
+                id,
+                settings,
+                paint_commands: self.drain_paint_lists(id),
+                text_cursor_rect: text_cursor_rect_in(id),
+            });
+        }
+
+        let paint_commands = self.drain_paint_lists(ViewportId::root());
+        (output, paint_commands)
+    }
+
+    /// Show things in another viewport. See [`crate::viewport::Viewport`].
+    pub(crate) fn in_viewport(
+        &self,
+        viewport_id: ViewportId,
+        screen_rect: Rect,
+        add_contents: impl FnOnce(&CtxRef),
+    ) {
+        let parent = self.frame_state().enter_viewport(viewport_id, screen_rect);
+
+        // So panels and background ui can catch clicks:
+        self.memory().areas.set_state(
+            viewport_id.background_layer(),
+            viewport_id,
+            containers::area::State {
+                pos: screen_rect.min,
+                size: screen_rect.size(),
+                interactable: true,
+            },
+        );
+
+        add_contents(self);
+
+        self.frame_state().exit_viewport(parent);
+    }
+
     // ---------------------------------------------------------------------
 
     /// If the given [`Id`] is not unique, an error will be printed at the given position.
@@ -226,7 +413,13 @@ impl CtxRef {
     ) -> Response {
         let interact_rect = rect.expand2((0.5 * item_spacing).min(Vec2::splat(5.0))); // make it easier to click
         let hovered = self.rect_contains_mouse(layer_id, clip_rect.intersect(interact_rect));
-        self.interact_with_hovered(layer_id, id, rect, sense, hovered)
+        let response = self.interact_with_hovered(layer_id, id, rect, sense, hovered);
+        if response.has_kb_focus && sense.focusable {
+            // Painted at the end of the frame, so that it ends up on top of the widget.
+            let viewport_id = self.viewport_id();
+            self.frame_state().kb_focus_rect = Some((viewport_id, layer_id, clip_rect, rect));
+        }
+        response
     }
 
     /// You specify if a thing is hovered, and the function gives a `Response`.
@@ -237,6 +430,77 @@ impl CtxRef {
         rect: Rect,
         sense: Sense,
         hovered: bool,
+    ) -> Response {
+        let blocked_by_modal = self.memory().areas.is_blocked_by_modal(layer_id);
+        if blocked_by_modal {
+            // Something modal is on top of us, so no keyboard focus here:
+            self.memory().surrender_kb_focus(id);
+        } else if sense.focusable && layer_id.allow_interaction() {
+            self.memory().interested_in_kb_focus(id);
+        }
+        let hovered = hovered && !blocked_by_modal;
+
+        let mut response = self.interact_with_hovered_impl(layer_id, id, rect, sense, hovered);
+
+        if response.has_kb_focus
+            && sense.click
+            && !sense.text_input
+            && (self.input.key_pressed(Key::Space) || self.input.key_pressed(Key::Enter))
+        {
+            // Activate with the keyboard:
+            response.clicked = true;
+        }
+
+        if response.hovered && sense.click {
+            let mouse = &self.input.mouse;
+            response.secondary_clicked = mouse.button_clicked(MouseButton::Secondary);
+            response.middle_clicked = mouse.button_clicked(MouseButton::Middle);
+        }
+
+        if self.is_recording_widgets() {
+            self.record_widget(&response);
+        }
+        response
+    }
+
+    fn record_widget(&self, response: &Response) {
+        let mut frame_state = self.frame_state();
+        let existing = frame_state
+            .widgets
+            .iter_mut()
+            .rev()
+            .find(|w| w.id == response.id);
+        if let Some(existing) = existing {
+            // The same widget interacted with again,
+            // e.g. with `Response::interact` or for each row of a wrapping label.
+            existing.rect = existing.rect.union(response.rect);
+            existing.sense = existing.sense.union(response.sense);
+            existing.hovered |= response.hovered;
+            existing.clicked |= response.clicked;
+            existing.active |= response.active;
+            existing.has_kb_focus |= response.has_kb_focus;
+        } else {
+            frame_state.widgets.push(WidgetRecord {
+                id: response.id,
+                layer_id: response.layer_id,
+                rect: response.rect,
+                sense: response.sense,
+                hovered: response.hovered,
+                clicked: response.clicked,
+                active: response.active,
+                has_kb_focus: response.has_kb_focus,
+                info: None,
+            });
+        }
+    }
+
+    fn interact_with_hovered_impl(
+        &self,
+        layer_id: LayerId,
+        id: Id,
+        rect: Rect,
+        sense: Sense,
+        hovered: bool,
     ) -> Response {
         let has_kb_focus = self.memory().has_kb_focus(id);
 
@@ -255,9 +519,12 @@ impl CtxRef {
                 hovered,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active: false,
                 has_kb_focus,
                 lost_kb_focus,
+                changed: false,
             };
         }
 
@@ -282,9 +549,12 @@ impl CtxRef {
                     hovered: true,
                     clicked: false,
                     double_clicked: false,
+                    secondary_clicked: false,
+                    middle_clicked: false,
                     active: false,
                     has_kb_focus,
                     lost_kb_focus,
+                    changed: false,
                 };
 
                 if sense.click && memory.interaction.click_id.is_none() {
@@ -315,9 +585,12 @@ impl CtxRef {
                     hovered,
                     clicked: false,
                     double_clicked: false,
+                    secondary_clicked: false,
+                    middle_clicked: false,
                     active: false,
                     has_kb_focus,
                     lost_kb_focus,
+                    changed: false,
                 }
             }
         } else if self.input.mouse.released {
@@ -331,9 +604,12 @@ impl CtxRef {
                 hovered,
                 clicked,
                 double_clicked: clicked && self.input.mouse.double_click,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
+                changed: false,
             }
         } else if self.input.mouse.down {
             Response {
@@ -345,9 +621,12 @@ impl CtxRef {
                 hovered: hovered && active,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
+                changed: false,
             }
         } else {
             Response {
@@ -359,9 +638,12 @@ impl CtxRef {
                 hovered,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
+                changed: false,
             }
         }
     }
@@ -458,6 +740,32 @@ impl Context {
         &self.input
     }
 
+    /// Was the shortcut pressed this frame? If so, the key press is marked as handled,
+    /// so that later calls with the same shortcut this frame return `false`,
+    /// and widgets (like a focused [`TextEdit`]) and [`InputState::key_pressed`] will ignore it.
+    ///
+    /// See [`KeyboardShortcut`] for an example.
+    pub fn consume_shortcut(&self, shortcut: &KeyboardShortcut) -> bool {
+        let input = &self.input;
+        let index = (0..input.events.len())
+            .find(|&index| !input.is_consumed(index) && shortcut.matches(&input.events[index]));
+        if let Some(index) = index {
+            input.consume_event(index);
+        }
+        index.is_some()
+    }
+
+    /// The viewport we are currently showing things in. See [`crate::viewport`].
+    pub fn viewport_id(&self) -> ViewportId {
+        self.frame_state().viewport_id
+    }
+
+    /// Position and size of the viewport we are currently showing things in.
+    /// In the root viewport this is the same as `ctx.input().screen_rect()`.
+    pub fn screen_rect(&self) -> Rect {
+        self.frame_state().screen_rect
+    }
+
     /// Not valid until first call to [`CtxRef::begin_frame()`].
     /// That's because since we don't know the proper `pixels_per_point` until then.
     pub fn fonts(&self) -> &Fonts {
@@ -467,6 +775,14 @@ impl Context {
             .expect("No fonts available until first call to CtxRef::begin_frame()")
     }
 
+    /// Like [`Self::fonts`], but shared, so you can tell (with `Arc::ptr_eq`) if the fonts have been replaced,
+    /// e.g. because the font definitions or `pixels_per_point` changed.
+    pub(crate) fn fonts_arc(&self) -> Arc<Fonts> {
+        self.fonts
+            .clone()
+            .expect("No fonts available until first call to CtxRef::begin_frame()")
+    }
+
     /// The Egui texture, containing font characters etc.
     /// Not valid until first call to [`CtxRef::begin_frame()`].
     /// That's because since we don't know the proper `pixels_per_point` until then.
@@ -490,6 +806,20 @@ impl Context {
         self.memory().options.style = style.into();
     }
 
+    /// Build an [`AccessibilityTree`] each frame and return it in [`Output::accessibility_tree`],
+    /// so that the integration can forward it to a screen reader.
+    ///
+    /// Off by default, since it costs a bit of performance.
+    pub fn set_accessibility(&self, enabled: bool) {
+        self.memory().options.accessibility = enabled;
+    }
+
+    /// Are we keeping a record of all the widgets laid out this frame?
+    pub(crate) fn is_recording_widgets(&self) -> bool {
+        let options = &self.memory().options;
+        options.record_widgets || options.accessibility
+    }
+
     /// The number of physical pixels for each logical point.
     pub fn pixels_per_point(&self) -> f32 {
         self.input.pixels_per_point()
@@ -529,13 +859,13 @@ impl Context {
         if window.width() > screen.width() {
             // Allow overlapping side bars.
             // This is important for small screens, e.g. mobiles running the web demo.
-            screen.max.x = self.input().screen_rect().max.x;
-            screen.min.x = self.input().screen_rect().min.x;
+            screen.max.x = self.screen_rect().max.x;
+            screen.min.x = self.screen_rect().min.x;
         }
         if window.height() > screen.height() {
             // Allow overlapping top/bottom bars:
-            screen.max.y = self.input().screen_rect().max.y;
-            screen.min.y = self.input().screen_rect().min.y;
+            screen.max.y = self.screen_rect().max.y;
+            screen.min.y = self.screen_rect().min.y;
         }
 
         let mut pos = window.min;
@@ -582,6 +912,7 @@ impl Context {
         let screen_rect = self.input.screen_rect();
         self.memory().areas.set_state(
             LayerId::background(),
+            ViewportId::root(),
             containers::area::State {
                 pos: screen_rect.min,
                 size: screen_rect.size(),
@@ -590,31 +921,27 @@ impl Context {
         );
     }
 
-    /// Call at the end of each frame.
-    /// Returns what has happened this frame (`Output`) as well as what you need to paint.
-    /// You can transform the returned paint commands into triangles with a call to
-    /// `Context::tessellate`.
-    #[must_use]
-    pub fn end_frame(&self) -> (Output, Vec<(Rect, PaintCmd)>) {
-        if self.input.wants_repaint() {
-            self.request_repaint();
-        }
-
-        self.memory().end_frame(&self.frame_state().used_ids);
-
-        let mut output: Output = std::mem::take(&mut self.output());
-        if self.repaint_requests.load(SeqCst) > 0 {
-            self.repaint_requests.fetch_sub(1, SeqCst);
-            output.needs_repaint = true;
+    fn paint_kb_focus(&self) {
+        let kb_focus_rect = self.frame_state().kb_focus_rect.take();
+        if let Some((viewport_id, layer_id, clip_rect, rect)) = kb_focus_rect {
+            let visuals = &self.style().visuals;
+            let paint_cmd = PaintCmd::Rect {
+                rect: rect.expand(2.0),
+                corner_radius: visuals.widgets.inactive.corner_radius,
+                fill: Default::default(),
+                stroke: visuals.focus_stroke,
+            };
+            self.graphics()
+                .list(viewport_id, layer_id)
+                .add(clip_rect, paint_cmd);
         }
-
-        let paint_commands = self.drain_paint_lists();
-        (output, paint_commands)
     }
 
-    fn drain_paint_lists(&self) -> Vec<(Rect, PaintCmd)> {
+    fn drain_paint_lists(&self, viewport_id: ViewportId) -> Vec<(Rect, PaintCmd)> {
         let memory = self.memory();
-        self.graphics().drain(memory.areas.order()).collect()
+        self.graphics()
+            .drain(viewport_id, memory.areas.order())
+            .collect()
     }
 
     /// Tessellate the given paint commands into triangle meshes.
@@ -692,12 +1019,15 @@ impl Context {
     /// Move all the graphics at the given layer.
     /// Can be used to implement drag-and-drop (see relevant demo).
     pub fn translate_layer(&self, layer_id: LayerId, delta: Vec2) {
-        self.graphics().list(layer_id).translate(delta);
+        let viewport_id = self.viewport_id();
+        self.graphics().list(viewport_id, layer_id).translate(delta);
     }
 
+    /// The top-most layer at the given position of the viewport the mouse is in.
     pub fn layer_id_at(&self, pos: Pos2) -> Option<LayerId> {
         let resize_grab_radius_side = self.style().interaction.resize_grab_radius_side;
-        self.memory().layer_id_at(pos, resize_grab_radius_side)
+        self.memory()
+            .layer_id_at(pos, self.input.viewport_id, resize_grab_radius_side)
     }
 
     pub(crate) fn rect_contains_mouse(&self, layer_id: LayerId, rect: Rect) -> bool {
@@ -856,6 +1186,20 @@ impl Context {
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label(format!("{} panels", self.memory().panels.len()));
+            if ui.button("Reset").clicked {
+                self.memory().panels = Default::default();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} tables", self.memory().table.len()));
+            if ui.button("Reset").clicked {
+                self.memory().table = Default::default();
+            }
+        });
+
         ui.horizontal(|ui| {
             ui.label(format!("{} resize areas", self.memory().resize.len()));
             if ui.button("Reset").clicked {
@@ -894,3 +1238,136 @@ impl paint::TessellationOptions {
         ui.checkbox(debug_ignore_clip_rects, "Ignore clip rectangles (debug)");
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_tab_focus_order() {
+    let mut text = String::new();
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let _ = ui.button("First");
+            ui.label("Not focusable");
+            let _ = ui.button("Second");
+            ui.text_edit_singleline(&mut text);
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+    let focused = |harness: &testing::Harness| {
+        let focused: Vec<_> = harness
+            .widgets()
+            .iter()
+            .filter(|w| w.has_kb_focus)
+            .collect();
+        assert!(focused.len() <= 1);
+        focused
+            .first()
+            .map(|w| (w.info.as_ref().unwrap().typ, w.label().map(String::from)))
+    };
+    assert_eq!(focused(&harness), None);
+
+    let button = |label: &str| Some((WidgetType::Button, Some(label.to_owned())));
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    assert_eq!(focused(&harness), button("First"));
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    assert_eq!(focused(&harness), button("Second"));
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    assert_eq!(focused(&harness).unwrap().0, WidgetType::TextEdit);
+
+    harness.set_modifiers(Modifiers {
+        shift: true,
+        ..Default::default()
+    });
+    // The previous widget has already been laid out, so it shows focus from the next frame on:
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert_eq!(focused(&harness), button("Second"));
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert_eq!(focused(&harness), button("First"));
+}
+
+#[test]
+fn test_tab_to_only_widget() {
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let _ = ui.button("Only");
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    assert!(harness.expect_widget("Only").has_kb_focus);
+}
+
+#[test]
+fn test_keyboard_activation() {
+    let mut clicks = 0;
+    let mut text = String::new();
+    let mut text_edit_clicked = false;
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Click me").clicked {
+                clicks += 1;
+            }
+            text_edit_clicked |= ui.text_edit_singleline(&mut text).clicked;
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.press_key(Key::Space);
+    harness.run(&mut app);
+    harness.press_key(Key::Enter);
+    harness.run(&mut app);
+
+    // Move on to the text edit and type into it:
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.type_text("a b");
+    harness.press_key(Key::Space);
+    harness.press_key(Key::Enter);
+    harness.run(&mut app);
+
+    assert_eq!(clicks, 2);
+    assert_eq!(text, "a b");
+    assert!(!text_edit_clicked);
+}
+
+#[test]
+fn test_arrow_keys_change_values() {
+    let mut slider_value = 50.0_f32;
+    let mut drag_value = 10.0_f32;
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.add(Slider::f32(&mut slider_value, 0.0..=100.0));
+            ui.add(DragValue::f32(&mut drag_value).speed(1.0));
+        });
+    };
+
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.press_key(Key::ArrowRight);
+    harness.run(&mut app);
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.press_key(Key::ArrowDown);
+    harness.press_key(Key::ArrowDown);
+    harness.run(&mut app);
+
+    assert_eq!(slider_value, 51.0);
+    assert_eq!(drag_value, 8.0);
+}
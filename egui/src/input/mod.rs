@@ -0,0 +1,1225 @@
+//! The input needed by Egui.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    math::*,
+    mutex::Mutex,
+    util::History,
+    viewport::{ViewportId, ViewportInfo},
+};
+
+mod touch;
+
+pub use touch::*;
+
+/// If mouse moves more than this, it is no longer a click (but maybe a drag)
+const MAX_CLICK_DIST: f32 = 6.0;
+/// The new mouse press must come within this many seconds from previous mouse release
+const MAX_CLICK_DELAY: f64 = 0.3;
+
+/// What the integrations provides to Egui at the start of each frame.
+///
+/// Set the values that make sense, leave the rest at their `Default::default()`.
+///
+/// All coordinates are in points (logical pixels) with origin (0, 0) in the top left corner.
+#[derive(Clone, Debug)]
+pub struct RawInput {
+    /// Which mouse buttons are currently down, indexed by `MouseButton as usize`.
+    ///
+    /// For touch screens, use the primary button for a finger on the screen.
+    pub mouse_down: [bool; NUM_MOUSE_BUTTONS],
+
+    /// Current position of the mouse in points.
+    pub mouse_pos: Option<Pos2>,
+
+    /// How many points (logical pixels) the user scrolled
+    pub scroll_delta: Vec2,
+
+    #[deprecated = "Use screen_rect instead: `Some(Rect::from_pos_size(Default::default(), vec2(window_width, window_height)))`"]
+    pub screen_size: Vec2,
+
+    /// Position and size of the area that Egui should use.
+    /// Usually you would set this to
+    ///
+    /// `Some(Rect::from_pos_size(Default::default(), vec2(window_width, window_height)))`.
+    ///
+    /// but you could also constrain Egui to some smaller portion of your window if you like.
+    ///
+    /// `None` will be treated as "same as last frame", with the default being a very big area.
+    pub screen_rect: Option<Rect>,
+
+    /// Also known as device pixel ratio, > 1 for HDPI screens.
+    /// If text looks blurry on high resolution screens, you probably forgot to set this.
+    pub pixels_per_point: Option<f32>,
+
+    /// Monotonically increasing time, in seconds. Relative to whatever. Used for animations.
+    /// If `None` is provided, Egui will assume a time delta of `predicted_dt` (default 1/60 seconds).
+    pub time: Option<f64>,
+
+    /// Should be set to the expected time between frames when painting at vsync speeds.
+    /// The default for this is 1/60.
+    /// Can safely be left at its default value.
+    pub predicted_dt: f32,
+
+    /// Which modifier keys are down at the start of the frame?
+    pub modifiers: Modifiers,
+
+    /// In-order events received this frame
+    pub events: Vec<Event>,
+
+    /// The viewport that `mouse_pos`, `mouse_down` and `scroll_delta` refer to,
+    /// i.e. the native window the mouse is in. See [`crate::viewport`].
+    pub viewport_id: ViewportId,
+
+    /// The open viewports, except for the root one (which uses `screen_rect`).
+    /// Leave empty if the integration doesn't support viewports.
+    pub viewports: HashMap<ViewportId, ViewportInfo>,
+}
+
+impl Default for RawInput {
+    fn default() -> Self {
+        #![allow(deprecated)] // for screen_size
+        Self {
+            mouse_down: [false; NUM_MOUSE_BUTTONS],
+            mouse_pos: None,
+            scroll_delta: Vec2::zero(),
+            screen_size: Default::default(),
+            screen_rect: None,
+            pixels_per_point: None,
+            time: None,
+            predicted_dt: 1.0 / 60.0,
+            modifiers: Modifiers::default(),
+            events: vec![],
+            viewport_id: ViewportId::root(),
+            viewports: Default::default(),
+        }
+    }
+}
+
+impl RawInput {
+    /// Helper: move volatile (deltas and events), clone the rest
+    pub fn take(&mut self) -> RawInput {
+        #![allow(deprecated)] // for screen_size
+        let viewports = self.viewports.clone();
+        for info in self.viewports.values_mut() {
+            info.close_requested = false;
+        }
+        RawInput {
+            mouse_down: self.mouse_down,
+            mouse_pos: self.mouse_pos,
+            scroll_delta: std::mem::take(&mut self.scroll_delta),
+            screen_size: self.screen_size,
+            screen_rect: self.screen_rect,
+            pixels_per_point: self.pixels_per_point,
+            time: self.time,
+            predicted_dt: self.predicted_dt,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            viewport_id: self.viewport_id,
+            viewports,
+        }
+    }
+}
+
+/// Input state that Egui updates each frame.
+#[derive(Clone, Debug)]
+pub struct InputState {
+    /// The raw input we got this frame
+    pub raw: RawInput,
+
+    pub mouse: MouseInput,
+
+    /// How many pixels the user scrolled.
+    /// When shift is held down, vertical scrolling is turned into horizontal scrolling.
+    pub scroll_delta: Vec2,
+
+    /// Position and size of the Egui area.
+    pub screen_rect: Rect,
+
+    /// Also known as device pixel ratio, > 1 for HDPI screens.
+    pub pixels_per_point: f32,
+
+    /// Time in seconds. Relative to whatever. Used for animation.
+    pub time: f64,
+
+    /// Time since last frame, in seconds.
+    /// This can be very unstable in reactive mode (when we don't paint each frame).
+    pub unstable_dt: f32,
+
+    /// Used for animations to get instant feedback (avoid frame delay).
+    /// Should be set to the expected time between frames when painting at vsync speeds.
+    pub predicted_dt: f32,
+
+    /// Which modifier keys are down at the start of the frame?
+    pub modifiers: Modifiers,
+
+    /// The keys that are down (according to the key events so far).
+    pub keys_down: HashSet<Key>,
+
+    /// The fingers on the touch screen, and the gestures they make.
+    pub touch: TouchInput,
+
+    /// In-order events received this frame
+    pub events: Vec<Event>,
+
+    /// Indices into [`Self::events`] of the events that have already been handled this frame,
+    /// e.g. by [`crate::Context::consume_shortcut`].
+    consumed_events: Mutex<Vec<usize>>,
+
+    /// The viewport the mouse is in. See [`crate::viewport`].
+    pub viewport_id: ViewportId,
+
+    /// The open viewports, except for the root one.
+    pub viewports: HashMap<ViewportId, ViewportInfo>,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            raw: Default::default(),
+            mouse: Default::default(),
+            scroll_delta: Default::default(),
+            screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
+            pixels_per_point: 1.0,
+            time: 0.0,
+            unstable_dt: 1.0 / 6.0,
+            predicted_dt: 1.0 / 6.0,
+            modifiers: Default::default(),
+            keys_down: Default::default(),
+            touch: Default::default(),
+            events: Default::default(),
+            consumed_events: Default::default(),
+            viewport_id: Default::default(),
+            viewports: Default::default(),
+        }
+    }
+}
+
+/// The mouse buttons Egui knows about.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MouseButton {
+    /// The primary mouse button is usually the left one.
+    Primary = 0,
+    /// The secondary mouse button is usually the right one,
+    /// and most often used for context menus.
+    Secondary = 1,
+    /// The middle mouse button, usually the scroll wheel.
+    Middle = 2,
+}
+
+/// The number of variants of [`MouseButton`].
+pub const NUM_MOUSE_BUTTONS: usize = 3;
+
+/// The state of one mouse button.
+#[derive(Clone, Copy, Debug, Default)]
+struct ButtonState {
+    down: bool,
+    pressed: bool,
+    released: bool,
+    click: bool,
+}
+
+/// Mouse (or touch) state.
+///
+/// The `down`, `pressed`, `released`, `click` and `double_click` fields are for the primary button.
+/// Use [`Self::button_down`], [`Self::button_clicked`] etc for the other buttons.
+#[derive(Clone, Debug)]
+pub struct MouseInput {
+    /// Is the primary button currently down?
+    /// true the frame when it is pressed,
+    /// false the frame it is released.
+    pub down: bool,
+
+    /// The primary button went from !down to down
+    pub pressed: bool,
+
+    /// The primary button went from down to !down
+    pub released: bool,
+
+    /// If the mouse is down, will it register as a click when released?
+    /// Set to true on mouse down, set to false when mouse moves too much.
+    pub could_be_click: bool,
+
+    /// Was there a click?
+    /// Did the primary button get released this frame closely after going down?
+    pub click: bool,
+
+    /// Was there a double-click with the primary button?
+    pub double_click: bool,
+
+    /// When did the primary button get clicked last?
+    /// Used to check for double-clicks.
+    pub last_click_time: f64,
+
+    /// How many times the primary button has been pressed in quick succession, at about the same spot:
+    /// `1` after a single press, `2` from the second press of a double-click, `3` for a triple-click etc.
+    ///
+    /// Unlike [`Self::double_click`] this is known already when the button goes down,
+    /// and stays the same while dragging.
+    pub press_count: u32,
+
+    /// When and where the primary button was last pressed.
+    /// Used for counting presses.
+    last_press: Option<(f64, Pos2)>,
+
+    /// Current position of the mouse in points.
+    /// None for touch screens when finger is not down.
+    pub pos: Option<Pos2>,
+
+    /// Where did the current click/drag originate?
+    pub press_origin: Option<Pos2>,
+
+    /// How much the mouse moved compared to last frame, in points.
+    pub delta: Vec2,
+
+    /// Current velocity of mouse cursor.
+    pub velocity: Vec2,
+
+    /// Recent movement of the mouse.
+    /// Used for calculating velocity of mouse pointer.
+    pos_history: History<Pos2>,
+
+    /// The state of each button, indexed by `MouseButton as usize`.
+    buttons: [ButtonState; NUM_MOUSE_BUTTONS],
+}
+
+impl Default for MouseInput {
+    fn default() -> Self {
+        Self {
+            down: false,
+            pressed: false,
+            released: false,
+            could_be_click: false,
+            click: false,
+            double_click: false,
+            last_click_time: std::f64::NEG_INFINITY,
+            press_count: 0,
+            last_press: None,
+            pos: None,
+            press_origin: None,
+            delta: Vec2::zero(),
+            velocity: Vec2::zero(),
+            pos_history: History::new(1000, 0.1),
+            buttons: Default::default(),
+        }
+    }
+}
+
+/// An input event generated by the integration.
+///
+/// This only covers events that Egui cares about.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The integration detected a "copy" event (e.g. Cmd+C).
+    Copy,
+    /// The integration detected a "cut" event (e.g. Cmd+X).
+    Cut,
+    /// Text input, e.g. via keyboard or paste action.
+    ///
+    /// When the user presses enter/return, do not send a `Text` (just [`Key::Enter`]).
+    Text(String),
+    Key {
+        key: Key,
+        pressed: bool,
+        modifiers: Modifiers,
+    },
+    /// An IME (input method editor, used e.g. for Chinese and Japanese) started composing text.
+    CompositionStart,
+    /// The text being composed with an IME changed.
+    /// This "preedit" text is only shown, it is not part of the edited text yet.
+    CompositionUpdate(String),
+    /// The IME composition is done, and this text should be inserted
+    /// (empty if the composition was cancelled).
+    CompositionEnd(String),
+    /// A finger on a touch screen.
+    ///
+    /// Integrations should also move the mouse with the first finger,
+    /// so that widgets that only know about the mouse keep working.
+    Touch {
+        /// Stays the same for as long as the finger touches the screen.
+        id: TouchId,
+        phase: TouchPhase,
+        /// Position in points.
+        pos: Pos2,
+        /// Pressure in the range `0..=1`, if the device knows it.
+        force: Option<f32>,
+    },
+}
+
+/// State of the modifier keys. These must be fed to Egui.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Modifiers {
+    /// Either of the alt keys are down (option ⌥ on Mac)
+    pub alt: bool,
+    /// Either of the control keys are down
+    pub ctrl: bool,
+    /// Either of the shift keys are down
+    pub shift: bool,
+    /// The Mac ⌘ Command key. Should always be set to `false` on other platforms.
+    pub mac_cmd: bool,
+    /// On Mac, this should be set whenever one of the ⌘ Command keys are down (same as `mac_cmd`).
+    /// On Windows and Linux, set this to the same value as `ctrl`.
+    /// This is so that Egui can, for instance, select all text by checking for `command + A`
+    /// and it will work on both Mac and Windows.
+    pub command: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        alt: false,
+        ctrl: false,
+        shift: false,
+        mac_cmd: false,
+        command: false,
+    };
+
+    pub const ALT: Self = Self {
+        alt: true,
+        ..Self::NONE
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+    /// The Mac ⌘ Command key. You probably want [`Self::COMMAND`] instead.
+    pub const MAC_CMD: Self = Self {
+        mac_cmd: true,
+        ..Self::NONE
+    };
+    /// Ctrl on Windows and Linux, ⌘ Command on Mac.
+    pub const COMMAND: Self = Self {
+        command: true,
+        ..Self::NONE
+    };
+
+    /// Combine two sets of modifiers, e.g. `Modifiers::COMMAND.plus(Modifiers::SHIFT)`.
+    pub const fn plus(self, rhs: Self) -> Self {
+        Self {
+            alt: self.alt | rhs.alt,
+            ctrl: self.ctrl | rhs.ctrl,
+            shift: self.shift | rhs.shift,
+            mac_cmd: self.mac_cmd | rhs.mac_cmd,
+            command: self.command | rhs.command,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Are exactly the modifiers of `pattern` down (no more, no less)?
+    ///
+    /// `command` in the `pattern` is either ctrl or ⌘ Command, depending on the platform.
+    pub fn matches(&self, pattern: Modifiers) -> bool {
+        if self.alt != pattern.alt || self.shift != pattern.shift {
+            return false;
+        }
+        if pattern.command {
+            // `command` is `mac_cmd` on Mac and `ctrl` everywhere else:
+            self.command
+                && self.ctrl == (pattern.ctrl || !self.mac_cmd)
+                && (self.mac_cmd || !pattern.mac_cmd)
+        } else {
+            self.ctrl == pattern.ctrl && self.mac_cmd == pattern.mac_cmd
+        }
+    }
+}
+
+/// Keyboard key name.
+///
+/// Keys are named after what is printed on them on a US keyboard,
+/// regardless of modifiers (e.g. shift+[`Key::Num1`] and not `!`).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Key {
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    Backspace,
+    Delete,
+    End,
+    Enter,
+    Space,
+    Escape,
+    Home,
+    Insert,
+    PageDown,
+    PageUp,
+    Tab,
+
+    /// `-`
+    Minus,
+    /// `=`
+    Equals,
+    /// `+`, e.g. on the numpad
+    Plus,
+    /// `,`
+    Comma,
+    /// `.`
+    Period,
+    /// `;`
+    Semicolon,
+    /// `/`
+    Slash,
+    /// `\`
+    Backslash,
+    /// `'`
+    Quote,
+    /// `` ` ``
+    Backtick,
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+
+    /// Either from the main row or from the numpad.
+    Num0,
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    Num6,
+    Num7,
+    Num8,
+    Num9,
+
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+}
+
+impl Key {
+    /// A short name of the key for showing to the user, e.g. `"A"`, `"F5"`, `"Enter"` or `"-"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::ArrowDown => "Down",
+            Key::ArrowLeft => "Left",
+            Key::ArrowRight => "Right",
+            Key::ArrowUp => "Up",
+            Key::Backspace => "Backspace",
+            Key::Delete => "Delete",
+            Key::End => "End",
+            Key::Enter => "Enter",
+            Key::Space => "Space",
+            Key::Escape => "Esc",
+            Key::Home => "Home",
+            Key::Insert => "Insert",
+            Key::PageDown => "PageDown",
+            Key::PageUp => "PageUp",
+            Key::Tab => "Tab",
+
+            Key::Minus => "-",
+            Key::Equals => "=",
+            Key::Plus => "+",
+            Key::Comma => ",",
+            Key::Period => ".",
+            Key::Semicolon => ";",
+            Key::Slash => "/",
+            Key::Backslash => "\\",
+            Key::Quote => "'",
+            Key::Backtick => "`",
+            Key::OpenBracket => "[",
+            Key::CloseBracket => "]",
+
+            Key::Num0 => "0",
+            Key::Num1 => "1",
+            Key::Num2 => "2",
+            Key::Num3 => "3",
+            Key::Num4 => "4",
+            Key::Num5 => "5",
+            Key::Num6 => "6",
+            Key::Num7 => "7",
+            Key::Num8 => "8",
+            Key::Num9 => "9",
+
+            Key::A => "A",
+            Key::B => "B",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::I => "I",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::N => "N",
+            Key::O => "O",
+            Key::P => "P",
+            Key::Q => "Q",
+            Key::R => "R",
+            Key::S => "S",
+            Key::T => "T",
+            Key::U => "U",
+            Key::V => "V",
+            Key::W => "W",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+        }
+    }
+}
+
+/// A key together with the modifiers that must be held down, e.g. ctrl+S.
+///
+/// ```
+/// # let ctx = &egui::CtxRef::default();
+/// use egui::{Key, KeyboardShortcut, Modifiers};
+///
+/// const SAVE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+///
+/// if ctx.consume_shortcut(&SAVE) {
+///     // …
+/// }
+/// assert_eq!(SAVE.format(false), "Ctrl+S");
+/// assert_eq!(SAVE.format(true), "⌘S");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyboardShortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyboardShortcut {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// Is this a press of the shortcut?
+    pub fn matches(&self, event: &Event) -> bool {
+        matches!(
+            event,
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+            } if *key == self.key && modifiers.matches(self.modifiers)
+        )
+    }
+
+    /// How to show the shortcut to the user, e.g. `"Ctrl+Shift+S"`,
+    /// or `"⇧⌘S"` if `is_mac` (using the Mac symbols and order of the modifiers).
+    pub fn format(&self, is_mac: bool) -> String {
+        let Modifiers {
+            alt,
+            ctrl,
+            shift,
+            mac_cmd,
+            command,
+        } = self.modifiers;
+        let mut s = String::new();
+        if is_mac {
+            let names = [
+                (ctrl, "⌃"),
+                (alt, "⌥"),
+                (shift, "⇧"),
+                (mac_cmd || command, "⌘"),
+            ];
+            for (down, name) in &names {
+                if *down {
+                    s += name;
+                }
+            }
+        } else {
+            let names = [
+                (ctrl || command, "Ctrl"),
+                (alt, "Alt"),
+                (shift, "Shift"),
+                (mac_cmd, "Cmd"),
+            ];
+            for (down, name) in &names {
+                if *down {
+                    s += name;
+                    s += "+";
+                }
+            }
+        }
+        s += self.key.name();
+        s
+    }
+}
+
+impl InputState {
+    #[must_use]
+    pub fn begin_frame(self, new: RawInput) -> InputState {
+        #![allow(deprecated)] // for screen_size
+
+        let time = new
+            .time
+            .unwrap_or_else(|| self.time + new.predicted_dt as f64);
+        let unstable_dt = (time - self.time) as f32;
+        let screen_rect = new.screen_rect.unwrap_or_else(|| {
+            if new.screen_size != Default::default() {
+                Rect::from_min_size(Default::default(), new.screen_size) // backwards compatability
+            } else {
+                self.screen_rect
+            }
+        });
+        let mut mouse = self.mouse.begin_frame(time, &new);
+        let touch = self.touch.begin_frame(time, &new.events);
+        if touch.long_pressed() {
+            mouse.long_press();
+        }
+        let mut scroll_delta = new.scroll_delta;
+        if new.modifiers.shift {
+            // Shift + scroll wheel means horizontal scrolling.
+            // Some platforms (e.g. Mac) already do this for us, in which case `y` is zero.
+            scroll_delta = vec2(scroll_delta.x + scroll_delta.y, 0.0);
+        }
+        let mut keys_down = self.keys_down;
+        for event in &new.events {
+            if let Event::Key { key, pressed, .. } = event {
+                if *pressed {
+                    keys_down.insert(*key);
+                } else {
+                    keys_down.remove(key);
+                }
+            }
+        }
+        InputState {
+            mouse,
+            scroll_delta,
+            screen_rect,
+            pixels_per_point: new.pixels_per_point.unwrap_or(self.pixels_per_point),
+            time,
+            unstable_dt,
+            predicted_dt: new.predicted_dt,
+            modifiers: new.modifiers,
+            keys_down,
+            touch,
+            events: new.events.clone(), // TODO: remove clone() and use raw.events
+            consumed_events: Default::default(),
+            viewport_id: new.viewport_id,
+            viewports: new.viewports.clone(),
+            raw: new,
+        }
+    }
+
+    pub fn screen_rect(&self) -> Rect {
+        self.screen_rect
+    }
+
+    pub fn wants_repaint(&self) -> bool {
+        self.mouse.any_pressed()
+            || self.mouse.any_released()
+            || self.mouse.delta != Vec2::zero()
+            || self.scroll_delta != Vec2::zero()
+            || !self.events.is_empty()
+            || self.touch.wants_repaint()
+    }
+
+    /// How much the user zoomed this frame by pinching on a touch screen:
+    /// `> 1` means zoom in, `< 1` means zoom out and `1` means no zoom.
+    pub fn zoom_delta(&self) -> f32 {
+        self.touch
+            .multi_touch()
+            .map_or(1.0, |multi_touch| multi_touch.zoom_delta)
+    }
+
+    /// The events of this frame that have not yet been handled,
+    /// e.g. by [`crate::Context::consume_shortcut`].
+    ///
+    /// Widgets should look at these rather than [`Self::events`].
+    pub fn unconsumed_events(&self) -> impl Iterator<Item = &Event> {
+        self.events
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| !self.is_consumed(*index))
+            .map(|(_, event)| event)
+    }
+
+    /// Has the event at this index in [`Self::events`] already been handled this frame?
+    pub fn is_consumed(&self, index: usize) -> bool {
+        self.consumed_events.lock().contains(&index)
+    }
+
+    /// Mark the event at this index in [`Self::events`] as handled,
+    /// so that it is skipped by [`Self::unconsumed_events`], [`Self::key_pressed`] etc.
+    pub fn consume_event(&self, index: usize) {
+        self.consumed_events.lock().push(index);
+    }
+
+    /// Was the given key pressed this frame (and not consumed by a shortcut)?
+    pub fn key_pressed(&self, desired_key: Key) -> bool {
+        self.unconsumed_events().any(|event| {
+            matches!(
+                event,
+                Event::Key {
+                    key,
+                    pressed: true,
+                    ..
+                } if *key == desired_key
+            )
+        })
+    }
+
+    /// Is the given key currently held down?
+    pub fn key_down(&self, desired_key: Key) -> bool {
+        self.keys_down.contains(&desired_key)
+    }
+
+    /// Was the given key released this frame (and not consumed by a shortcut)?
+    pub fn key_released(&self, desired_key: Key) -> bool {
+        self.unconsumed_events().any(|event| {
+            matches!(
+                event,
+                Event::Key {
+                    key,
+                    pressed: false,
+                    ..
+                } if *key == desired_key
+            )
+        })
+    }
+
+    /// Also known as device pixel ratio, > 1 for HDPI screens.
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
+
+    /// Size of a physical pixel in logical gui coordinates (points).
+    pub fn physical_pixel_size(&self) -> f32 {
+        1.0 / self.pixels_per_point()
+    }
+
+    /// How imprecise do we expect the mouse/touch input to be?
+    /// Returns imprecision in points.
+    pub fn aim_radius(&self) -> f32 {
+        // TODO: multiply by ~3 for touch inputs because fingers are fat
+        self.physical_pixel_size()
+    }
+}
+
+impl MouseInput {
+    #[must_use]
+    pub fn begin_frame(mut self, time: f64, new: &RawInput) -> MouseInput {
+        let delta = new
+            .mouse_pos
+            .and_then(|new| self.pos.map(|last| new - last))
+            .unwrap_or_default();
+
+        let mut buttons = [ButtonState::default(); NUM_MOUSE_BUTTONS];
+        for (i, button) in buttons.iter_mut().enumerate() {
+            let was_down = self.buttons[i].down;
+            let is_down = new.mouse_down[i];
+            let released = was_down && !is_down;
+            *button = ButtonState {
+                down: is_down && new.mouse_pos.is_some(),
+                pressed: !was_down && is_down,
+                released,
+                click: released && self.could_be_click,
+            };
+        }
+        let primary = buttons[MouseButton::Primary as usize];
+
+        let click = primary.click;
+        let double_click = click && (time - self.last_click_time) < MAX_CLICK_DELAY;
+        let mut press_origin = self.press_origin;
+        let mut could_be_click = self.could_be_click;
+        let mut last_click_time = self.last_click_time;
+        if click {
+            last_click_time = time
+        }
+
+        let mut press_count = self.press_count;
+        let mut last_press = self.last_press;
+        if primary.pressed {
+            if let Some(pos) = new.mouse_pos {
+                let quick_succession = self.last_press.map_or(false, |(last_time, last_pos)| {
+                    time - last_time < MAX_CLICK_DELAY && last_pos.distance(pos) < MAX_CLICK_DIST
+                });
+                press_count = if quick_succession { press_count + 1 } else { 1 };
+                last_press = Some((time, pos));
+            }
+        }
+
+        // A click or drag starts when any button is pressed, and lasts as long as any button is down:
+        let any_pressed = buttons.iter().any(|button| button.pressed);
+        if any_pressed {
+            press_origin = new.mouse_pos;
+            could_be_click = true;
+        } else if !self.any_down() || self.pos.is_none() {
+            press_origin = None;
+        }
+
+        if let (Some(press_origin), Some(mouse_pos)) = (new.mouse_pos, press_origin) {
+            could_be_click &= press_origin.distance(mouse_pos) < MAX_CLICK_DIST;
+        } else {
+            could_be_click = false;
+        }
+
+        if any_pressed {
+            // Start of a drag: we want to track the velocity for during the drag
+            // and ignore any incoming movement
+            self.pos_history.clear();
+        }
+
+        if let Some(mouse_pos) = new.mouse_pos {
+            self.pos_history.add(time, mouse_pos);
+        } else {
+            // we do not clear the `mouse_tracker` here, because it is exactly when a finger has
+            // released from the touch screen that we may want to assign a velocity to whatever
+            // the user tried to throw
+        }
+
+        self.pos_history.flush(time);
+        let velocity = if self.pos_history.len() >= 3 && self.pos_history.duration() > 0.01 {
+            self.pos_history.velocity().unwrap_or_default()
+        } else {
+            Vec2::default()
+        };
+
+        MouseInput {
+            down: primary.down,
+            pressed: primary.pressed,
+            released: primary.released,
+            could_be_click,
+            click,
+            double_click,
+            last_click_time,
+            press_count,
+            last_press,
+            pos: new.mouse_pos,
+            press_origin,
+            delta,
+            velocity,
+            pos_history: self.pos_history,
+            buttons,
+        }
+    }
+
+    /// Is this button currently down?
+    pub fn button_down(&self, button: MouseButton) -> bool {
+        self.buttons[button as usize].down
+    }
+
+    /// Did this button go from up to down this frame?
+    pub fn button_pressed(&self, button: MouseButton) -> bool {
+        self.buttons[button as usize].pressed
+    }
+
+    /// Did this button go from down to up this frame?
+    pub fn button_released(&self, button: MouseButton) -> bool {
+        self.buttons[button as usize].released
+    }
+
+    /// Was this button released this frame closely after going down?
+    pub fn button_clicked(&self, button: MouseButton) -> bool {
+        self.buttons[button as usize].click
+    }
+
+    /// Is any mouse button down?
+    pub fn any_down(&self) -> bool {
+        self.buttons.iter().any(|button| button.down)
+    }
+
+    /// Was any mouse button pressed this frame?
+    pub fn any_pressed(&self) -> bool {
+        self.buttons.iter().any(|button| button.pressed)
+    }
+
+    /// Was any mouse button released this frame?
+    pub fn any_released(&self) -> bool {
+        self.buttons.iter().any(|button| button.released)
+    }
+
+    /// A finger was held still on a touch screen: that is a secondary click,
+    /// and lifting the finger later should not also count as a primary click.
+    fn long_press(&mut self) {
+        self.buttons[MouseButton::Secondary as usize].click = true;
+        self.could_be_click = false;
+    }
+}
+
+impl RawInput {
+    pub fn ui(&self, ui: &mut crate::Ui) {
+        #![allow(deprecated)] // for screen_size
+        let Self {
+            mouse_down,
+            mouse_pos,
+            scroll_delta,
+            screen_size: _,
+            screen_rect,
+            pixels_per_point,
+            time,
+            predicted_dt,
+            modifiers,
+            events,
+            viewport_id,
+            viewports,
+        } = self;
+
+        // TODO: simpler way to show values, e.g. `ui.value("Mouse Pos:", self.mouse_pos);
+        // TODO: `ui.style_mut().text_style = TextStyle::Monospace`;
+        ui.label(format!("mouse_down: {:?}", mouse_down));
+        ui.label(format!("mouse_pos: {:.1?}", mouse_pos));
+        ui.label(format!("scroll_delta: {:?} points", scroll_delta));
+        ui.label(format!("screen_rect: {:?} points", screen_rect));
+        ui.label(format!("pixels_per_point: {:?}", pixels_per_point))
+            .on_hover_text(
+                "Also called HDPI factor.\nNumber of physical pixels per each logical pixel.",
+            );
+        if let Some(time) = time {
+            ui.label(format!("time: {:.3} s", time));
+        } else {
+            ui.label("time: None");
+        }
+        ui.label(format!("predicted_dt: {:.1} ms", 1e3 * predicted_dt));
+        ui.label(format!("modifiers: {:#?}", modifiers));
+        ui.label(format!("events: {:?}", events))
+            .on_hover_text("key presses etc");
+        ui.label(format!("viewport_id: {:?}", viewport_id));
+        ui.label(format!("viewports: {:?}", viewports));
+    }
+}
+
+impl InputState {
+    pub fn ui(&self, ui: &mut crate::Ui) {
+        let Self {
+            raw,
+            mouse,
+            scroll_delta,
+            screen_rect,
+            pixels_per_point,
+            time,
+            unstable_dt,
+            predicted_dt,
+            modifiers,
+            keys_down,
+            touch,
+            events,
+            consumed_events: _,
+            viewport_id,
+            viewports,
+        } = self;
+
+        ui.style_mut().body_text_style = crate::paint::TextStyle::Monospace;
+        ui.collapsing("Raw Input", |ui| raw.ui(ui));
+
+        crate::containers::CollapsingHeader::new("🖱 Mouse")
+            .default_open(true)
+            .show(ui, |ui| {
+                mouse.ui(ui);
+            });
+        crate::containers::CollapsingHeader::new("👆 Touch")
+            .default_open(false)
+            .show(ui, |ui| {
+                touch.ui(ui);
+            });
+
+        ui.label(format!("scroll_delta: {:?} points", scroll_delta));
+        ui.label(format!("screen_rect: {:?} points", screen_rect));
+        ui.label(format!(
+            "{:?} physical pixels for each logical point",
+            pixels_per_point
+        ));
+        ui.label(format!("time: {:.3} s", time));
+        ui.label(format!(
+            "time since previous frame: {:.1} ms",
+            1e3 * unstable_dt
+        ));
+        ui.label(format!("predicted_dt: {:.1} ms", 1e3 * predicted_dt));
+        ui.label(format!("modifiers: {:#?}", modifiers));
+        ui.label(format!("keys_down: {:?}", keys_down));
+        ui.label(format!("events: {:?}", events))
+            .on_hover_text("key presses etc");
+        ui.label(format!("viewport_id: {:?}", viewport_id));
+        ui.label(format!("viewports: {:?}", viewports));
+    }
+}
+
+impl MouseInput {
+    pub fn ui(&self, ui: &mut crate::Ui) {
+        let Self {
+            down,
+            pressed,
+            released,
+            could_be_click,
+            click,
+            double_click,
+            last_click_time,
+            press_count,
+            last_press: _,
+            pos,
+            press_origin,
+            delta,
+            velocity,
+            pos_history: _,
+            buttons,
+        } = self;
+
+        ui.label(format!("down: {}", down));
+        ui.label(format!("pressed: {}", pressed));
+        ui.label(format!("released: {}", released));
+        ui.label(format!("could_be_click: {}", could_be_click));
+        ui.label(format!("click: {}", click));
+        ui.label(format!("double_click: {}", double_click));
+        ui.label(format!("last_click_time: {:.3}", last_click_time));
+        ui.label(format!("press_count: {}", press_count));
+        ui.label(format!("pos: {:?}", pos));
+        ui.label(format!("press_origin: {:?}", press_origin));
+        ui.label(format!("delta: {:?}", delta));
+        ui.label(format!(
+            "velocity: [{:3.0} {:3.0}] points/sec",
+            velocity.x, velocity.y
+        ));
+        ui.label(format!("buttons: {:#?}", buttons));
+    }
+}
+
+#[test]
+fn test_keyboard_shortcuts() {
+    let save = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+    let save_as = KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::S);
+
+    let windows_ctrl = Modifiers {
+        ctrl: true,
+        command: true,
+        ..Default::default()
+    };
+    let mac_cmd = Modifiers {
+        mac_cmd: true,
+        command: true,
+        ..Default::default()
+    };
+    assert!(windows_ctrl.matches(save.modifiers));
+    assert!(mac_cmd.matches(save.modifiers));
+    assert!(!Modifiers::CTRL.matches(save.modifiers));
+    assert!(!mac_cmd.plus(Modifiers::CTRL).matches(save.modifiers));
+    assert!(!mac_cmd.matches(save_as.modifiers));
+    assert_eq!(save_as.format(false), "Ctrl+Shift+S");
+    assert_eq!(save_as.format(true), "⇧⌘S");
+
+    let mut harness = crate::testing::Harness::new(vec2(100.0, 100.0));
+    let mut consumed = vec![];
+    let mut keys_down = vec![];
+    {
+        let mut app = |ctx: &crate::CtxRef| {
+            consumed.push((ctx.consume_shortcut(&save), ctx.consume_shortcut(&save)));
+            keys_down.push(ctx.input().key_down(Key::S));
+        };
+        harness.set_modifiers(windows_ctrl);
+        harness.event(Event::Key {
+            key: Key::S,
+            pressed: true,
+            modifiers: windows_ctrl,
+        });
+        harness.run(&mut app);
+        harness.run(&mut app);
+        harness.event(Event::Key {
+            key: Key::S,
+            pressed: false,
+            modifiers: windows_ctrl,
+        });
+        harness.run(&mut app);
+    }
+    assert_eq!(
+        consumed,
+        vec![(true, false), (false, false), (false, false)]
+    );
+    assert_eq!(keys_down, vec![true, true, false]);
+}
+
+#[test]
+fn test_consumed_shortcut_does_not_reach_text_edit() {
+    let delete = KeyboardShortcut::new(Modifiers::default(), Key::Backspace);
+    let mut text = "abc".to_owned();
+    let mut deleted = 0;
+    let mut pressed_after_consume = false;
+    let mut app = |ctx: &crate::CtxRef| {
+        if ctx.consume_shortcut(&delete) {
+            deleted += 1;
+            pressed_after_consume |= ctx.input().key_pressed(Key::Backspace);
+        }
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.text_edit_singleline(&mut text);
+        });
+    };
+
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    harness.run(&mut app);
+    harness.press_key(Key::Tab);
+    harness.run(&mut app);
+    harness.press_key(Key::Backspace);
+    harness.run(&mut app);
+    harness.type_text("d");
+    harness.run(&mut app);
+    drop(app);
+
+    assert_eq!(deleted, 1);
+    assert!(!pressed_after_consume);
+    assert_eq!(text, "abcd");
+}
@@ -0,0 +1,287 @@
+//! The fingers on a touch screen, and the gestures they make.
+
+use crate::math::*;
+
+use super::{Event, MAX_CLICK_DIST};
+
+/// Hold a finger still on the screen for this many seconds to get a secondary click.
+const LONG_PRESS_TIME: f64 = 0.5;
+
+/// Identifies a finger on a touch screen for as long as it touches the screen.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TouchId(pub u64);
+
+/// Where a touch is in its lifetime.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TouchPhase {
+    /// The finger was put on the screen.
+    Start,
+    /// The finger moved.
+    Move,
+    /// The finger was lifted from the screen.
+    End,
+    /// The touch was interrupted, e.g. because the system took over the gesture.
+    Cancel,
+}
+
+/// What two (or more) fingers on a touch screen did this frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiTouchInfo {
+    /// How many fingers are on the screen.
+    pub num_touches: usize,
+    /// Halfway between the first two fingers.
+    pub center: Pos2,
+    /// How much the first two fingers were spread apart this frame:
+    /// `> 1` means zoom in, `< 1` means zoom out and `1` means no zoom.
+    pub zoom_delta: f32,
+    /// How much the first two fingers turned this frame, in radians (clockwise).
+    pub rotation_delta: f32,
+    /// How much the point between the first two fingers moved this frame, in points.
+    pub translation_delta: Vec2,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ActiveTouch {
+    id: TouchId,
+    pos: Pos2,
+    start_pos: Pos2,
+    start_time: f64,
+    force: Option<f32>,
+    /// Can this still turn into a long-press?
+    could_be_long_press: bool,
+}
+
+/// The fingers on the touch screen, fed by [`Event::Touch`].
+///
+/// Integrations usually also move the mouse with the first finger,
+/// so most widgets work on touch screens without looking at this.
+#[derive(Clone, Debug, Default)]
+pub struct TouchInput {
+    /// In the order they touched the screen.
+    touches: Vec<ActiveTouch>,
+    multi_touch: Option<MultiTouchInfo>,
+    long_press: bool,
+}
+
+impl TouchInput {
+    #[must_use]
+    pub(crate) fn begin_frame(self, time: f64, events: &[Event]) -> Self {
+        let mut touches = self.touches;
+        let before = first_two(&touches);
+
+        for event in events {
+            if let Event::Touch {
+                id,
+                phase,
+                pos,
+                force,
+            } = *event
+            {
+                match touches.iter().position(|touch| touch.id == id) {
+                    None => {
+                        if phase == TouchPhase::Start {
+                            touches.push(ActiveTouch {
+                                id,
+                                pos,
+                                start_pos: pos,
+                                start_time: time,
+                                force,
+                                could_be_long_press: true,
+                            });
+                        }
+                    }
+                    Some(index) => match phase {
+                        TouchPhase::Start | TouchPhase::Move => {
+                            touches[index].pos = pos;
+                            touches[index].force = force;
+                        }
+                        TouchPhase::End | TouchPhase::Cancel => {
+                            touches.remove(index);
+                        }
+                    },
+                }
+            }
+        }
+
+        let multi_touch = match (before, first_two(&touches)) {
+            (Some(before), Some(after)) if before.0 == after.0 => {
+                let (before, after) = (before.1, after.1);
+                let (before_vec, after_vec) = (before[1] - before[0], after[1] - after[0]);
+                let zoom_delta = if before_vec.length() > 0.0 {
+                    after_vec.length() / before_vec.length()
+                } else {
+                    1.0
+                };
+                let mut rotation_delta =
+                    after_vec.y.atan2(after_vec.x) - before_vec.y.atan2(before_vec.x);
+                if rotation_delta > std::f32::consts::PI {
+                    rotation_delta -= std::f32::consts::TAU;
+                } else if rotation_delta < -std::f32::consts::PI {
+                    rotation_delta += std::f32::consts::TAU;
+                }
+                Some(MultiTouchInfo {
+                    num_touches: touches.len(),
+                    center: center(after),
+                    zoom_delta,
+                    rotation_delta,
+                    translation_delta: center(after) - center(before),
+                })
+            }
+            (_, Some(after)) => Some(MultiTouchInfo {
+                // A new gesture starts:
+                num_touches: touches.len(),
+                center: center(after.1),
+                zoom_delta: 1.0,
+                rotation_delta: 0.0,
+                translation_delta: Vec2::zero(),
+            }),
+            (_, None) => None,
+        };
+
+        let mut long_press = false;
+        let num_touches = touches.len();
+        for touch in &mut touches {
+            touch.could_be_long_press &=
+                num_touches == 1 && touch.start_pos.distance(touch.pos) < MAX_CLICK_DIST;
+            if touch.could_be_long_press && time - touch.start_time >= LONG_PRESS_TIME {
+                touch.could_be_long_press = false;
+                long_press = true;
+            }
+        }
+
+        Self {
+            touches,
+            multi_touch,
+            long_press,
+        }
+    }
+
+    /// Is any finger on the screen?
+    pub fn any_touches(&self) -> bool {
+        !self.touches.is_empty()
+    }
+
+    /// How many fingers are on the screen?
+    pub fn num_touches(&self) -> usize {
+        self.touches.len()
+    }
+
+    /// The fingers on the screen, in the order they touched it,
+    /// with their position and (if known) pressure in the range `0..=1`.
+    pub fn touches(&self) -> impl Iterator<Item = (TouchId, Pos2, Option<f32>)> + '_ {
+        self.touches
+            .iter()
+            .map(|touch| (touch.id, touch.pos, touch.force))
+    }
+
+    /// Zoom, rotation and translation from moving two fingers,
+    /// or `None` unless there are at least two fingers on the screen.
+    pub fn multi_touch(&self) -> Option<MultiTouchInfo> {
+        self.multi_touch
+    }
+
+    /// Did a finger stay still on the screen long enough to count as a secondary click this frame?
+    pub fn long_pressed(&self) -> bool {
+        self.long_press
+    }
+
+    /// A finger could still turn into a long-press, so we need to check again soon.
+    pub(crate) fn wants_repaint(&self) -> bool {
+        self.touches.iter().any(|touch| touch.could_be_long_press)
+    }
+
+    pub fn ui(&self, ui: &mut crate::Ui) {
+        for touch in &self.touches {
+            ui.label(format!(
+                "{:?}: pos {:.1?}, force {:?}",
+                touch.id, touch.pos, touch.force
+            ));
+        }
+        ui.label(format!("multi_touch: {:.3?}", self.multi_touch));
+    }
+}
+
+/// The ids and positions of the first two fingers (if there are two).
+fn first_two(touches: &[ActiveTouch]) -> Option<([TouchId; 2], [Pos2; 2])> {
+    match touches {
+        [a, b, ..] => Some(([a.id, b.id], [a.pos, b.pos])),
+        _ => None,
+    }
+}
+
+fn center(positions: [Pos2; 2]) -> Pos2 {
+    positions[0] + 0.5 * (positions[1] - positions[0])
+}
+
+#[test]
+fn test_touch_gestures() {
+    use crate::{testing::Harness, CentralPanel, CtxRef};
+
+    let mut harness = Harness::new(vec2(400.0, 300.0));
+    let mut multi_touch = vec![];
+    let mut record = |ctx: &CtxRef| multi_touch.push(ctx.input().touch.multi_touch());
+
+    harness.touch(1, TouchPhase::Start, pos2(40.0, 50.0));
+    harness.run(&mut record);
+    harness.touch(2, TouchPhase::Start, pos2(60.0, 50.0));
+    harness.run(&mut record);
+    // Spread the fingers apart:
+    harness.touch(1, TouchPhase::Move, pos2(20.0, 50.0));
+    harness.touch(2, TouchPhase::Move, pos2(80.0, 50.0));
+    harness.run(&mut record);
+    // Turn them a quarter clockwise:
+    harness.touch(2, TouchPhase::Move, pos2(20.0, 110.0));
+    harness.run(&mut record);
+    harness.touch(1, TouchPhase::End, pos2(20.0, 50.0));
+    harness.touch(2, TouchPhase::Cancel, pos2(20.0, 110.0));
+    harness.run(&mut record);
+
+    assert_eq!(multi_touch[0], None);
+    assert_eq!(multi_touch[1].unwrap().zoom_delta, 1.0);
+    let spread = multi_touch[2].unwrap();
+    assert_eq!(spread.num_touches, 2);
+    assert_eq!(spread.center, pos2(50.0, 50.0));
+    assert_eq!(spread.zoom_delta, 3.0);
+    assert_eq!(spread.rotation_delta, 0.0);
+    assert_eq!(spread.translation_delta, Vec2::zero());
+    let turn = multi_touch[3].unwrap();
+    assert_eq!(turn.zoom_delta, 1.0);
+    assert!((turn.rotation_delta - std::f32::consts::TAU / 4.0).abs() < 1e-6);
+    assert_eq!(turn.translation_delta, vec2(-30.0, 30.0));
+    assert_eq!(multi_touch[4], None);
+    assert!(!harness.ctx().input().touch.any_touches());
+
+    // Long-press is a secondary click, and lifting the finger is not a primary click:
+    let mut clicks = vec![];
+    {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = ui.button("Hold me");
+                clicks.push((response.clicked, response.secondary_clicked));
+            });
+        };
+        harness.run(&mut app);
+        let pos = harness.expect_widget("Hold me").rect.center();
+        harness.move_mouse_to(pos);
+        harness.set_mouse_down(true);
+        harness.touch(3, TouchPhase::Start, pos);
+        harness.run(&mut app);
+        harness.advance_time(0.2);
+        harness.run(&mut app);
+        assert!(harness.ctx().input().wants_repaint());
+        harness.advance_time(0.4);
+        harness.run(&mut app);
+        harness.set_mouse_down(false);
+        harness.touch(3, TouchPhase::End, pos);
+        harness.run(&mut app);
+    }
+    assert_eq!(
+        clicks[1..],
+        [
+            (false, false),
+            (false, false),
+            (false, true),
+            (false, false)
+        ]
+    );
+}
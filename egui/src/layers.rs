@@ -1,6 +1,6 @@
 use ahash::AHashMap;
 
-use crate::{math::Rect, paint::PaintCmd, Id, *};
+use crate::{math::Rect, paint::PaintCmd, viewport::ViewportId, Id, *};
 
 /// Different layer categories
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -115,18 +115,20 @@ impl PaintList {
     }
 }
 
+/// The paint lists of each layer, for each viewport.
 #[derive(Clone, Default)]
-pub(crate) struct GraphicLayers([AHashMap<Id, PaintList>; Order::COUNT]);
+pub(crate) struct GraphicLayers([AHashMap<(ViewportId, Id), PaintList>; Order::COUNT]);
 
 impl GraphicLayers {
-    pub fn list(&mut self, layer_id: LayerId) -> &mut PaintList {
+    pub fn list(&mut self, viewport_id: ViewportId, layer_id: LayerId) -> &mut PaintList {
         self.0[layer_id.order as usize]
-            .entry(layer_id.id)
+            .entry((viewport_id, layer_id.id))
             .or_default()
     }
 
     pub fn drain(
         &mut self,
+        viewport_id: ViewportId,
         area_order: &[LayerId],
     ) -> impl ExactSizeIterator<Item = (Rect, PaintCmd)> {
         let mut all_commands: Vec<_> = Default::default();
@@ -142,15 +144,17 @@ impl GraphicLayers {
             // First do the layers part of area_order:
             for layer_id in area_order {
                 if layer_id.order == order {
-                    if let Some(commands) = order_map.get_mut(&layer_id.id) {
+                    if let Some(commands) = order_map.get_mut(&(viewport_id, layer_id.id)) {
                         all_commands.extend(commands.0.drain(..));
                     }
                 }
             }
 
             // Also draw areas that are missing in `area_order`:
-            for commands in order_map.values_mut() {
-                all_commands.extend(commands.0.drain(..));
+            for ((list_viewport_id, _), commands) in order_map.iter_mut() {
+                if *list_viewport_id == viewport_id {
+                    all_commands.extend(commands.0.drain(..));
+                }
             }
         }
 
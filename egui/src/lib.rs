@@ -4,7 +4,7 @@
 //! which uses [`eframe`](https://docs.rs/eframe).
 //!
 //! To create a GUI using Egui you first need a [`CtxRef`] (by convention referred to by `ctx`).
-//! Use one of [`SidePanel`], [`TopPanel`], [`CentralPanel`], [`Window`] or [`Area`] to
+//! Use one of [`SidePanel`], [`TopPanel`], [`BottomPanel`], [`CentralPanel`], [`Window`] or [`Area`] to
 //! get access to an [`Ui`] where you can put widgets. For example:
 //!
 //! ```
@@ -78,6 +78,7 @@
 )]
 #![allow(clippy::manual_range_contains)]
 
+pub mod accessibility;
 pub mod align;
 mod animation_manager;
 pub mod containers;
@@ -93,9 +94,12 @@ pub mod menu;
 pub mod paint;
 mod painter;
 pub mod style;
+pub mod testing;
+pub mod toasts;
 mod types;
 mod ui;
 pub mod util;
+pub mod viewport;
 pub mod widgets;
 
 pub use {
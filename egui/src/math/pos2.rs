@@ -98,6 +98,28 @@ impl PartialEq for Pos2 {
 }
 impl Eq for Pos2 {}
 
+impl std::ops::Index<usize> for Pos2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Pos2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl AddAssign<Vec2> for Pos2 {
     fn add_assign(&mut self, rhs: Vec2) {
         *self = Pos2 {
@@ -1,11 +1,16 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
-    area, collapsing_header, menu,
+    area, collapsing_header, grid, menu,
     paint::color::{Color32, Hsva},
-    resize, scroll_area,
+    panel, resize, scroll_area, table, tree_view,
     util::Cache,
-    widgets::text_edit,
+    viewport::ViewportId,
+    widgets::{plot, text_edit},
     window, Id, LayerId, Pos2, Rect, Style,
 };
 
@@ -28,15 +33,27 @@ pub struct Memory {
 
     // states of various types of widgets
     pub(crate) collapsing_headers: HashMap<Id, collapsing_header::State>,
+    pub(crate) grid: HashMap<Id, grid::State>,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) menu_bar: HashMap<Id, menu::BarState>,
+    pub(crate) panels: HashMap<Id, panel::State>,
+    pub(crate) plot: HashMap<Id, plot::State>,
     pub(crate) resize: HashMap<Id, resize::State>,
     pub(crate) scroll_areas: HashMap<Id, scroll_area::State>,
+    pub(crate) table: HashMap<Id, table::State>,
     pub(crate) text_edit: HashMap<Id, text_edit::State>,
+    pub(crate) tree_view: HashMap<Id, tree_view::State>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) toasts: crate::toasts::Toasts,
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) window_interaction: Option<window::WindowInteraction>,
 
+    /// What is being dragged (if anything). See [`crate::Response::dnd_set_payload`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) dnd_payload: Option<DragAndDropPayload>,
+
     /// For temporary edit of e.g. a slider value.
     /// Couples with [`Interaction::kb_focus_id`].
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -53,12 +70,28 @@ pub struct Memory {
     #[cfg_attr(feature = "serde", serde(skip))]
     popup: Option<Id>,
 
+    /// Where the open context menu (if any) was opened.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) context_menu_pos: Option<Pos2>,
+
     #[cfg_attr(feature = "serde", serde(skip))]
     everything_is_visible: bool,
 }
 
 // ----------------------------------------------------------------------------
 
+/// The payload of an ongoing drag-and-drop, of any type.
+#[derive(Clone)]
+pub(crate) struct DragAndDropPayload(pub(crate) Arc<dyn Any + Send + Sync>);
+
+impl std::fmt::Debug for DragAndDropPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DragAndDropPayload")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
@@ -69,6 +102,12 @@ pub(crate) struct Options {
     pub(crate) tessellation_options: crate::paint::TessellationOptions,
     /// Font sizes etc.
     pub(crate) font_definitions: crate::paint::FontDefinitions,
+    /// Keep a record of all widgets laid out each frame, e.g. for testing.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) record_widgets: bool,
+    /// Build an accessibility tree each frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) accessibility: bool,
 }
 
 // ----------------------------------------------------------------------------
@@ -101,6 +140,11 @@ pub(crate) struct Interaction {
     /// The last widget interested in kb focus.
     pub kb_focus_last_interested: Option<Id>,
 
+    /// Has any widget been interested in kb focus this frame?
+    /// If not, `kb_focus_last_interested` is from the previous frame.
+    /// Cleared to false at start of each frame.
+    kb_focus_interest: bool,
+
     /// Set at the beginning of the frame, set to `false` when "used".
     pressed_tab: bool,
     /// Set at the beginning of the frame, set to `false` when "used".
@@ -133,6 +177,7 @@ impl Interaction {
         new_input: &crate::input::RawInput,
     ) {
         self.kb_focus_id_previous_frame = self.kb_focus_id;
+        self.kb_focus_interest = false;
         self.click_interest = false;
         self.drag_interest = false;
 
@@ -162,6 +207,15 @@ impl Interaction {
                 }
             }
         }
+
+        if self.kb_focus_id.is_none() {
+            // Nothing has focus, so start from one of the ends:
+            if self.pressed_tab {
+                self.kb_focus_give_to_next = true;
+            } else if self.pressed_shift_tab {
+                self.kb_focus_id = self.kb_focus_last_interested;
+            }
+        }
     }
 }
 
@@ -175,6 +229,7 @@ impl Memory {
 
         if !prev_input.mouse.down {
             self.window_interaction = None;
+            self.dnd_payload = None;
         }
     }
 
@@ -193,8 +248,15 @@ impl Memory {
         }
     }
 
-    pub fn layer_id_at(&self, pos: Pos2, resize_interact_radius_side: f32) -> Option<LayerId> {
-        self.areas.layer_id_at(pos, resize_interact_radius_side)
+    /// The top-most layer in the given viewport at the given position.
+    pub fn layer_id_at(
+        &self,
+        pos: Pos2,
+        viewport_id: ViewportId,
+        resize_interact_radius_side: f32,
+    ) -> Option<LayerId> {
+        self.areas
+            .layer_id_at(pos, viewport_id, resize_interact_radius_side)
     }
 
     /// True if the given widget had keyboard focus last frame, but not this one.
@@ -218,7 +280,16 @@ impl Memory {
 
     /// Register this widget as being interested in getting keyboard focus.
     /// This will allow the user to select it with tab and shift-tab.
+    ///
+    /// This is called automatically for all widgets with a [`Sense::focusable`](crate::Sense::focusable),
+    /// in the order they are laid out.
     pub fn interested_in_kb_focus(&mut self, id: Id) {
+        if self.interaction.kb_focus_interest
+            && self.interaction.kb_focus_last_interested == Some(id)
+        {
+            return; // Same widget interacted with twice in a row.
+        }
+
         if self.interaction.kb_focus_give_to_next {
             self.interaction.kb_focus_id = Some(id);
             self.interaction.kb_focus_give_to_next = false;
@@ -234,6 +305,7 @@ impl Memory {
         }
 
         self.interaction.kb_focus_last_interested = Some(id);
+        self.interaction.kb_focus_interest = true;
     }
 
     /// Stop editing of active `TextEdit` (if any).
@@ -249,6 +321,13 @@ impl Memory {
         self.interaction.drag_id == Some(id)
     }
 
+    /// The payload of the ongoing drag-and-drop, if there is one and it is of type `T`.
+    /// See [`crate::Response::dnd_set_payload`].
+    pub fn dnd_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let payload = self.dnd_payload.as_ref()?.0.clone();
+        payload.downcast().ok()
+    }
+
     /// Forget window positions, sizes etc.
     /// Can be used to auto-layout windows.
     pub fn reset_areas(&mut self) {
@@ -319,6 +398,17 @@ pub struct Areas {
     /// So if you close three windows and then reopen them all in one frame,
     /// they will all be sent to the top, but keep their previous internal order.
     wants_to_be_on_top: HashSet<LayerId>,
+
+    /// Which viewport each area was last shown in. Missing means the root viewport.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    viewports: HashMap<LayerId, ViewportId>,
+
+    /// The modal area shown last frame, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    modal_last_frame: Option<LayerId>,
+    /// The modal area shown this frame so far, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    modal_current_frame: Option<LayerId>,
 }
 
 impl Areas {
@@ -334,17 +424,38 @@ impl Areas {
         &self.order
     }
 
-    pub(crate) fn set_state(&mut self, layer_id: LayerId, state: area::State) {
+    pub(crate) fn set_state(
+        &mut self,
+        layer_id: LayerId,
+        viewport_id: ViewportId,
+        state: area::State,
+    ) {
         self.visible_current_frame.insert(layer_id);
         self.areas.insert(layer_id.id, state);
+        if viewport_id == ViewportId::root() {
+            self.viewports.remove(&layer_id);
+        } else {
+            self.viewports.insert(layer_id, viewport_id);
+        }
         if self.order.iter().find(|x| **x == layer_id).is_none() {
             self.order.push(layer_id);
         }
     }
 
-    pub fn layer_id_at(&self, pos: Pos2, resize_interact_radius_side: f32) -> Option<LayerId> {
+    /// The top-most layer in the given viewport at the given position.
+    ///
+    /// Everything outside a modal area counts as being part of it.
+    pub fn layer_id_at(
+        &self,
+        pos: Pos2,
+        viewport_id: ViewportId,
+        resize_interact_radius_side: f32,
+    ) -> Option<LayerId> {
         for layer in self.order.iter().rev() {
-            if self.is_visible(layer) {
+            if self.is_blocked_by_modal(*layer) {
+                break;
+            }
+            if self.is_visible(layer) && self.viewport_of(*layer) == viewport_id {
                 if let Some(state) = self.areas.get(&layer.id) {
                     if state.interactable {
                         let rect = Rect::from_min_size(state.pos, state.size);
@@ -357,7 +468,43 @@ impl Areas {
                 }
             }
         }
-        None
+        self.modal_layer()
+    }
+
+    /// The viewport the area was last shown in.
+    pub fn viewport_of(&self, layer_id: LayerId) -> ViewportId {
+        self.viewports
+            .get(&layer_id)
+            .copied()
+            .unwrap_or_else(ViewportId::root)
+    }
+
+    pub(crate) fn set_modal(&mut self, layer_id: LayerId) {
+        self.modal_current_frame = Some(layer_id);
+    }
+
+    /// The modal area that is blocking input to everything beneath it, if any.
+    pub fn modal_layer(&self) -> Option<LayerId> {
+        self.modal_current_frame.or(self.modal_last_frame)
+    }
+
+    /// Is this layer beneath a modal area, so that it shouldn't react to any input?
+    pub fn is_blocked_by_modal(&self, layer_id: LayerId) -> bool {
+        let modal = match self.modal_layer() {
+            Some(modal) => modal,
+            None => return false,
+        };
+        if layer_id == modal {
+            false
+        } else if layer_id.order != modal.order {
+            layer_id.order < modal.order
+        } else {
+            let index = |layer_id: LayerId| self.order.iter().position(|x| *x == layer_id);
+            match (index(layer_id), index(modal)) {
+                (Some(layer_index), Some(modal_index)) => layer_index < modal_index,
+                _ => true,
+            }
+        }
     }
 
     pub fn visible_last_frame(&self, layer_id: &LayerId) -> bool {
@@ -399,10 +546,13 @@ impl Areas {
             visible_current_frame,
             order,
             wants_to_be_on_top,
+            modal_last_frame,
+            modal_current_frame,
             ..
         } = self;
 
         *visible_last_frame = std::mem::take(visible_current_frame);
+        *modal_last_frame = modal_current_frame.take();
         order.sort_by_key(|layer| (layer.order, wants_to_be_on_top.contains(layer)));
         wants_to_be_on_top.clear();
     }
@@ -1,62 +1,132 @@
-//! Menu bar functionality (very basic so far).
+//! Menu bars, menus, submenus and context menus.
 //!
 //! Usage:
 //! ```
-//! fn show_menu(ui: &mut egui::Ui) {
-//!     use egui::{menu, Button};
+//! fn show_menu(ui: &mut egui::Ui, autosave: &mut bool) {
+//!     use egui::menu::{self, MenuItem};
 //!
 //!     menu::bar(ui, |ui| {
 //!         menu::menu(ui, "File", |ui| {
-//!             if ui.button("Open").clicked {
+//!             if ui.add(MenuItem::new("Open").shortcut("Ctrl+O")).clicked {
 //!                 // ...
 //!             }
+//!             menu::submenu(ui, "Open recent", |ui| {
+//!                 if ui.add(MenuItem::new("notes.txt")).clicked {
+//!                     // ...
+//!                 }
+//!             });
+//!             ui.separator();
+//!             menu::checkbox(ui, autosave, "Autosave");
+//!             let _ = ui.add(MenuItem::new("Print").enabled(false));
 //!         });
 //!     });
 //! }
 //! ```
+//!
+//! Once a menu is open, hovering another menu in the bar opens that one instead,
+//! and hovering a submenu opens it.
+//!
+//! Tapping alt opens the first menu of a [`bar`], or closes the open one.
+//! The up and down arrow keys move between the items of the open menu,
+//! right opens a submenu (or moves to the next menu of the bar), left closes it again,
+//! enter clicks the item and escape closes the menu.
 
 use crate::{paint::Stroke, widgets::*, *};
 
-/// What is saved between frames.
-#[derive(Clone, Copy, Debug, Default)]
+/// What is saved between frames, for each menu bar and context menu.
+#[derive(Clone, Debug, Default)]
 pub(crate) struct BarState {
+    /// The open top level menu (if any).
     open_menu: Option<Id>,
+
+    /// The open submenus, outermost first.
+    /// `open_submenus[i]` is open inside of the menu at depth `i`.
+    open_submenus: Vec<Id>,
+
+    /// The top level menus of the bar, left to right.
+    menus: Vec<Id>,
+
+    /// The items of each open menu (outermost first) that the arrow keys move between,
+    /// as laid out this frame, together with the submenu they open (if any).
+    items: Vec<Vec<(Id, Option<Id>)>>,
+
+    /// Give keyboard focus to the first item of the menu at this depth once it is shown.
+    focus_first_item: Option<usize>,
+
+    /// An item was clicked this frame, so the menus should close.
+    item_clicked: bool,
+
+    /// There was a click this frame that should not close the menus (e.g. on a submenu).
+    keep_open: bool,
+
+    /// Was alt down last frame?
+    alt_down: bool,
+
+    /// Alt has been held down without pressing anything else.
+    alt_alone: bool,
 }
 
 impl BarState {
-    fn load(ctx: &Context, bar_id: &Id) -> Self {
-        ctx.memory()
-            .menu_bar
-            .get(bar_id)
-            .cloned()
-            .unwrap_or_default()
+    fn open(&mut self, menu_id: Id) {
+        self.open_menu = Some(menu_id);
+        self.open_submenus.clear();
+        self.focus_first_item = None;
     }
 
-    fn save(self, ctx: &Context, bar_id: Id) {
-        ctx.memory().menu_bar.insert(bar_id, self);
+    fn close(&mut self) {
+        self.open_menu = None;
+        self.open_submenus.clear();
+        self.focus_first_item = None;
     }
 }
 
+/// Change the [`BarState`] of the menu bar (or context menu) with the given id.
+///
+/// Don't lock [`Memory`] inside of `f`.
+fn with_state<R>(ctx: &Context, root_id: Id, f: impl FnOnce(&mut BarState) -> R) -> R {
+    f(ctx.memory().menu_bar.entry(root_id).or_default())
+}
+
+/// Which menu a [`Ui`] is in.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MenuScope {
+    /// The menu bar or context menu that the menu belongs to.
+    root_id: Id,
+    /// 0 for a top level menu or a context menu, 1 for their submenus, and so on.
+    depth: usize,
+}
+
+fn set_menu_style(ui: &mut Ui) {
+    let mut style = ui.style().clone();
+    style.spacing.button_padding = vec2(2.0, 0.0);
+    // style.visuals.widgets.active.bg_fill = Color32::TRANSPARENT;
+    style.visuals.widgets.active.bg_stroke = Stroke::none();
+    // style.visuals.widgets.hovered.bg_fill = Color32::TRANSPARENT;
+    style.visuals.widgets.hovered.bg_stroke = Stroke::none();
+    style.visuals.widgets.inactive.bg_fill = Color32::TRANSPARENT;
+    style.visuals.widgets.inactive.bg_stroke = Stroke::none();
+    style.visuals.widgets.disabled.bg_fill = Color32::TRANSPARENT;
+    style.visuals.widgets.disabled.bg_stroke = Stroke::none();
+    ui.set_style(style);
+}
+
 /// The menu bar goes well in `TopPanel`,
 /// but can also be placed in a `Window`.
 /// In the latter case you may want to wrap it in `Frame`.
 pub fn bar<R>(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
     ui.horizontal(|ui| {
-        let mut style = ui.style().clone();
-        style.spacing.button_padding = vec2(2.0, 0.0);
-        // style.visuals.widgets.active.bg_fill = Color32::TRANSPARENT;
-        style.visuals.widgets.active.bg_stroke = Stroke::none();
-        // style.visuals.widgets.hovered.bg_fill = Color32::TRANSPARENT;
-        style.visuals.widgets.hovered.bg_stroke = Stroke::none();
-        style.visuals.widgets.inactive.bg_fill = Color32::TRANSPARENT;
-        style.visuals.widgets.inactive.bg_stroke = Stroke::none();
-        ui.set_style(style);
+        set_menu_style(ui);
 
         // Take full width and fixed height:
         let height = ui.style().spacing.interact_size.y;
         ui.set_min_size(vec2(ui.available_width(), height));
 
-        add_contents(ui)
+        let bar_id = ui.id();
+        with_state(ui.ctx(), bar_id, |state| state.menus.clear());
+        let ret = add_contents(ui);
+        alt_key(ui.ctx(), bar_id);
+        keyboard_navigation(ui.ctx(), bar_id);
+        ret
     })
 }
 
@@ -74,53 +144,758 @@ fn menu_impl<'c>(
     let bar_id = ui.id();
     let menu_id = bar_id.with(&title);
 
-    let mut bar_state = BarState::load(ui.ctx(), &bar_id);
+    let was_open = with_state(ui.ctx(), bar_id, |state| {
+        if !state.menus.contains(&menu_id) {
+            state.menus.push(menu_id);
+        }
+        state.open_menu == Some(menu_id)
+    });
 
     let mut button = Button::new(title);
 
-    if bar_state.open_menu == Some(menu_id) {
+    if was_open {
         button = button.fill(Some(ui.style().visuals.widgets.active.fg_fill));
     }
 
     let button_response = ui.add(button);
-    if button_response.clicked {
-        // Toggle
-        if bar_state.open_menu == Some(menu_id) {
-            bar_state.open_menu = None;
-        } else {
-            bar_state.open_menu = Some(menu_id);
+    let is_open = with_state(ui.ctx(), bar_id, |state| {
+        if button_response.clicked {
+            // Toggle
+            if state.open_menu == Some(menu_id) {
+                state.close();
+            } else {
+                state.open(menu_id);
+            }
+        } else if button_response.hovered
+            && state.open_menu.is_some()
+            && state.open_menu != Some(menu_id)
+        {
+            state.open(menu_id);
         }
-    } else if button_response.hovered && bar_state.open_menu.is_some() {
-        bar_state.open_menu = Some(menu_id);
+        state.open_menu == Some(menu_id)
+    });
+
+    if is_open || ui.ctx().memory().everything_is_visible() {
+        let scope = MenuScope {
+            root_id: bar_id,
+            depth: 0,
+        };
+        show_menu(
+            ui.ctx(),
+            scope,
+            menu_id,
+            button_response.rect.left_bottom(),
+            add_contents,
+        );
+
+        if is_open && should_close(ui.ctx(), bar_id) && !button_response.clicked {
+            with_state(ui.ctx(), bar_id, BarState::close);
+        }
+    }
+}
+
+/// A menu inside of another menu (or context menu), that opens when hovered.
+///
+/// Outside of a menu this is the same as [`menu()`].
+pub fn submenu(ui: &mut Ui, title: impl Into<String>, add_contents: impl FnOnce(&mut Ui)) {
+    submenu_impl(ui, title.into(), Box::new(add_contents))
+}
+
+fn submenu_impl<'c>(ui: &mut Ui, title: String, add_contents: Box<dyn FnOnce(&mut Ui) + 'c>) {
+    let scope = match ui.menu_scope() {
+        Some(scope) => scope,
+        None => return menu_impl(ui, title, add_contents),
+    };
+    let submenu_id = ui.make_persistent_id(&title);
+
+    let was_open = with_state(ui.ctx(), scope.root_id, |state| {
+        state.open_submenus.get(scope.depth) == Some(&submenu_id)
+    });
+    let response = item_ui(
+        ui,
+        title.clone(),
+        None,
+        Check::None,
+        true,
+        Sense::click(),
+        was_open,
+    );
+    response.widget_info(|| WidgetInfo {
+        expanded: Some(was_open),
+        ..WidgetInfo::labeled(WidgetType::MenuItem, &title)
+    });
+    register_item(ui, response.id, Some(submenu_id));
+
+    let is_open = with_state(ui.ctx(), scope.root_id, |state| {
+        if response.clicked {
+            state.keep_open = true;
+            if !response.hovered {
+                // Clicked with the keyboard:
+                state.focus_first_item = Some(scope.depth + 1);
+            }
+        }
+        if response.hovered || response.clicked {
+            state.open_submenus.truncate(scope.depth);
+            state.open_submenus.push(submenu_id);
+        }
+        state.open_submenus.get(scope.depth) == Some(&submenu_id)
+    });
+
+    if is_open {
+        let margin = Frame::menu(ui.style()).margin;
+        let pos = response.rect.right_top() + vec2(margin.x, -margin.y);
+        let scope = MenuScope {
+            depth: scope.depth + 1,
+            ..scope
+        };
+        show_menu(ui.ctx(), scope, submenu_id, pos, add_contents);
     }
+}
 
-    if bar_state.open_menu == Some(menu_id) || ui.ctx().memory().everything_is_visible() {
-        let area = Area::new(menu_id)
-            .order(Order::Foreground)
-            .fixed_pos(button_response.rect.left_bottom());
-        let frame = Frame::menu(ui.style());
+/// Show the contents of an open menu in an [`Area`] of its own.
+fn show_menu<'c>(
+    ctx: &CtxRef,
+    scope: MenuScope,
+    menu_id: Id,
+    pos: Pos2,
+    add_contents: Box<dyn FnOnce(&mut Ui) + 'c>,
+) -> Response {
+    with_state(ctx, scope.root_id, |state| {
+        state.items.truncate(scope.depth);
+        state.items.resize_with(scope.depth + 1, Vec::new);
+    });
+
+    let frame = Frame::menu(&ctx.style());
+    // Make all items as wide as the widest one last frame, so the shortcuts line up:
+    let min_width = ctx
+        .memory()
+        .areas
+        .get(menu_id)
+        .map_or(0.0, |area| area.size.x - 2.0 * frame.margin.x);
 
-        area.show(ui.ctx(), |ui| {
+    Area::new(menu_id)
+        .order(Order::Foreground)
+        .fixed_pos(pos)
+        .show(ctx, |ui| {
             frame.show(ui, |ui| {
-                let mut style = ui.style().clone();
-                style.spacing.button_padding = vec2(2.0, 0.0);
-                // style.visuals.widgets.active.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.active.bg_stroke = Stroke::none();
-                // style.visuals.widgets.hovered.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.hovered.bg_stroke = Stroke::none();
-                style.visuals.widgets.inactive.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.inactive.bg_stroke = Stroke::none();
-                ui.set_style(style);
-                ui.with_layout(Layout::top_down_justified(Align::left()), add_contents);
+                set_menu_style(ui);
+                ui.set_menu_scope(scope);
+                ui.with_layout(Layout::top_down_justified(Align::left()), |ui| {
+                    ui.set_min_width(min_width);
+                    add_contents(ui);
+                });
             })
+        })
+}
+
+/// Let the arrow keys move to this item of the menu `ui` is in.
+fn register_item(ui: &Ui, id: Id, submenu: Option<Id>) {
+    let scope = match ui.menu_scope() {
+        Some(scope) => scope,
+        None => return,
+    };
+    let focus = with_state(ui.ctx(), scope.root_id, |state| {
+        if let Some(items) = state.items.get_mut(scope.depth) {
+            items.push((id, submenu));
+        }
+        if state.focus_first_item == Some(scope.depth) {
+            state.focus_first_item = None;
+            true
+        } else {
+            false
+        }
+    });
+    if focus {
+        ui.memory().request_kb_focus(id);
+    }
+}
+
+/// Handle clicks and the escape key for the open menus of the bar (or context menu).
+///
+/// Returns `true` if they should all close.
+fn should_close(ctx: &CtxRef, root_id: Id) -> bool {
+    let escape = ctx.input().key_pressed(Key::Escape);
+    let click = ctx.input().mouse.click;
+    let (close, focus) = with_state(ctx, root_id, |state| {
+        if escape && !state.open_submenus.is_empty() {
+            // Only close the innermost submenu, and go back to where it was opened:
+            let submenu = state.open_submenus.pop();
+            let depth = state.open_submenus.len();
+            let focus = state.items.get(depth).and_then(|items| {
+                items
+                    .iter()
+                    .find(|(_, item_submenu)| *item_submenu == submenu)
+                    .map(|(id, _)| *id)
+            });
+            (false, focus)
+        } else {
+            let close = escape || state.item_clicked || (click && !state.keep_open);
+            state.item_clicked = false;
+            state.keep_open = false;
+            (close, None)
+        }
+    });
+    if let Some(focus) = focus {
+        ctx.memory().request_kb_focus(focus);
+    }
+    close
+}
+
+/// Tapping alt (pressing and releasing it without pressing anything else)
+/// opens the first menu of the bar, or closes the open one.
+fn alt_key(ctx: &CtxRef, bar_id: Id) {
+    let input = ctx.input();
+    let alt = input.modifiers.alt;
+    let pressed_something = input.mouse.down
+        || input.events.iter().any(|event| {
+            matches!(
+                event,
+                Event::Key { pressed: true, .. } | Event::Text(_) | Event::Copy | Event::Cut
+            )
         });
 
-        // TODO: this prevents sub-menus in menus. We should fix that.
-        if ui.input().key_pressed(Key::Escape) || ui.input().mouse.click && !button_response.clicked
-        {
-            bar_state.open_menu = None;
+    let tapped = with_state(ctx, bar_id, |state| {
+        let tapped = state.alt_down && !alt && state.alt_alone;
+        if alt && !state.alt_down {
+            state.alt_alone = true;
+        }
+        if pressed_something {
+            state.alt_alone = false;
+        }
+        state.alt_down = alt;
+
+        if tapped {
+            if state.open_menu.is_some() {
+                state.close();
+            } else if let Some(&first) = state.menus.first() {
+                state.open(first);
+                state.focus_first_item = Some(0);
+            }
+        }
+        tapped
+    });
+    if tapped {
+        ctx.request_repaint();
+    }
+}
+
+/// Move keyboard focus between the items of the open menus with the arrow keys.
+fn keyboard_navigation(ctx: &CtxRef, root_id: Id) {
+    let keys: Vec<Key> = ctx
+        .input()
+        .unconsumed_events()
+        .filter_map(|event| match event {
+            Event::Key {
+                key, pressed: true, ..
+            } => Some(*key),
+            _ => None,
+        })
+        .collect();
+    if keys.is_empty() {
+        return;
+    }
+
+    let mut focused = ctx.memory().interaction.kb_focus_id;
+    let mut moved_focus = false;
+    let mut changed = false;
+    with_state(ctx, root_id, |state| {
+        if state.open_menu.is_none() {
+            return;
+        }
+        let focus_is_in_menu = focused.map_or(true, |focused| {
+            state
+                .items
+                .iter()
+                .flatten()
+                .any(|(item, _)| *item == focused)
+        });
+        if !focus_is_in_menu {
+            return; // e.g. a `TextEdit` in the menu, or elsewhere.
+        }
+
+        for key in keys {
+            let depth = state.open_submenus.len();
+            let items = match state.items.get(depth) {
+                Some(items) => items.clone(),
+                None => continue, // Not shown yet.
+            };
+            let index = items.iter().position(|(id, _)| Some(*id) == focused);
+            let num_items = items.len();
+
+            let next_index = match key {
+                Key::ArrowDown if num_items > 0 => {
+                    Some(index.map_or(0, |index| (index + 1) % num_items))
+                }
+                Key::ArrowUp if num_items > 0 => {
+                    Some(index.map_or(num_items - 1, |index| (index + num_items - 1) % num_items))
+                }
+                Key::ArrowRight => {
+                    if let Some((_, Some(submenu))) = index.map(|index| items[index]) {
+                        state.open_submenus.push(submenu);
+                        state.focus_first_item = Some(depth + 1);
+                    } else if let Some(next) = neighbor_menu(state, 1) {
+                        state.open(next);
+                        state.focus_first_item = Some(0);
+                    }
+                    changed = true;
+                    None
+                }
+                Key::ArrowLeft => {
+                    if let Some(submenu) = state.open_submenus.pop() {
+                        let parent = state.items.get(depth - 1).and_then(|items| {
+                            items.iter().find(|(_, item)| *item == Some(submenu))
+                        });
+                        if let Some((id, _)) = parent {
+                            focused = Some(*id);
+                            moved_focus = true;
+                        }
+                    } else if let Some(previous) = neighbor_menu(state, -1) {
+                        state.open(previous);
+                        state.focus_first_item = Some(0);
+                    }
+                    changed = true;
+                    None
+                }
+                _ => None,
+            };
+
+            if let Some(next_index) = next_index {
+                focused = Some(items[next_index].0);
+                moved_focus = true;
+            }
         }
+    });
+
+    if moved_focus {
+        if let Some(focused) = focused {
+            ctx.memory().request_kb_focus(focused);
+        }
+    }
+    if moved_focus || changed {
+        ctx.request_repaint();
     }
+}
 
-    bar_state.save(ui.ctx(), bar_id);
+/// The top level menu `step` menus to the right of the open one, wrapping around.
+fn neighbor_menu(state: &BarState, step: isize) -> Option<Id> {
+    let num_menus = state.menus.len() as isize;
+    let index = state
+        .menus
+        .iter()
+        .position(|menu| Some(*menu) == state.open_menu)? as isize;
+    let next = (index + step).rem_euclid(num_menus) as usize;
+    Some(state.menus[next]).filter(|next| Some(*next) != state.open_menu)
+}
+
+/// Use [`Response::context_menu`] instead.
+pub(crate) fn context_menu(response: &Response, add_contents: impl FnOnce(&mut Ui)) {
+    let ctx = &response.ctx;
+    let popup_id = response.id.with("__context_menu");
+
+    let response = if response.sense.click {
+        response.clone()
+    } else {
+        // e.g. a label
+        response.interact(Sense::click().not_focusable())
+    };
+
+    if response.secondary_clicked {
+        let mut memory = ctx.memory();
+        memory.open_popup(popup_id);
+        memory.context_menu_pos = ctx.input().mouse.pos;
+        drop(memory);
+        with_state(ctx, popup_id, |state| state.open(popup_id));
+    }
+
+    if !ctx.memory().is_popup_open(popup_id) {
+        return;
+    }
+
+    let pos = ctx
+        .memory()
+        .context_menu_pos
+        .unwrap_or_else(|| response.rect.left_bottom());
+    let scope = MenuScope {
+        root_id: popup_id,
+        depth: 0,
+    };
+    let area_response = show_menu(ctx, scope, popup_id, pos, Box::new(add_contents));
+    keyboard_navigation(ctx, popup_id);
+
+    let clicked_elsewhere = ctx.input().mouse.button_clicked(MouseButton::Secondary)
+        && !response.secondary_clicked
+        && !area_response.hovered;
+    if should_close(ctx, popup_id) || clicked_elsewhere {
+        ctx.memory().close_popup();
+        with_state(ctx, popup_id, BarState::close);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Check {
+    None,
+    Checkbox(bool),
+    Radio(bool),
+}
+
+/// A clickable item in a menu, with an optional check mark and keyboard shortcut.
+///
+/// The menu closes when the item is clicked.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// # let mut show_grid = false;
+/// use egui::menu::MenuItem;
+///
+/// if ui.add(MenuItem::new("Save").shortcut("Ctrl+S")).clicked {
+///     // …
+/// }
+/// if ui.add(MenuItem::new("Show grid").checked(show_grid)).clicked {
+///     show_grid = !show_grid;
+/// }
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+#[derive(Clone, Debug)]
+pub struct MenuItem {
+    text: String,
+    shortcut: Option<String>,
+    check: Check,
+    enabled: bool,
+}
+
+impl MenuItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            shortcut: None,
+            check: Check::None,
+            enabled: true,
+        }
+    }
+
+    /// Show this text on the right side of the item, e.g. `"Ctrl+S"`.
+    ///
+    /// This is just a reminder for the user: checking for the key press is up to you.
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Show a check mark in front of the text if `checked`.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.check = Check::Checkbox(checked);
+        self
+    }
+
+    /// Show a dot in front of the text if `selected`, for one out of several alternatives.
+    pub fn radio(mut self, selected: bool) -> Self {
+        self.check = Check::Radio(selected);
+        self
+    }
+
+    /// If you set this to `false`, the item will be grayed out and un-clickable.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+impl Widget for MenuItem {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let MenuItem {
+            text,
+            shortcut,
+            check,
+            enabled,
+        } = self;
+
+        let sense = if enabled {
+            Sense::click()
+        } else {
+            Sense::hover()
+        };
+        let response = item_ui(ui, text.clone(), shortcut, check, false, sense, false);
+        response.widget_info(|| match check {
+            Check::None => WidgetInfo::labeled(WidgetType::MenuItem, &text),
+            Check::Checkbox(selected) | Check::Radio(selected) => {
+                WidgetInfo::selected(WidgetType::MenuItem, selected, &text)
+            }
+        });
+
+        if let Some(scope) = ui.menu_scope() {
+            if enabled {
+                register_item(ui, response.id, None);
+            }
+            let click = ui.input().mouse.click;
+            with_state(ui.ctx(), scope.root_id, |state| {
+                if response.hovered {
+                    state.open_submenus.truncate(scope.depth);
+                }
+                if response.clicked {
+                    state.item_clicked = true;
+                } else if response.hovered && click {
+                    state.keep_open = true; // Clicked a disabled item
+                }
+            });
+        }
+
+        response
+    }
+}
+
+/// A [`MenuItem`] with a check mark that toggles `checked` when clicked.
+pub fn checkbox(ui: &mut Ui, checked: &mut bool, text: impl Into<String>) -> Response {
+    let response = ui.add(MenuItem::new(text).checked(*checked));
+    if response.clicked {
+        *checked = !*checked;
+    }
+    response
+}
+
+/// A [`MenuItem`] that sets `current_value` to `selected_value` when clicked,
+/// with a dot in front of it if they are equal.
+pub fn radio_value<Value: PartialEq>(
+    ui: &mut Ui,
+    current_value: &mut Value,
+    selected_value: Value,
+    text: impl Into<String>,
+) -> Response {
+    let response = ui.add(MenuItem::new(text).radio(*current_value == selected_value));
+    if response.clicked {
+        *current_value = selected_value;
+    }
+    response
+}
+
+/// Lay out and paint a menu item: a check mark or dot (if any), the text,
+/// and then either the shortcut (if any) or an arrow for a submenu on the right.
+fn item_ui(
+    ui: &mut Ui,
+    text: String,
+    shortcut: Option<String>,
+    check: Check,
+    submenu: bool,
+    sense: Sense,
+    open: bool,
+) -> Response {
+    let text_style = TextStyle::Button;
+    let spacing = &ui.style().spacing;
+    let button_padding = spacing.button_padding;
+    let icon_width = spacing.icon_width;
+    let icon_spacing = spacing.icon_spacing;
+    let min_height = spacing.interact_size.y;
+
+    let font = &ui.fonts()[text_style];
+    let galley = font.layout_single_line(text);
+    let shortcut_galley = shortcut.map(|shortcut| font.layout_single_line(shortcut));
+
+    let right_width = if submenu {
+        icon_width
+    } else {
+        shortcut_galley
+            .as_ref()
+            .map_or(0.0, |galley| 2.0 * icon_width + galley.size.x)
+    };
+    let mut desired_size = 2.0 * button_padding
+        + vec2(
+            icon_width + icon_spacing + galley.size.x + right_width,
+            galley.size.y,
+        );
+    desired_size.y = desired_size.y.at_least(min_height);
+
+    let response = ui.allocate_response(desired_size, sense);
+    if !ui.clip_rect().intersects(response.rect) {
+        return response;
+    }
+
+    let rect = response.rect;
+    let visuals = if open {
+        ui.style().visuals.widgets.hovered
+    } else {
+        *ui.style().interact(&response)
+    };
+    let painter = ui.painter();
+    painter.rect(
+        rect,
+        visuals.corner_radius,
+        visuals.bg_fill,
+        visuals.bg_stroke,
+    );
+
+    let inner = rect.shrink2(button_padding);
+    let (small_icon_rect, _) = ui.style().spacing.icon_rectangles(inner);
+    match check {
+        Check::Checkbox(true) => {
+            painter.add(PaintCmd::line(
+                vec![
+                    pos2(small_icon_rect.left(), small_icon_rect.center().y),
+                    pos2(small_icon_rect.center().x, small_icon_rect.bottom()),
+                    pos2(small_icon_rect.right(), small_icon_rect.top()),
+                ],
+                visuals.fg_stroke,
+            ));
+        }
+        Check::Radio(true) => {
+            painter.circle_filled(
+                small_icon_rect.center(),
+                small_icon_rect.width() / 3.0,
+                visuals.fg_stroke.color,
+            );
+        }
+        _ => {}
+    }
+
+    let text_color = ui
+        .style()
+        .visuals
+        .override_text_color
+        .unwrap_or_else(|| visuals.text_color());
+    let text_pos = pos2(
+        inner.left() + icon_width + icon_spacing,
+        inner.center().y - 0.5 * galley.size.y,
+    );
+    painter.galley(text_pos, galley, text_style, text_color);
+
+    if let Some(shortcut_galley) = shortcut_galley {
+        let pos = pos2(
+            inner.right() - shortcut_galley.size.x,
+            inner.center().y - 0.5 * shortcut_galley.size.y,
+        );
+        let color = ui.style().visuals.widgets.disabled.text_color();
+        painter.galley(pos, shortcut_galley, text_style, color);
+    }
+
+    if submenu {
+        let arrow = Rect::from_center_size(
+            pos2(inner.right() - 0.5 * icon_width, inner.center().y),
+            Vec2::splat(0.5 * icon_width),
+        );
+        let points = vec![arrow.left_top(), arrow.right_center(), arrow.left_bottom()];
+        painter.add(PaintCmd::closed_line(points, visuals.fg_stroke));
+    }
+
+    response
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_context_menu() {
+    let mut count = 0;
+    {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("Right-click me").context_menu(|ui| {
+                    if ui.button("Increment").clicked {
+                        count += 1;
+                    }
+                });
+            });
+        };
+
+        let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+        harness.run(&mut app);
+        assert!(harness.widget("Increment").is_none());
+
+        harness.secondary_click("Right-click me", &mut app);
+        harness.run(&mut app);
+        harness.click("Increment", &mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Increment").is_none(), "Menu should close");
+    }
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_menu_bar() {
+    let mut saved = 0;
+    let mut autosave = false;
+    {
+        let mut app = |ctx: &CtxRef| {
+            TopPanel::top("menu_bar").show(ctx, |ui| {
+                bar(ui, |ui| {
+                    menu(ui, "File", |ui| {
+                        if ui.add(MenuItem::new("Save").shortcut("Ctrl+S")).clicked {
+                            saved += 1;
+                        }
+                        submenu(ui, "Preferences", |ui| {
+                            checkbox(ui, &mut autosave, "Autosave");
+                        });
+                        let _ = ui.add(MenuItem::new("Print").enabled(false));
+                    });
+                    menu(ui, "Edit", |ui| {
+                        let _ = ui.add(MenuItem::new("Undo"));
+                    });
+                });
+            });
+        };
+
+        let mut harness = testing::Harness::new(vec2(600.0, 400.0));
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_none());
+
+        // Open a menu, and hover another one to switch to it:
+        harness.click("File", &mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_some());
+        harness.hover("Edit", &mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_none());
+        assert!(harness.widget("Undo").is_some());
+
+        // Submenus open on hover, and clicking an item closes all the menus:
+        harness.hover("File", &mut app);
+        harness.run(&mut app);
+        harness.hover("Preferences", &mut app);
+        assert!(harness.widget("Autosave").is_some());
+        harness.click("Autosave", &mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_none());
+
+        // Clicking a disabled item or a submenu keeps the menu open:
+        harness.click("File", &mut app);
+        harness.run(&mut app);
+        harness.click("Print", &mut app);
+        harness.click("Preferences", &mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_some());
+        assert!(harness.widget("Autosave").is_some());
+
+        // Keyboard navigation: escape closes the submenu, down moves to the next item:
+        harness.move_mouse_to(pos2(500.0, 300.0));
+        harness.press_key(Key::Escape);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Autosave").is_none());
+        assert!(harness.expect_widget("Preferences").has_kb_focus);
+        harness.press_key(Key::ArrowDown);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        assert!(harness.expect_widget("Save").has_kb_focus, "Wraps around");
+        harness.press_key(Key::Enter);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        assert!(harness.widget("Save").is_none());
+
+        // Tapping alt opens the first menu, with the first item focused:
+        harness.set_modifiers(Modifiers {
+            alt: true,
+            ..Default::default()
+        });
+        harness.run(&mut app);
+        harness.set_modifiers(Default::default());
+        harness.run(&mut app);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        assert!(harness.expect_widget("Save").has_kb_focus);
+        harness.press_key(Key::ArrowRight);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        harness.run(&mut app);
+        assert!(harness.expect_widget("Undo").has_kb_focus);
+    }
+    assert_eq!(saved, 1);
+    assert!(autosave);
 }
@@ -46,7 +46,9 @@ pub enum PaintCmd {
         pos: Pos2,
         /// The layed out text
         galley: Galley,
+        /// Ignored if the galley was laid out from a `LayoutJob` (it knows its own fonts and colors).
         text_style: TextStyle, // TODO: Font?
+        /// Ignored if the galley was laid out from a `LayoutJob` (it knows its own fonts and colors).
         color: Color32,
     },
     Triangles(Triangles),
@@ -235,7 +235,7 @@ impl Font {
     /// Typeset the given text onto one row.
     /// Assumes there are no `\n` in the text.
     /// Return `x_offsets`, one longer than the number of characters in the text.
-    fn layout_single_row_fragment(&self, text: &str) -> Vec<f32> {
+    pub(crate) fn layout_single_row_fragment(&self, text: &str) -> Vec<f32> {
         let mut x_offsets = Vec::with_capacity(text.chars().count() + 1);
         x_offsets.push(0.0);
 
@@ -278,6 +278,7 @@ impl Font {
             text,
             rows: vec![row],
             size,
+            sections: vec![],
         };
         galley.sanity_check();
         galley
@@ -351,7 +352,12 @@ impl Font {
         }
         let size = vec2(widest_row, rows.last().unwrap().y_max);
 
-        let galley = Galley { text, rows, size };
+        let galley = Galley {
+            text,
+            rows,
+            size,
+            sections: vec![],
+        };
         galley.sanity_check();
         galley
     }
@@ -362,81 +368,25 @@ impl Font {
     fn layout_paragraph_max_width(
         &self,
         text: &str,
-        mut first_row_indentation: f32,
+        first_row_indentation: f32,
         max_width_in_points: f32,
     ) -> Vec<Row> {
-        if text.is_empty() {
-            return vec![Row {
-                x_offsets: vec![first_row_indentation],
-                y_min: 0.0,
-                y_max: self.row_height(),
-                ends_with_newline: false,
-            }];
-        }
-
         let full_x_offsets = self.layout_single_row_fragment(text);
-
-        let mut row_start_x = 0.0; // NOTE: BEFORE the `first_row_indentation`.
+        let wrapped_rows = wrap_paragraph(
+            text,
+            &full_x_offsets,
+            first_row_indentation,
+            max_width_in_points,
+        );
 
         let mut cursor_y = 0.0;
-        let mut row_start_idx = 0;
-
-        // start index of the last space. A candidate for a new row.
-        let mut last_space = None;
-
-        let mut out_rows = vec![];
-
-        for (i, (x, chr)) in full_x_offsets.iter().skip(1).zip(text.chars()).enumerate() {
-            debug_assert!(chr != '\n');
-            let potential_row_width = first_row_indentation + x - row_start_x;
-
-            if potential_row_width > max_width_in_points {
-                if let Some(last_space_idx) = last_space {
-                    // We include the trailing space in the row:
-                    let row = Row {
-                        x_offsets: full_x_offsets[row_start_idx..=last_space_idx + 1]
-                            .iter()
-                            .map(|x| first_row_indentation + x - row_start_x)
-                            .collect(),
-                        y_min: cursor_y,
-                        y_max: cursor_y + self.row_height(),
-                        ends_with_newline: false,
-                    };
-                    row.sanity_check();
-                    out_rows.push(row);
-
-                    row_start_idx = last_space_idx + 1;
-                    row_start_x = first_row_indentation + full_x_offsets[row_start_idx];
-                    last_space = None;
-                    cursor_y = self.round_to_pixel(cursor_y + self.row_height());
-                } else if out_rows.is_empty() && first_row_indentation > 0.0 {
-                    assert_eq!(row_start_idx, 0);
-                    // Allow the first row to be completely empty, because we know there will be more space on the next row:
-                    let row = Row {
-                        x_offsets: vec![first_row_indentation],
-                        y_min: cursor_y,
-                        y_max: cursor_y + self.row_height(),
-                        ends_with_newline: false,
-                    };
-                    row.sanity_check();
-                    out_rows.push(row);
-                    cursor_y = self.round_to_pixel(cursor_y + self.row_height());
-                    first_row_indentation = 0.0; // Continue all other rows as if there is no indentation
-                }
-            }
-
-            const NON_BREAKING_SPACE: char = '\u{A0}';
-            if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
-                last_space = Some(i);
+        let mut out_rows = Vec::with_capacity(wrapped_rows.len());
+        for (_, x_offsets) in wrapped_rows {
+            if !out_rows.is_empty() {
+                cursor_y = self.round_to_pixel(cursor_y + self.row_height());
             }
-        }
-
-        if row_start_idx + 1 < full_x_offsets.len() {
             let row = Row {
-                x_offsets: full_x_offsets[row_start_idx..]
-                    .iter()
-                    .map(|x| first_row_indentation + x - row_start_x)
-                    .collect(),
+                x_offsets,
                 y_min: cursor_y,
                 y_max: cursor_y + self.row_height(),
                 ends_with_newline: false,
@@ -444,11 +394,77 @@ impl Font {
             row.sanity_check();
             out_rows.push(row);
         }
-
         out_rows
     }
 }
 
+/// Break a paragraph (text with no line break character in it) into rows
+/// no wider than `max_width_in_points`, breaking at spaces.
+///
+/// `full_x_offsets` is the paragraph laid out on a single row, starting at zero,
+/// one longer than the number of characters in `text`.
+///
+/// Returns the index of the first character of each row, and the `x_offsets` of that row.
+/// Always returns at least one row.
+pub(crate) fn wrap_paragraph(
+    text: &str,
+    full_x_offsets: &[f32],
+    mut first_row_indentation: f32,
+    max_width_in_points: f32,
+) -> Vec<(usize, Vec<f32>)> {
+    if text.is_empty() {
+        return vec![(0, vec![first_row_indentation])];
+    }
+
+    let mut row_start_x = 0.0; // NOTE: BEFORE the `first_row_indentation`.
+
+    let mut row_start_idx = 0;
+
+    // start index of the last space. A candidate for a new row.
+    let mut last_space = None;
+
+    let mut out_rows = vec![];
+
+    for (i, (x, chr)) in full_x_offsets.iter().skip(1).zip(text.chars()).enumerate() {
+        let potential_row_width = first_row_indentation + x - row_start_x;
+
+        if potential_row_width > max_width_in_points {
+            if let Some(last_space_idx) = last_space {
+                // We include the trailing space in the row:
+                let x_offsets = full_x_offsets[row_start_idx..=last_space_idx + 1]
+                    .iter()
+                    .map(|x| first_row_indentation + x - row_start_x)
+                    .collect();
+                out_rows.push((row_start_idx, x_offsets));
+
+                row_start_idx = last_space_idx + 1;
+                row_start_x = first_row_indentation + full_x_offsets[row_start_idx];
+                last_space = None;
+            } else if out_rows.is_empty() && first_row_indentation > 0.0 {
+                assert_eq!(row_start_idx, 0);
+                // Allow the first row to be completely empty, because we know there will be more space on the next row:
+                out_rows.push((0, vec![first_row_indentation]));
+                first_row_indentation = 0.0; // Continue all other rows as if there is no indentation
+            }
+        }
+
+        const NON_BREAKING_SPACE: char = '\u{A0}';
+        if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
+            last_space = Some(i);
+        }
+    }
+
+    if row_start_idx + 1 < full_x_offsets.len() {
+        let x_offsets = full_x_offsets[row_start_idx..]
+            .iter()
+            .map(|x| first_row_indentation + x - row_start_x)
+            .collect();
+        out_rows.push((row_start_idx, x_offsets));
+    }
+
+    out_rows
+}
+
 fn allocate_glyph(
     atlas: &mut TextureAtlas,
     glyph: rusttype::Glyph<'static>,
@@ -176,6 +176,9 @@ impl Default for FontDefinitions {
     }
 }
 
+/// How many different font sizes [`Fonts::font`] creates before it starts reusing the closest one.
+const MAX_SIZED_FONTS: usize = 64;
+
 /// The collection of fonts used by Egui.
 ///
 /// Note: `Fonts::default()` is invalid (missing `pixels_per_point`).
@@ -183,8 +186,12 @@ impl Default for FontDefinitions {
 pub struct Fonts {
     pixels_per_point: f32,
     definitions: FontDefinitions,
-    fonts: BTreeMap<TextStyle, Font>,
+    fonts: BTreeMap<TextStyle, Arc<Font>>,
     atlas: Arc<Mutex<TextureAtlas>>,
+    font_impl_cache: Mutex<FontImplCache>,
+    /// Fonts of a specific family and size, created on demand by [`Self::font`].
+    /// Linear search, just like `FontImplCache::cache`.
+    sized_fonts: Mutex<Vec<(FontFamily, f32, Arc<Font>)>>,
     /// Copy of the texture in the texture atlas.
     /// This is so we can return a reference to it (the texture atlas is behind a lock).
     buffered_texture: Mutex<Arc<Texture>>,
@@ -207,20 +214,14 @@ impl Fonts {
 
         let mut font_impl_cache = FontImplCache::new(atlas.clone(), pixels_per_point, &definitions);
 
+        let mut sized_fonts = vec![];
         let fonts = definitions
             .family_and_size
             .iter()
             .map(|(&text_style, &(family, scale_in_points))| {
-                let fonts = &definitions.fonts_for_family.get(&family);
-                let fonts = fonts.unwrap_or_else(|| {
-                    panic!("FontFamily::{:?} is not bound to any fonts", family)
-                });
-                let fonts: Vec<Arc<FontImpl>> = fonts
-                    .iter()
-                    .map(|font_name| font_impl_cache.font_impl(font_name, scale_in_points))
-                    .collect();
-
-                (text_style, Font::new(fonts))
+                let font = Arc::new(font_impl_cache.font(&definitions, family, scale_in_points));
+                sized_fonts.push((family, scale_in_points, font.clone()));
+                (text_style, font)
             })
             .collect();
 
@@ -239,10 +240,69 @@ impl Fonts {
             definitions,
             fonts,
             atlas,
+            font_impl_cache: Mutex::new(font_impl_cache),
+            sized_fonts: Mutex::new(sized_fonts),
             buffered_texture: Default::default(), //atlas.lock().texture().clone();
         }
     }
 
+    /// The font of the given family and size (in points).
+    ///
+    /// The fonts of the [`TextStyle`]s are always available.
+    /// Other sizes are rounded to whole physical pixels and created the first time they are asked for.
+    /// The glyphs of each size take up room in the font texture (for as long as the `Fonts` live),
+    /// so after a few dozen sizes we use the closest one we already have instead.
+    pub fn font(&self, family: FontFamily, scale_in_points: f32) -> Arc<Font> {
+        let mut sized_fonts = self.sized_fonts.lock();
+        let find = |sized_fonts: &[(FontFamily, f32, Arc<Font>)], scale_in_points: f32| {
+            sized_fonts
+                .iter()
+                .find(|entry| (entry.0, entry.1) == (family, scale_in_points))
+                .map(|entry| entry.2.clone())
+        };
+        if let Some(font) = find(&sized_fonts, scale_in_points) {
+            return font; // e.g. the exact size of a `TextStyle`
+        }
+
+        let scale_in_pixels = (scale_in_points * self.pixels_per_point).round().max(1.0);
+        let scale_in_points = scale_in_pixels / self.pixels_per_point;
+        if let Some(font) = find(&sized_fonts, scale_in_points) {
+            return font;
+        }
+
+        if sized_fonts.len() >= MAX_SIZED_FONTS {
+            let closest = sized_fonts
+                .iter()
+                .filter(|entry| entry.0 == family)
+                .min_by(|a, b| {
+                    let a = (a.1 - scale_in_points).abs();
+                    let b = (b.1 - scale_in_points).abs();
+                    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                });
+            if let Some(entry) = closest {
+                return entry.2.clone();
+            }
+        }
+
+        let font = Arc::new(self.font_impl_cache.lock().font(
+            &self.definitions,
+            family,
+            scale_in_points,
+        ));
+        sized_fonts.push((family, scale_in_points, font.clone()));
+        font
+    }
+
+    /// The [`FontFamily`] and size used for the given [`TextStyle`].
+    pub fn family_and_size(&self, text_style: TextStyle) -> (FontFamily, f32) {
+        self.definitions.family_and_size[&text_style]
+    }
+
+    /// Lay out rich text, where each section can have its own font and color.
+    pub fn layout_job(&self, job: super::LayoutJob) -> super::Galley {
+        super::text_layout::layout(self, job)
+    }
+
     pub fn pixels_per_point(&self) -> f32 {
         self.pixels_per_point
     }
@@ -273,6 +333,7 @@ impl std::ops::Index<TextStyle> for Fonts {
 
 // ----------------------------------------------------------------------------
 
+#[derive(Default)]
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
@@ -332,4 +393,49 @@ impl FontImplCache {
             .push((font_name.to_owned(), scale_in_points, font_impl.clone()));
         font_impl
     }
+
+    /// A [`Font`] with all the fonts of the given family, in order of priority.
+    pub fn font(
+        &mut self,
+        definitions: &FontDefinitions,
+        family: FontFamily,
+        scale_in_points: f32,
+    ) -> Font {
+        let font_names = definitions
+            .fonts_for_family
+            .get(&family)
+            .unwrap_or_else(|| panic!("FontFamily::{:?} is not bound to any fonts", family));
+        let fonts: Vec<Arc<FontImpl>> = font_names
+            .iter()
+            .map(|font_name| self.font_impl(font_name, scale_in_points))
+            .collect();
+        Font::new(fonts)
+    }
+}
+
+#[test]
+fn test_font_sizes_are_cached() {
+    let fonts = Fonts::from_definitions(2.0, Default::default());
+    let body = fonts.family_and_size(TextStyle::Body);
+    assert!(Arc::ptr_eq(
+        &fonts.font(body.0, body.1),
+        &fonts.font(body.0, body.1)
+    ));
+
+    // Sizes are rounded to whole physical pixels:
+    let family = FontFamily::Proportional;
+    assert!(Arc::ptr_eq(
+        &fonts.font(family, 20.1),
+        &fonts.font(family, 19.9)
+    ));
+    assert!(!Arc::ptr_eq(
+        &fonts.font(family, 20.0),
+        &fonts.font(family, 20.5)
+    ));
+
+    // Zooming smoothly through lots of sizes doesn't create a font for each:
+    for i in 0..10_000 {
+        fonts.font(family, 1.0 + i as f32 * 0.1);
+    }
+    assert_eq!(fonts.sized_fonts.lock().len(), MAX_SIZED_FONTS);
 }
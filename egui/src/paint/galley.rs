@@ -139,6 +139,10 @@ pub struct Galley {
 
     // Optimization: calculated once and reused.
     pub size: Vec2,
+
+    /// The fonts and colors of the text, if it was laid out from a [`super::LayoutJob`].
+    /// Empty for text laid out with a single [`super::Font`].
+    pub sections: Vec<super::LayoutSection>,
 }
 
 /// A typeset piece of text on a single row.
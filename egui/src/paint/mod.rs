@@ -5,9 +5,11 @@ pub mod command;
 pub mod font;
 pub mod fonts;
 mod galley;
+pub mod rasterizer;
 mod shadow;
 pub mod stats;
 pub mod tessellator;
+mod text_layout;
 mod texture_atlas;
 
 pub use {
@@ -20,6 +22,7 @@ pub use {
     tessellator::{
         PaintJob, PaintJobs, TessellationOptions, TextureId, Triangles, Vertex, WHITE_UV,
     },
+    text_layout::{LayoutJob, LayoutSection, TextFormat},
     texture_atlas::{Texture, TextureAtlas},
 };
 
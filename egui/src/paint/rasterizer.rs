@@ -0,0 +1,389 @@
+//! Paints [`PaintJobs`] on the CPU, without any GPU.
+//!
+//! Slow compared to a GPU backend, but useful for screenshot tests, thumbnails
+//! and on machines where there is no GPU available.
+//!
+//! Blending is done in linear space with premultiplied alpha,
+//! just like the `egui_glium` backend does, so the results should match closely.
+
+use {
+    super::{Color32, PaintJob, Rgba, Texture, TextureId, Triangles, Vertex},
+    crate::math::*,
+};
+
+/// An image of `sRGBA` pixels with premultiplied alpha, row by row, top to bottom.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColorImage {
+    /// width, height
+    pub size: [usize; 2],
+    pub pixels: Vec<Color32>,
+}
+
+impl ColorImage {
+    /// An image filled with the given color.
+    pub fn new(size: [usize; 2], color: Color32) -> Self {
+        Self {
+            size,
+            pixels: vec![color; size[0] * size[1]],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.size[0]
+    }
+
+    pub fn height(&self) -> usize {
+        self.size[1]
+    }
+
+    /// The pixels as `sRGBA` bytes WITHOUT premultiplied alpha,
+    /// which is what most image formats (e.g. PNG) expect.
+    pub fn to_srgba_unmultiplied(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 * self.pixels.len());
+        for &color in &self.pixels {
+            let a = color.a();
+            if a == 0 {
+                bytes.extend_from_slice(&[0, 0, 0, 0]);
+            } else if a == 255 {
+                bytes.extend_from_slice(&color.to_array());
+            } else {
+                let opaque = Color32::from(Rgba::from(color).to_opaque());
+                bytes.extend_from_slice(&[opaque.r(), opaque.g(), opaque.b(), a]);
+            }
+        }
+        bytes
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for ColorImage {
+    type Output = Color32;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Color32 {
+        assert!(x < self.size[0]);
+        assert!(y < self.size[1]);
+        &self.pixels[y * self.size[0] + x]
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A texture in linear space with premultiplied alpha, ready for sampling.
+#[derive(Default)]
+struct LinearTexture {
+    size: [usize; 2],
+    pixels: Vec<Rgba>,
+}
+
+impl LinearTexture {
+    fn from_srgba(size: [usize; 2], pixels: impl Iterator<Item = Color32>) -> Self {
+        let pixels: Vec<Rgba> = pixels.map(Rgba::from).collect();
+        assert_eq!(size[0] * size[1], pixels.len());
+        Self { size, pixels }
+    }
+
+    fn pixel(&self, x: isize, y: isize) -> Rgba {
+        // Clamping sampler, like the GPU backends use:
+        let x = clamp(x, 0..=self.size[0] as isize - 1) as usize;
+        let y = clamp(y, 0..=self.size[1] as isize - 1) as usize;
+        self.pixels[y * self.size[0] + x]
+    }
+
+    /// Bilinear sampling at normalized texture coordinates.
+    fn sample(&self, uv: Pos2) -> Rgba {
+        if self.pixels.is_empty() {
+            return Rgba::TRANSPARENT;
+        }
+        let x = uv.x * self.size[0] as f32 - 0.5;
+        let y = uv.y * self.size[1] as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+        let top = lerp(self.pixel(x0, y0)..=self.pixel(x0 + 1, y0), tx);
+        let bottom = lerp(self.pixel(x0, y0 + 1)..=self.pixel(x0 + 1, y0 + 1), tx);
+        lerp(top..=bottom, ty)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Paints [`PaintJobs`](super::PaintJobs) into a [`ColorImage`] on the CPU.
+///
+/// The API mirrors that of `egui_glium::Painter`:
+///
+/// ```
+/// let mut ctx = egui::CtxRef::default();
+/// ctx.begin_frame(Default::default());
+/// egui::CentralPanel::default().show(&ctx, |ui| {
+///     ui.label("Hello");
+/// });
+/// let (_output, paint_commands) = ctx.end_frame();
+/// let paint_jobs = ctx.tessellate(paint_commands);
+///
+/// let mut rasterizer = egui::paint::rasterizer::Rasterizer::default();
+/// let image = rasterizer.paint_jobs(
+///     [640, 480],
+///     ctx.pixels_per_point(),
+///     egui::Rgba::BLACK,
+///     &paint_jobs,
+///     &ctx.texture(),
+/// );
+/// assert_eq!(image.size, [640, 480]);
+/// ```
+#[derive(Default)]
+pub struct Rasterizer {
+    egui_texture: LinearTexture,
+    egui_texture_version: Option<u64>,
+    user_textures: Vec<Option<LinearTexture>>,
+}
+
+impl Rasterizer {
+    /// Paint the given jobs into a new image of the given size in physical pixels.
+    pub fn paint_jobs(
+        &mut self,
+        size_in_pixels: [usize; 2],
+        pixels_per_point: f32,
+        clear_color: Rgba,
+        jobs: &[PaintJob],
+        egui_texture: &Texture,
+    ) -> ColorImage {
+        self.upload_egui_texture(egui_texture);
+
+        let mut target = vec![clear_color; size_in_pixels[0] * size_in_pixels[1]];
+        for (clip_rect, triangles) in jobs {
+            self.paint_job(
+                &mut target,
+                size_in_pixels,
+                pixels_per_point,
+                *clip_rect,
+                triangles,
+            );
+        }
+
+        ColorImage {
+            size: size_in_pixels,
+            pixels: target.into_iter().map(Color32::from).collect(),
+        }
+    }
+
+    fn upload_egui_texture(&mut self, texture: &Texture) {
+        if self.egui_texture_version != Some(texture.version) {
+            self.egui_texture =
+                LinearTexture::from_srgba([texture.width, texture.height], texture.srgba_pixels());
+            self.egui_texture_version = Some(texture.version);
+        }
+    }
+
+    fn paint_job(
+        &self,
+        target: &mut [Rgba],
+        size_in_pixels: [usize; 2],
+        pixels_per_point: f32,
+        clip_rect: Rect,
+        triangles: &Triangles,
+    ) {
+        debug_assert!(triangles.is_valid());
+
+        let texture = match self.get_texture(triangles.texture_id) {
+            Some(texture) => texture,
+            None => return,
+        };
+
+        // Transform clip rect to physical pixels, rounded the same way the GPU backends do:
+        let [width, height] = size_in_pixels;
+        let clip_min_x = clamp(pixels_per_point * clip_rect.min.x, 0.0..=width as f32);
+        let clip_min_y = clamp(pixels_per_point * clip_rect.min.y, 0.0..=height as f32);
+        let clip_max_x = clamp(
+            pixels_per_point * clip_rect.max.x,
+            clip_min_x..=width as f32,
+        );
+        let clip_max_y = clamp(
+            pixels_per_point * clip_rect.max.y,
+            clip_min_y..=height as f32,
+        );
+        let clip = [
+            clip_min_x.round() as usize,
+            clip_min_y.round() as usize,
+            clip_max_x.round() as usize,
+            clip_max_y.round() as usize,
+        ];
+
+        for indices in triangles.indices.chunks_exact(3) {
+            let vertex = |i: u32| {
+                let v: &Vertex = &triangles.vertices[i as usize];
+                (
+                    pos2(pixels_per_point * v.pos.x, pixels_per_point * v.pos.y),
+                    v.uv,
+                    Rgba::from(v.color),
+                )
+            };
+            paint_triangle(
+                target,
+                width,
+                clip,
+                texture,
+                [vertex(indices[0]), vertex(indices[1]), vertex(indices[2])],
+            );
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // user textures
+
+    pub fn alloc_user_texture(&mut self) -> TextureId {
+        for (i, tex) in self.user_textures.iter_mut().enumerate() {
+            if tex.is_none() {
+                *tex = Some(Default::default());
+                return TextureId::User(i as u64);
+            }
+        }
+        let id = TextureId::User(self.user_textures.len() as u64);
+        self.user_textures.push(Some(Default::default()));
+        id
+    }
+
+    /// Set the `sRGBA` pixels (with premultiplied alpha) of a texture allocated with [`Self::alloc_user_texture`].
+    pub fn set_user_texture(&mut self, id: TextureId, size: (usize, usize), pixels: &[Color32]) {
+        assert_eq!(size.0 * size.1, pixels.len());
+
+        if let TextureId::User(id) = id {
+            if let Some(Some(user_texture)) = self.user_textures.get_mut(id as usize) {
+                *user_texture = LinearTexture::from_srgba([size.0, size.1], pixels.iter().copied());
+            }
+        }
+    }
+
+    pub fn free_user_texture(&mut self, id: TextureId) {
+        if let TextureId::User(id) = id {
+            let index = id as usize;
+            if index < self.user_textures.len() {
+                self.user_textures[index] = None;
+            }
+        }
+    }
+
+    fn get_texture(&self, texture_id: TextureId) -> Option<&LinearTexture> {
+        match texture_id {
+            TextureId::Egui => Some(&self.egui_texture),
+            TextureId::User(id) => self.user_textures.get(id as usize)?.as_ref(),
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, p`.
+fn edge_function(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Top-left fill rule: pixel centers exactly on an edge are only painted if it is a top or left edge,
+/// so that triangles sharing an edge don't paint (and blend) those pixels twice.
+fn is_top_left(a: Pos2, b: Pos2) -> bool {
+    let d = b - a;
+    (d.y == 0.0 && d.x > 0.0) || d.y < 0.0
+}
+
+/// `clip` is `[min_x, min_y, max_x, max_y]` in pixels, with the max exclusive.
+fn paint_triangle(
+    target: &mut [Rgba],
+    width: usize,
+    clip: [usize; 4],
+    texture: &LinearTexture,
+    mut vertices: [(Pos2, Pos2, Rgba); 3],
+) {
+    let mut area = edge_function(vertices[0].0, vertices[1].0, vertices[2].0);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        vertices.swap(1, 2);
+        area = -area;
+    }
+    let [(p0, uv0, c0), (p1, uv1, c1), (p2, uv2, c2)] = vertices;
+
+    let min_x = p0.x.min(p1.x).min(p2.x).floor().max(clip[0] as f32) as usize;
+    let min_y = p0.y.min(p1.y).min(p2.y).floor().max(clip[1] as f32) as usize;
+    let max_x = (p0.x.max(p1.x).max(p2.x).ceil() as usize).min(clip[2]);
+    let max_y = (p0.y.max(p1.y).max(p2.y).ceil() as usize).min(clip[3]);
+
+    let edges = [(p1, p2), (p2, p0), (p0, p1)];
+    let top_left = [
+        is_top_left(p1, p2),
+        is_top_left(p2, p0),
+        is_top_left(p0, p1),
+    ];
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+
+            let mut w = [0.0; 3];
+            let mut inside = true;
+            for i in 0..3 {
+                w[i] = edge_function(edges[i].0, edges[i].1, p);
+                inside &= w[i] > 0.0 || (w[i] == 0.0 && top_left[i]);
+            }
+            if !inside {
+                continue;
+            }
+            let [w0, w1, w2] = [w[0] / area, w[1] / area, w[2] / area];
+
+            let uv = pos2(
+                w0 * uv0.x + w1 * uv1.x + w2 * uv2.x,
+                w0 * uv0.y + w1 * uv1.y + w2 * uv2.y,
+            );
+            let color = w0 * c0 + w1 * c1 + w2 * c2;
+            let src = color * texture.sample(uv);
+
+            // Premultiplied alpha blending:
+            let dst = &mut target[y * width + x];
+            *dst = src + (1.0 - src.a()) * *dst;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize_rect() {
+        let texture = Texture {
+            version: 0,
+            width: 1,
+            height: 1,
+            pixels: vec![255],
+        };
+        let paint = |rect: Rect, color: Color32| {
+            let mut triangles = Triangles::default();
+            triangles.add_colored_rect(rect, color);
+            let clip_rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(8.0, 8.0));
+            Rasterizer::default().paint_jobs(
+                [8, 8],
+                1.0,
+                Rgba::TRANSPARENT,
+                &[(clip_rect, triangles)],
+                &texture,
+            )
+        };
+
+        let image = paint(
+            Rect::from_min_max(pos2(2.0, 2.0), pos2(6.0, 6.0)),
+            Color32::RED,
+        );
+        assert_eq!(image[(0, 0)], Color32::TRANSPARENT);
+        assert_eq!(image[(2, 2)], Color32::RED);
+        assert_eq!(image[(5, 5)], Color32::RED);
+        assert_eq!(image[(6, 6)], Color32::TRANSPARENT);
+        assert_eq!(
+            image.pixels.iter().filter(|&&c| c == Color32::RED).count(),
+            16
+        );
+
+        // The two triangles of a translucent rect must not overlap along the diagonal:
+        let translucent = Color32::from_rgba_premultiplied(100, 0, 0, 128);
+        let image = paint(
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(8.0, 8.0)),
+            translucent,
+        );
+        assert!(image.pixels.iter().all(|&c| c == image[(0, 7)]));
+    }
+}
@@ -789,7 +789,11 @@ impl Tessellator {
                 text_style,
                 color,
             } => {
-                self.tessellate_text(fonts, pos, &galley, text_style, color, out);
+                if galley.sections.is_empty() {
+                    self.tessellate_text(fonts, pos, &galley, text_style, color, out);
+                } else {
+                    self.tessellate_rich_text(fonts, pos, &galley, out);
+                }
             }
         }
     }
@@ -886,6 +890,117 @@ impl Tessellator {
         }
         assert_eq!(chars.next(), None);
     }
+
+    /// Tessellate a [`super::Galley`] laid out from a [`super::LayoutJob`],
+    /// using the font, color, background, underline and strikethrough of each section.
+    pub fn tessellate_rich_text(
+        &mut self,
+        fonts: &Fonts,
+        pos: Pos2,
+        galley: &super::Galley,
+        out: &mut Triangles,
+    ) {
+        galley.sanity_check();
+
+        // Backgrounds go below the glyphs, so we collect the glyphs separately:
+        let mut glyphs = Triangles::default();
+        let num_chars = galley.text.chars().count();
+        glyphs.reserve_triangles(num_chars * 2);
+        glyphs.reserve_vertices(num_chars * 4);
+
+        let tex_w = fonts.texture().width as f32;
+        let tex_h = fonts.texture().height as f32;
+
+        let text_offset = vec2(0.0, 1.0); // Same as `tessellate_text`.
+
+        let clip_rect = self.clip_rect.expand(2.0); // Some fudge to handle letters that are slightly larger than expected.
+
+        let section_fonts: Vec<_> = galley
+            .sections
+            .iter()
+            .map(|section| fonts.font(section.format.family, section.format.size))
+            .collect();
+
+        // Consecutive characters on the same row with the same section,
+        // as `(row index, section index, min x, max x)`:
+        let mut runs: Vec<(usize, usize, f32, f32)> = vec![];
+
+        let mut chars = galley.text.char_indices();
+        for (row_index, row) in galley.rows.iter().enumerate() {
+            let is_row_visible =
+                pos.y + row.y_max >= clip_rect.min.y && pos.y + row.y_min <= clip_rect.max.y;
+
+            for x_range in row.x_offsets.windows(2) {
+                let (byte_index, c) = chars.next().unwrap();
+                let section_index =
+                    super::text_layout::section_index_at(&galley.sections, byte_index);
+
+                match runs.last_mut() {
+                    Some(run) if run.0 == row_index && run.1 == section_index => {
+                        run.3 = x_range[1];
+                    }
+                    _ => runs.push((row_index, section_index, x_range[0], x_range[1])),
+                }
+
+                if self.options.coarse_tessellation_culling && !is_row_visible {
+                    continue;
+                }
+
+                let font = &section_fonts[section_index];
+                if let Some(glyph) = font.uv_rect(c) {
+                    // Align the bottom of all fonts on the row:
+                    let y = row.y_max - font.row_height();
+                    let mut left_top = pos + glyph.offset + vec2(x_range[0], y) + text_offset;
+                    left_top.x = font.round_to_pixel(left_top.x); // Pixel-perfection.
+                    left_top.y = font.round_to_pixel(left_top.y); // Pixel-perfection.
+
+                    let rect = Rect::from_min_max(left_top, left_top + glyph.size);
+                    let uv = Rect::from_min_max(
+                        pos2(glyph.min.0 as f32 / tex_w, glyph.min.1 as f32 / tex_h),
+                        pos2(glyph.max.0 as f32 / tex_w, glyph.max.1 as f32 / tex_h),
+                    );
+                    let color = galley.sections[section_index].format.color;
+                    glyphs.add_rect_with_uv(rect, uv, color);
+                }
+            }
+            if row.ends_with_newline {
+                let (_, newline) = chars.next().unwrap();
+                debug_assert_eq!(newline, '\n');
+            }
+        }
+        assert_eq!(chars.next(), None);
+
+        for &(row_index, section_index, min_x, max_x) in &runs {
+            let row = &galley.rows[row_index];
+            let background = galley.sections[section_index].format.background;
+            if background != Color32::TRANSPARENT {
+                let rect =
+                    Rect::from_min_max(pos + vec2(min_x, row.y_min), pos + vec2(max_x, row.y_max));
+                out.add_colored_rect(rect, background);
+            }
+        }
+
+        out.append(glyphs);
+
+        for &(row_index, section_index, min_x, max_x) in &runs {
+            let row = &galley.rows[row_index];
+            let format = &galley.sections[section_index].format;
+            let font = &section_fonts[section_index];
+            let mut line = |y: f32, stroke: Stroke| {
+                if stroke.width > 0.0 && stroke.color != Color32::TRANSPARENT {
+                    let y = font.round_to_pixel(pos.y + y) + 0.5 * stroke.width;
+                    let points = [pos2(pos.x + min_x, y), pos2(pos.x + max_x, y)];
+                    self.tessellate_paint_command(
+                        fonts,
+                        PaintCmd::line_segment(points, stroke),
+                        out,
+                    );
+                }
+            };
+            line(row.y_max - 1.0, format.underline);
+            line(row.y_max - 0.5 * font.row_height(), format.strikethrough);
+        }
+    }
 }
 
 /// Turns [`PaintCmd`]:s into sets of triangles.
@@ -0,0 +1,297 @@
+//! Rich text: a paragraph made up of sections, each with its own font and color.
+//!
+//! ```
+//! # let ui = egui::Ui::__test();
+//! use egui::paint::{Color32, FontFamily, LayoutJob, TextFormat};
+//!
+//! let mut job = LayoutJob::default();
+//! job.append("Hello ", TextFormat::simple(FontFamily::Proportional, 14.0, Color32::WHITE));
+//! job.append(
+//!     "world",
+//!     TextFormat {
+//!         color: Color32::RED,
+//!         underline: egui::Stroke::new(1.0, Color32::RED),
+//!         ..TextFormat::simple(FontFamily::Monospace, 14.0, Color32::WHITE)
+//!     },
+//! );
+//! let galley = ui.fonts().layout_job(job);
+//! assert_eq!(galley.text, "Hello world");
+//! ```
+
+use std::ops::Range;
+
+use crate::math::vec2;
+
+use super::{font::wrap_paragraph, Color32, FontFamily, Fonts, Galley, Row, Stroke, TextStyle};
+
+/// How to format a section of text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextFormat {
+    pub family: FontFamily,
+    /// Height of the font, in points.
+    pub size: f32,
+    pub color: Color32,
+    /// Painted behind the text. Use [`Color32::TRANSPARENT`] for none.
+    pub background: Color32,
+    /// Use [`Stroke::none()`] for no underline.
+    pub underline: Stroke,
+    /// Use [`Stroke::none()`] for no strikethrough.
+    pub strikethrough: Stroke,
+}
+
+impl Default for TextFormat {
+    fn default() -> Self {
+        Self::simple(FontFamily::Proportional, 14.0, Color32::GRAY)
+    }
+}
+
+impl TextFormat {
+    /// Just a font and a color: no background, underline or strikethrough.
+    pub fn simple(family: FontFamily, size: f32, color: Color32) -> Self {
+        Self {
+            family,
+            size,
+            color,
+            background: Color32::TRANSPARENT,
+            underline: Stroke::none(),
+            strikethrough: Stroke::none(),
+        }
+    }
+
+    /// Use the font family and size of the given [`TextStyle`].
+    pub fn text_style(fonts: &Fonts, text_style: TextStyle, color: Color32) -> Self {
+        let (family, size) = fonts.family_and_size(text_style);
+        Self::simple(family, size, color)
+    }
+}
+
+/// A part of the text of a [`LayoutJob`] with a specific [`TextFormat`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutSection {
+    /// Range into [`LayoutJob::text`], in bytes.
+    pub byte_range: Range<usize>,
+    pub format: TextFormat,
+}
+
+/// Describes how to lay out some rich text, i.e. text where different parts can have different
+/// fonts, colors etc.
+///
+/// Lay it out with [`Fonts::layout_job`] to get a [`Galley`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutJob {
+    /// The complete text.
+    pub text: String,
+
+    /// The sections of the text, in order. Together they must cover all of [`Self::text`].
+    pub sections: Vec<LayoutSection>,
+
+    /// Wrap the text at this width (in points). Default: no wrapping.
+    pub wrap_width: f32,
+
+    /// Extra space before the very first character (in points),
+    /// e.g. when continuing after another widget on the same row.
+    pub first_row_indentation: f32,
+
+    /// If `true` (default), each `\n` starts a new paragraph.
+    /// If `false`, `\n` is treated like any other character (and shown as a replacement glyph).
+    pub break_on_newline: bool,
+}
+
+impl Default for LayoutJob {
+    fn default() -> Self {
+        Self {
+            text: Default::default(),
+            sections: Default::default(),
+            wrap_width: f32::INFINITY,
+            first_row_indentation: 0.0,
+            break_on_newline: true,
+        }
+    }
+}
+
+impl LayoutJob {
+    /// A job with just one section.
+    pub fn single_section(text: String, format: TextFormat) -> Self {
+        Self {
+            sections: vec![LayoutSection {
+                byte_range: 0..text.len(),
+                format,
+            }],
+            text,
+            ..Default::default()
+        }
+    }
+
+    /// Add some text at the end, with the given format.
+    pub fn append(&mut self, text: &str, format: TextFormat) {
+        let start = self.text.len();
+        self.text += text;
+        self.sections.push(LayoutSection {
+            byte_range: start..self.text.len(),
+            format,
+        });
+    }
+
+    /// Is there no text?
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Index of the section that the byte at `byte_index` belongs to.
+pub(crate) fn section_index_at(sections: &[LayoutSection], byte_index: usize) -> usize {
+    sections
+        .iter()
+        .rposition(|section| section.byte_range.start <= byte_index)
+        .unwrap_or(0)
+}
+
+pub(crate) fn layout(fonts: &Fonts, mut job: LayoutJob) -> Galley {
+    if job.sections.is_empty() {
+        job.sections.push(LayoutSection {
+            byte_range: 0..job.text.len(),
+            format: Default::default(),
+        });
+    }
+    debug_assert_eq!(job.sections[0].byte_range.start, 0);
+    debug_assert_eq!(job.sections.last().unwrap().byte_range.end, job.text.len());
+
+    let section_fonts: Vec<_> = job
+        .sections
+        .iter()
+        .map(|section| fonts.font(section.format.family, section.format.size))
+        .collect();
+    let row_height_at =
+        |byte_index: usize| section_fonts[section_index_at(&job.sections, byte_index)].row_height();
+    let round_to_pixel =
+        |point: f32| (point * fonts.pixels_per_point()).round() / fonts.pixels_per_point();
+
+    let text = &job.text;
+    let mut cursor_y = 0.0;
+    let mut rows = Vec::new();
+    let mut paragraph_start = 0;
+
+    while paragraph_start < text.len() {
+        let next_newline = if job.break_on_newline {
+            text[paragraph_start..].find('\n')
+        } else {
+            None
+        };
+        let paragraph_end = next_newline
+            .map(|newline| paragraph_start + newline)
+            .unwrap_or_else(|| text.len());
+        let paragraph_text = &text[paragraph_start..paragraph_end];
+
+        // Lay out the whole paragraph on one row, one section at a time:
+        let mut full_x_offsets = vec![0.0];
+        let mut char_row_heights = vec![];
+        for (section, font) in job.sections.iter().zip(&section_fonts) {
+            let start = section.byte_range.start.max(paragraph_start);
+            let end = section.byte_range.end.min(paragraph_end);
+            if start < end {
+                let x = *full_x_offsets.last().unwrap();
+                let fragment = font.layout_single_row_fragment(&text[start..end]);
+                full_x_offsets.extend(fragment.iter().skip(1).map(|offset| x + offset));
+                char_row_heights.resize(full_x_offsets.len() - 1, font.row_height());
+            }
+        }
+
+        let first_row_indentation = if rows.is_empty() {
+            job.first_row_indentation
+        } else {
+            0.0
+        };
+        let wrapped_rows = wrap_paragraph(
+            paragraph_text,
+            &full_x_offsets,
+            first_row_indentation,
+            job.wrap_width,
+        );
+
+        let mut paragraph_rows: Vec<Row> = vec![];
+        for (first_char, x_offsets) in wrapped_rows {
+            let num_chars = x_offsets.len() - 1;
+            let row_height = char_row_heights[first_char..first_char + num_chars]
+                .iter()
+                .copied()
+                .fold(0.0, f32::max);
+            let row_height = if num_chars == 0 {
+                row_height_at(paragraph_start)
+            } else {
+                row_height
+            };
+
+            if let Some(last_row) = paragraph_rows.last() {
+                cursor_y = round_to_pixel(last_row.y_max);
+            }
+            paragraph_rows.push(Row {
+                x_offsets,
+                y_min: cursor_y,
+                y_max: cursor_y + row_height,
+                ends_with_newline: false,
+            });
+        }
+        let last_row = paragraph_rows.last_mut().unwrap();
+        last_row.ends_with_newline = next_newline.is_some();
+        cursor_y = last_row.y_max;
+        cursor_y += last_row.height() * 0.4; // Extra spacing between paragraphs, just like `Font::layout_multiline`.
+
+        rows.append(&mut paragraph_rows);
+        paragraph_start = paragraph_end + 1;
+    }
+
+    if text.is_empty() || (job.break_on_newline && text.ends_with('\n')) {
+        let row_height = row_height_at(text.len());
+        rows.push(Row {
+            x_offsets: vec![0.0],
+            y_min: cursor_y,
+            y_max: cursor_y + row_height,
+            ends_with_newline: false,
+        });
+    }
+
+    let widest_row = rows.iter().map(|row| row.max_x()).fold(0.0, f32::max);
+    let size = vec2(widest_row, rows.last().unwrap().y_max);
+
+    let galley = Galley {
+        text: job.text,
+        rows,
+        size,
+        sections: job.sections,
+    };
+    galley.sanity_check();
+    galley
+}
+
+#[test]
+fn test_layout_job() {
+    let fonts = Fonts::from_definitions(1.0, Default::default());
+    let text = "Hello world, this is a long text that will wrap.\nSecond paragraph";
+
+    // A single section is laid out just like `Font::layout_multiline`:
+    let body = TextFormat::text_style(&fonts, TextStyle::Body, Color32::WHITE);
+    let mut job = LayoutJob::single_section(text.to_owned(), body);
+    job.wrap_width = 100.0;
+    let galley = fonts.layout_job(job);
+    let expected = fonts[TextStyle::Body].layout_multiline(text.to_owned(), 100.0);
+    assert_eq!(galley.rows.len(), expected.rows.len());
+    assert_eq!(galley.size, expected.size);
+    for (row, expected) in galley.rows.iter().zip(&expected.rows) {
+        assert_eq!(row.x_offsets, expected.x_offsets);
+        assert_eq!((row.y_min, row.y_max), (expected.y_min, expected.y_max));
+    }
+
+    // A row is as high as its biggest font:
+    let mut job = LayoutJob::default();
+    job.append("small ", TextFormat { size: 10.0, ..body });
+    job.append("big", TextFormat { size: 30.0, ..body });
+    let galley = fonts.layout_job(job);
+    assert_eq!(galley.rows.len(), 1);
+    assert_eq!(
+        galley.rows[0].height(),
+        fonts.font(body.family, 30.0).row_height()
+    );
+    assert_eq!(galley.sections.len(), 2);
+}
@@ -3,6 +3,7 @@ use crate::{
     layers::PaintCmdIdx,
     math::{Pos2, Rect, Vec2},
     paint::{Fonts, Galley, PaintCmd, Stroke, TextStyle},
+    viewport::ViewportId,
     Color32, CtxRef, LayerId,
 };
 
@@ -17,6 +18,9 @@ pub struct Painter {
     /// Where we paint
     layer_id: LayerId,
 
+    /// The viewport that was current when we were created.
+    viewport_id: ViewportId,
+
     /// Everything painted in this `Painter` will be clipped against this.
     /// This means nothing outside of this rectangle will be visible on screen.
     clip_rect: Rect,
@@ -24,20 +28,18 @@ pub struct Painter {
 
 impl Painter {
     pub fn new(ctx: CtxRef, layer_id: LayerId, clip_rect: Rect) -> Self {
+        let viewport_id = ctx.viewport_id();
         Self {
             ctx,
             layer_id,
+            viewport_id,
             clip_rect,
         }
     }
 
     #[must_use]
     pub fn with_layer_id(self, layer_id: LayerId) -> Self {
-        Self {
-            ctx: self.ctx,
-            layer_id,
-            clip_rect: self.clip_rect,
-        }
+        Self { layer_id, ..self }
     }
 
     /// redirect
@@ -50,11 +52,10 @@ impl Painter {
     /// The clip-rect of the returned `Painter` will be the intersection
     /// of the given rectangle and the `clip_rect()` of this `Painter`.
     pub fn sub_region(&self, rect: Rect) -> Self {
-        Self::new(
-            self.ctx.clone(),
-            self.layer_id,
-            rect.intersect(self.clip_rect),
-        )
+        Self {
+            clip_rect: rect.intersect(self.clip_rect),
+            ..self.clone()
+        }
     }
 }
 
@@ -110,14 +111,14 @@ impl Painter {
     pub fn add(&self, paint_cmd: PaintCmd) -> PaintCmdIdx {
         self.ctx
             .graphics()
-            .list(self.layer_id)
+            .list(self.viewport_id, self.layer_id)
             .add(self.clip_rect, paint_cmd)
     }
 
     pub fn extend(&self, cmds: Vec<PaintCmd>) {
         self.ctx
             .graphics()
-            .list(self.layer_id)
+            .list(self.viewport_id, self.layer_id)
             .extend(self.clip_rect, cmds);
     }
 
@@ -125,7 +126,7 @@ impl Painter {
     pub fn set(&self, idx: PaintCmdIdx, cmd: PaintCmd) {
         self.ctx
             .graphics()
-            .list(self.layer_id)
+            .list(self.viewport_id, self.layer_id)
             .set(idx, self.clip_rect, cmd)
     }
 }
@@ -129,16 +129,25 @@ pub struct Visuals {
 
     pub selection: Selection,
 
+    /// Drawn around the widget with keyboard focus (e.g. after pressing Tab).
+    pub focus_stroke: Stroke,
+
     /// e.g. the background of the slider or text edit,
     /// needs to look different from other interactive stuff.
     pub dark_bg_color: Color32, // TODO: remove, rename, or clarify what it is for
 
+    /// Subtle background color used to make e.g. every other row of a striped [`Grid`](crate::Grid) stand out.
+    pub faint_bg_color: Color32,
+
     /// The color used for `Hyperlink`,
     pub hyperlink_color: Color32,
 
     pub window_corner_radius: f32,
     pub window_shadow: Shadow,
 
+    /// Painted over everything behind a modal [`Window`](crate::Window) or [`Area`](crate::Area).
+    pub modal_backdrop_color: Color32,
+
     pub resize_corner_size: f32,
 
     pub text_cursor_width: f32,
@@ -279,10 +288,13 @@ impl Default for Visuals {
             override_text_color: None,
             widgets: Default::default(),
             selection: Default::default(),
+            focus_stroke: Stroke::new(1.5, Color32::from_rgb(255, 200, 60)),
             dark_bg_color: Color32::black_alpha(140),
+            faint_bg_color: Color32::additive_luminance(8),
             hyperlink_color: Color32::from_rgb(90, 170, 255),
             window_corner_radius: 10.0,
             window_shadow: Shadow::big(),
+            modal_backdrop_color: Color32::black_alpha(100),
             resize_corner_size: 12.0,
             text_cursor_width: 2.0,
             clip_rect_margin: 1.0, // should be half the size of the widest frame stroke
@@ -473,10 +485,13 @@ impl Visuals {
             override_text_color: _,
             widgets,
             selection,
+            focus_stroke,
             dark_bg_color,
+            faint_bg_color,
             hyperlink_color,
             window_corner_radius,
             window_shadow,
+            modal_backdrop_color,
             resize_corner_size,
             text_cursor_width,
             clip_rect_margin,
@@ -487,10 +502,13 @@ impl Visuals {
 
         ui.collapsing("widgets", |ui| widgets.ui(ui));
         ui.collapsing("selection", |ui| selection.ui(ui));
+        focus_stroke.ui(ui, "focus_stroke");
         ui_color(ui, dark_bg_color, "dark_bg_color");
+        ui_color(ui, faint_bg_color, "faint_bg_color");
         ui_color(ui, hyperlink_color, "hyperlink_color");
         ui.add(Slider::f32(window_corner_radius, 0.0..=20.0).text("window_corner_radius"));
         window_shadow.ui(ui, "Window shadow:");
+        ui_color(ui, modal_backdrop_color, "modal_backdrop_color");
         ui.add(Slider::f32(resize_corner_size, 0.0..=20.0).text("resize_corner_size"));
         ui.add(Slider::f32(text_cursor_width, 0.0..=2.0).text("text_cursor_width"));
         ui.add(Slider::f32(clip_rect_margin, 0.0..=20.0).text("clip_rect_margin"));
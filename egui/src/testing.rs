@@ -0,0 +1,285 @@
+//! Run your UI without a GPU, drive it with scripted input and check what happened.
+//!
+//! ```
+//! use egui::testing::Harness;
+//!
+//! let mut saved = false;
+//! let mut name = String::new();
+//! let mut app = |ctx: &egui::CtxRef| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.text_edit_singleline(&mut name);
+//!         if ui.button("Save").clicked {
+//!             saved = true;
+//!         }
+//!     });
+//! };
+//!
+//! let mut harness = Harness::new(egui::vec2(400.0, 300.0));
+//! harness.run(&mut app); // Lay out the widgets so we know where they are.
+//! harness.click("Save", &mut app);
+//! drop(app);
+//! assert!(saved);
+//! ```
+
+use crate::{
+    paint::{
+        rasterizer::{ColorImage, Rasterizer},
+        PaintJobs,
+    },
+    *,
+};
+
+/// Feeds a [`CtxRef`] with scripted [`RawInput`] and records the widgets laid out each frame.
+///
+/// All interaction methods take your app as a closure, `FnMut(&CtxRef)`,
+/// which should show your whole UI (panels, windows, …) once each time it is called.
+pub struct Harness {
+    ctx: CtxRef,
+    /// Input for the next frame.
+    input: RawInput,
+    /// Time of the next frame, in seconds.
+    time: f64,
+    /// The widgets that were laid out the last frame.
+    widgets: Vec<WidgetRecord>,
+    /// The output of the last frame.
+    output: Output,
+    /// What was painted the last frame.
+    paint_jobs: PaintJobs,
+    rasterizer: Rasterizer,
+}
+
+impl Harness {
+    /// Create a new harness with a screen of the given size (in points).
+    pub fn new(screen_size: Vec2) -> Self {
+        let ctx = CtxRef::default();
+        ctx.memory().options.record_widgets = true;
+        Self {
+            ctx,
+            input: RawInput {
+                screen_rect: Some(Rect::from_min_size(Default::default(), screen_size)),
+                pixels_per_point: Some(1.0),
+                ..Default::default()
+            },
+            time: 0.0,
+            widgets: vec![],
+            output: Default::default(),
+            paint_jobs: Default::default(),
+            rasterizer: Default::default(),
+        }
+    }
+
+    /// The context that is being driven.
+    pub fn ctx(&self) -> &CtxRef {
+        &self.ctx
+    }
+
+    /// Run one frame with the input queued up so far,
+    /// then clear all the events and deltas of that input.
+    ///
+    /// Advances the time by [`RawInput::predicted_dt`].
+    pub fn run(&mut self, mut app: impl FnMut(&CtxRef)) -> &Output {
+        self.input.time = Some(self.time);
+        self.ctx.begin_frame(self.input.take());
+        app(&self.ctx);
+        let (output, paint_commands) = self.ctx.end_frame();
+        self.paint_jobs = self.ctx.tessellate(paint_commands);
+        self.widgets = self.ctx.frame_state().widgets.clone();
+        self.output = output;
+        self.time += self.input.predicted_dt as f64;
+        &self.output
+    }
+
+    /// Run frames until egui no longer asks for a repaint (e.g. after an animation),
+    /// but at most `max_frames`.
+    pub fn run_until_settled(&mut self, mut app: impl FnMut(&CtxRef), max_frames: usize) {
+        for _ in 0..max_frames {
+            if !self.run(&mut app).needs_repaint {
+                break;
+            }
+        }
+    }
+
+    /// Let some time pass before the next frame.
+    pub fn advance_time(&mut self, seconds: f64) {
+        self.time += seconds;
+    }
+
+    /// Move the mouse to this position before the next frame.
+    pub fn move_mouse_to(&mut self, pos: Pos2) {
+        self.input.mouse_pos = Some(pos);
+    }
+
+    /// Simulate the mouse leaving the window.
+    pub fn mouse_leave(&mut self) {
+        self.input.mouse_pos = None;
+    }
+
+    /// Press or release the primary mouse button before the next frame.
+    pub fn set_mouse_down(&mut self, down: bool) {
+        self.set_button_down(MouseButton::Primary, down);
+    }
+
+    /// Press or release the given mouse button before the next frame.
+    pub fn set_button_down(&mut self, button: MouseButton, down: bool) {
+        self.input.mouse_down[button as usize] = down;
+    }
+
+    /// Open (`Some`) or close (`None`) the native window of a [`crate::viewport::Viewport`].
+    pub fn set_viewport(
+        &mut self,
+        viewport_id: crate::viewport::ViewportId,
+        info: Option<crate::viewport::ViewportInfo>,
+    ) {
+        match info {
+            Some(info) => self.input.viewports.insert(viewport_id, info),
+            None => self.input.viewports.remove(&viewport_id),
+        };
+    }
+
+    /// Move the mouse into another viewport before the next frame.
+    /// Mouse positions are relative to the viewport the mouse is in.
+    pub fn set_mouse_viewport(&mut self, viewport_id: crate::viewport::ViewportId) {
+        self.input.viewport_id = viewport_id;
+    }
+
+    /// Set the modifier keys (ctrl, shift, …) held down from the next frame on.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.input.modifiers = modifiers;
+    }
+
+    /// Scroll by this many points the next frame.
+    pub fn scroll(&mut self, delta: Vec2) {
+        self.input.scroll_delta += delta;
+    }
+
+    /// Queue up an event for the next frame.
+    pub fn event(&mut self, event: Event) {
+        self.input.events.push(event);
+    }
+
+    /// Type some text the next frame (into whatever has keyboard focus).
+    pub fn type_text(&mut self, text: impl Into<String>) {
+        self.event(Event::Text(text.into()));
+    }
+
+    /// Put a finger on the touch screen, move it or lift it the next frame.
+    ///
+    /// This does not move the mouse, unlike most integrations.
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, pos: Pos2) {
+        self.event(Event::Touch {
+            id: TouchId(id),
+            phase,
+            pos,
+            force: None,
+        });
+    }
+
+    /// Press and release a key the next frame.
+    pub fn press_key(&mut self, key: Key) {
+        let modifiers = self.input.modifiers;
+        self.event(Event::Key {
+            key,
+            pressed: true,
+            modifiers,
+        });
+        self.event(Event::Key {
+            key,
+            pressed: false,
+            modifiers,
+        });
+    }
+
+    /// Click at the given position. Runs two frames: one pressing the mouse button, one releasing it.
+    pub fn click_at(&mut self, pos: Pos2, app: impl FnMut(&CtxRef)) {
+        self.click_button_at(MouseButton::Primary, pos, app);
+    }
+
+    /// Like [`Self::click_at`], but with any mouse button.
+    pub fn click_button_at(
+        &mut self,
+        button: MouseButton,
+        pos: Pos2,
+        mut app: impl FnMut(&CtxRef),
+    ) {
+        self.move_mouse_to(pos);
+        self.set_button_down(button, true);
+        self.run(&mut app);
+        self.set_button_down(button, false);
+        self.run(&mut app);
+    }
+
+    /// Click the center of the first widget with this label (as laid out last frame).
+    ///
+    /// Panics if there is no such widget.
+    pub fn click(&mut self, label: &str, app: impl FnMut(&CtxRef)) {
+        let pos = self.expect_widget(label).rect.center();
+        self.click_at(pos, app);
+    }
+
+    /// Click the center of the first widget with this label with the secondary mouse button,
+    /// e.g. to open a context menu.
+    ///
+    /// Panics if there is no such widget.
+    pub fn secondary_click(&mut self, label: &str, app: impl FnMut(&CtxRef)) {
+        let pos = self.expect_widget(label).rect.center();
+        self.click_button_at(MouseButton::Secondary, pos, app);
+    }
+
+    /// Hover the mouse over the first widget with this label (as laid out last frame)
+    /// and run one frame.
+    ///
+    /// Panics if there is no such widget.
+    pub fn hover(&mut self, label: &str, app: impl FnMut(&CtxRef)) {
+        let pos = self.expect_widget(label).rect.center();
+        self.move_mouse_to(pos);
+        self.run(app);
+    }
+
+    /// The output of the last frame.
+    pub fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Paint the last frame on the CPU, on top of the given background color.
+    ///
+    /// Useful for screenshot tests.
+    pub fn screenshot(&mut self, clear_color: impl Into<Rgba>) -> ColorImage {
+        let pixels_per_point = self.ctx.pixels_per_point();
+        let size = self.ctx.input().screen_rect().size() * pixels_per_point;
+        self.rasterizer.paint_jobs(
+            [size.x.round() as usize, size.y.round() as usize],
+            pixels_per_point,
+            clear_color.into(),
+            &self.paint_jobs,
+            &self.ctx.texture(),
+        )
+    }
+
+    /// All widgets (and other interactive areas) laid out the last frame, in the order they were added.
+    pub fn widgets(&self) -> &[WidgetRecord] {
+        &self.widgets
+    }
+
+    /// The first widget with this label laid out the last frame (if any).
+    pub fn widget(&self, label: &str) -> Option<&WidgetRecord> {
+        self.widgets.iter().find(|w| w.label() == Some(label))
+    }
+
+    /// Like [`Self::widget`], but panics with a helpful message if there is no such widget.
+    pub fn expect_widget(&self, label: &str) -> &WidgetRecord {
+        self.widget(label).unwrap_or_else(|| {
+            let labels: Vec<&str> = self.widgets.iter().filter_map(|w| w.label()).collect();
+            panic!(
+                "No widget labeled {:?} last frame. Labels found: {:?}",
+                label, labels
+            )
+        })
+    }
+
+    /// All widgets of the given type laid out the last frame.
+    pub fn widgets_of_type(&self, typ: WidgetType) -> impl Iterator<Item = &WidgetRecord> {
+        self.widgets
+            .iter()
+            .filter(move |w| w.info.as_ref().map(|info| info.typ) == Some(typ))
+    }
+}
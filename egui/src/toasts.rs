@@ -0,0 +1,279 @@
+//! Toasts: short notifications such as "Saved" or "Export failed",
+//! shown in a corner of the screen for a few seconds.
+//!
+//! ```
+//! # let ctx = egui::CtxRef::default();
+//! use egui::toasts::Toast;
+//!
+//! ctx.toast(Toast::success("Saved"));
+//! ctx.toast(Toast::error("Export failed: disk full").duration(10.0));
+//! ```
+
+use crate::{align::Align2, paint::TextStyle, *};
+
+/// How wide each toast is (excluding the frame margins).
+const TOAST_WIDTH: f32 = 240.0;
+
+/// What kind of message a [`Toast`] has, which decides its icon and color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn icon(self) -> &'static str {
+        match self {
+            ToastKind::Info => "ℹ",
+            ToastKind::Success => "✔",
+            ToastKind::Warning => "⚠",
+            ToastKind::Error => "❗",
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            ToastKind::Info => Color32::from_rgb(90, 170, 255),
+            ToastKind::Success => Color32::from_rgb(100, 200, 100),
+            ToastKind::Warning => Color32::from_rgb(255, 200, 60),
+            ToastKind::Error => Color32::from_rgb(255, 90, 90),
+        }
+    }
+}
+
+/// A notification to show with [`Context::toast`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    kind: ToastKind,
+    text: String,
+    duration: f32,
+}
+
+impl Toast {
+    /// Shown for four seconds by default.
+    pub fn new(kind: ToastKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            duration: 4.0,
+        }
+    }
+
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Info, text)
+    }
+
+    pub fn success(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Success, text)
+    }
+
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Warning, text)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Error, text)
+    }
+
+    /// For how many seconds to show the toast (not counting the time the mouse is hovering it).
+    /// The user can always close it early with its close button.
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = seconds;
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct ActiveToast {
+    id: Id,
+    toast: Toast,
+    time_left: f32,
+    /// Animating out.
+    closing: bool,
+}
+
+/// The toasts that are currently shown, kept in [`Memory`].
+#[derive(Clone, Debug)]
+pub(crate) struct Toasts {
+    anchor: Align2,
+    toasts: Vec<ActiveToast>,
+    next_id: u64,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            anchor: align::RIGHT_BOTTOM,
+            toasts: vec![],
+            next_id: 0,
+        }
+    }
+}
+
+impl Context {
+    /// Show a notification in a corner of the screen.
+    /// It disappears by itself after a while, but stays while the mouse is hovering it.
+    ///
+    /// See [`toasts`](crate::toasts) for an example.
+    pub fn toast(&self, toast: Toast) {
+        let mut memory = self.memory();
+        let toasts = &mut memory.toasts;
+        let id = Id::new("toasts").with(toasts.next_id);
+        toasts.next_id += 1;
+        toasts.toasts.push(ActiveToast {
+            id,
+            time_left: toast.duration,
+            toast,
+            closing: false,
+        });
+        drop(memory);
+
+        self.animate_bool(id, false); // So that it animates in
+        self.request_repaint();
+    }
+
+    /// In which corner (or at which edge) of the screen to show toasts. Default: bottom right.
+    pub fn set_toast_anchor(&self, anchor: Align2) {
+        self.memory().toasts.anchor = anchor;
+    }
+}
+
+/// Show all toasts. Called at the end of each frame.
+pub(crate) fn show_toasts(ctx: &CtxRef) {
+    let (anchor, mut toasts) = {
+        let memory = ctx.memory();
+        (memory.toasts.anchor, memory.toasts.toasts.clone())
+    };
+    if toasts.is_empty() {
+        return;
+    }
+    let num_shown = toasts.len();
+
+    // Place the stack of toasts in the corner, based on its size last frame:
+    let area = Area::new("toasts").order(Order::Foreground).movable(false);
+    let last_size = ctx
+        .memory()
+        .areas
+        .get(area.layer().id)
+        .map_or(Vec2::zero(), |state| state.size);
+    let margin = ctx.style().spacing.window_padding.x;
+    let available_rect = ctx.available_rect().shrink(margin);
+    let corner = pos2(
+        match anchor.0 {
+            Align::Min => available_rect.left(),
+            Align::Center => available_rect.center().x,
+            Align::Max => available_rect.right(),
+        },
+        match anchor.1 {
+            Align::Min => available_rect.top(),
+            Align::Center => available_rect.center().y,
+            Align::Max => available_rect.bottom(),
+        },
+    );
+    let pos = align::anchor_rect(Rect::from_min_size(corner, last_size), anchor).min;
+
+    // Toasts slide in from (and out to) the closest edge of the screen:
+    let slide_direction = match anchor.0 {
+        Align::Min => -1.0,
+        Align::Center => 0.0,
+        Align::Max => 1.0,
+    };
+
+    let dt = ctx.input().unstable_dt.at_most(0.1);
+    area.fixed_pos(pos).show(ctx, |ui| {
+        for toast in &mut toasts {
+            let how_shown = ui.ctx().animate_bool(toast.id, !toast.closing);
+            let offset = slide_direction * (1.0 - how_shown) * (TOAST_WIDTH + 2.0 * margin);
+            let max_rect = ui.available_rect_before_wrap().translate(vec2(offset, 0.0));
+            let mut toast_ui = ui.child_ui(max_rect, *ui.layout());
+
+            let mut close_clicked = false;
+            Frame::popup(ui.style()).show(&mut toast_ui, |ui| {
+                ui.set_min_width(TOAST_WIDTH);
+                ui.set_max_width(TOAST_WIDTH);
+                let layout = Layout::right_to_left().with_cross_align(Align::Min);
+                ui.with_layout(layout, |ui| {
+                    close_clicked = ui
+                        .add(Button::new("🗙").frame(false).text_style(TextStyle::Small))
+                        .clicked;
+                    let layout = Layout::left_to_right().with_cross_align(Align::Min);
+                    ui.with_layout(layout, |ui| {
+                        let kind = toast.toast.kind;
+                        ui.add(Label::new(kind.icon()).text_color(kind.color()));
+                        ui.add(Label::new(&toast.toast.text).multiline(true));
+                    });
+                });
+            });
+
+            let rect = toast_ui.min_rect();
+            let response = ui.interact(rect, toast.id, Sense::hover());
+            if close_clicked || (toast.time_left <= 0.0 && !toast.closing) {
+                toast.closing = true;
+            } else if !response.hovered {
+                toast.time_left -= dt;
+            }
+
+            // Collapse the space while animating, so that the others move smoothly:
+            ui.allocate_space(vec2(rect.width(), how_shown * rect.height()));
+        }
+    });
+
+    // Forget the toasts that are done animating out:
+    toasts.retain(|toast| !toast.closing || ctx.animate_bool(toast.id, false) > 0.0);
+
+    // Keep any toasts that were added while we were showing the others:
+    let mut memory = ctx.memory();
+    let added: Vec<_> = memory.toasts.toasts.drain(num_shown..).collect();
+    memory.toasts.toasts = toasts;
+    memory.toasts.toasts.extend(added);
+    drop(memory);
+
+    ctx.request_repaint(); // For the timeouts and animations
+}
+
+#[test]
+fn test_toasts() {
+    let mut harness = testing::Harness::new(vec2(600.0, 400.0));
+    let app = |_ctx: &CtxRef| {};
+
+    harness.ctx().toast(Toast::success("Saved").duration(1.0));
+    harness
+        .ctx()
+        .toast(Toast::error("Export failed").duration(60.0));
+    for _ in 0..10 {
+        harness.run(app); // Animate in
+    }
+    let saved = harness.expect_widget("Saved").rect;
+    let close_button = harness.expect_widget("🗙").rect;
+    assert!(saved.left() > 300.0 && saved.bottom() > 300.0);
+    assert!(close_button.right() < 600.0);
+    assert!(harness.widget("Export failed").is_some());
+
+    // Hovering pauses the timeout:
+    harness.move_mouse_to(saved.center());
+    for _ in 0..10 {
+        harness.advance_time(0.2);
+        harness.run(app);
+    }
+    assert!(harness.widget("Saved").is_some());
+
+    harness.mouse_leave();
+    for _ in 0..20 {
+        harness.advance_time(0.2);
+        harness.run(app);
+    }
+    assert!(harness.widget("Saved").is_none());
+    assert!(harness.widget("Export failed").is_some());
+
+    // Close with the close button:
+    harness.click("🗙", app);
+    for _ in 0..10 {
+        harness.advance_time(0.2);
+        harness.run(app);
+    }
+    assert!(harness.widget("Export failed").is_none());
+}
@@ -1,4 +1,6 @@
-use crate::{lerp, math::Rect, Align, CtxRef, Id, LayerId, Ui};
+use std::{any::Any, sync::Arc};
+
+use crate::{lerp, math::Rect, memory::DragAndDropPayload, Align, CtxRef, Id, LayerId, Ui};
 
 // ----------------------------------------------------------------------------
 
@@ -20,6 +22,18 @@ pub struct Output {
     /// This happens for instance when there is an animation, or if a user has called `Context::request_repaint()`.
     /// Don't set this manually, but call `Context::request_repaint()` instead.
     pub needs_repaint: bool,
+
+    /// Describes the widgets of this frame for screen readers.
+    /// Only set if turned on with [`crate::Context::set_accessibility`].
+    pub accessibility_tree: Option<crate::accessibility::AccessibilityTree>,
+
+    /// Where the text cursor of the focused [`crate::TextEdit`] is in the root viewport, in points.
+    /// Integrations should show the IME candidate window (for e.g. Chinese and Japanese text entry) next to it.
+    pub text_cursor_rect: Option<Rect>,
+
+    /// The viewports (native windows) shown this frame, except for the root one.
+    /// See [`crate::viewport`].
+    pub viewports: Vec<crate::viewport::ViewportOutput>,
 }
 
 /// A mouse cursor icon.
@@ -83,6 +97,12 @@ pub struct Response {
     /// The thing was double-clicked.
     pub double_clicked: bool,
 
+    /// The thing was clicked with the secondary mouse button (usually the right one) this frame.
+    pub secondary_clicked: bool,
+
+    /// The thing was clicked with the middle mouse button this frame.
+    pub middle_clicked: bool,
+
     /// The mouse is interacting with this thing (e.g. dragging it).
     pub active: bool,
 
@@ -103,6 +123,14 @@ pub struct Response {
     /// }
     /// ```
     pub lost_kb_focus: bool,
+
+    /// The widget changed the value it is editing this frame,
+    /// e.g. the user typed into a [`crate::TextEdit`], dragged a [`crate::Slider`] or [`crate::DragValue`],
+    /// or clicked a [`crate::Checkbox`], `ui.radio_value` or `ui.selectable_value`.
+    ///
+    /// A [`crate::combo_box`] doesn't know what its contents edit,
+    /// so look at the responses of the widgets inside it instead.
+    pub changed: bool,
 }
 
 impl std::fmt::Debug for Response {
@@ -116,9 +144,12 @@ impl std::fmt::Debug for Response {
             hovered,
             clicked,
             double_clicked,
+            secondary_clicked,
+            middle_clicked,
             active,
             has_kb_focus,
             lost_kb_focus,
+            changed,
         } = self;
         f.debug_struct("Response")
             .field("layer_id", layer_id)
@@ -128,9 +159,12 @@ impl std::fmt::Debug for Response {
             .field("hovered", hovered)
             .field("clicked", clicked)
             .field("double_clicked", double_clicked)
+            .field("secondary_clicked", secondary_clicked)
+            .field("middle_clicked", middle_clicked)
             .field("active", active)
             .field("has_kb_focus", has_kb_focus)
             .field("lost_kb_focus", lost_kb_focus)
+            .field("changed", changed)
             .finish()
     }
 }
@@ -172,6 +206,43 @@ impl Response {
             .interact_with_hovered(self.layer_id, self.id, self.rect, sense, self.hovered)
     }
 
+    /// Describe what kind of widget this is, and what it shows.
+    ///
+    /// Widgets call this so that tests (see [`crate::testing`]) can find them by their label.
+    /// `make_info` is only called if widgets are being recorded, so it is fine for it to allocate.
+    pub fn widget_info(&self, make_info: impl FnOnce() -> WidgetInfo) {
+        if self.ctx.is_recording_widgets() {
+            let info = make_info();
+            let mut frame_state = self.ctx.frame_state();
+            if let Some(record) = frame_state
+                .widgets
+                .iter_mut()
+                .rev()
+                .find(|w| w.id == self.id)
+            {
+                record.info = Some(info);
+            }
+        }
+    }
+
+    /// Show a context menu at the mouse position when this is clicked with the secondary mouse button
+    /// (usually the right one).
+    ///
+    /// The menu closes when something is clicked or Escape is pressed.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// ui.label("Right-click me").context_menu(|ui| {
+    ///     if ui.button("Copy").clicked {
+    ///         // …
+    ///     }
+    /// });
+    /// ```
+    pub fn context_menu(self, add_contents: impl FnOnce(&mut Ui)) -> Self {
+        crate::menu::context_menu(&self, add_contents);
+        self
+    }
+
     /// Move the scroll to this UI with the specified alignment.
     ///
     /// ```
@@ -187,8 +258,59 @@ impl Response {
     /// });
     /// ```
     pub fn scroll_to_me(&self, align: Align) {
-        let scroll_target = lerp(self.rect.y_range(), align.scroll_center_factor());
-        self.ctx.frame_state().scroll_target = Some((scroll_target, align));
+        let center_factor = align.scroll_center_factor();
+        self.ctx.frame_state().scroll_target = [
+            Some((lerp(self.rect.x_range(), center_factor), align)),
+            Some((lerp(self.rect.y_range(), center_factor), align)),
+        ];
+    }
+
+    /// If this widget is being dragged, let `payload` come along so that
+    /// a drop target can pick it up with [`Self::dnd_release_payload`].
+    ///
+    /// The payload is kept in [`crate::Memory`] until the mouse button is released.
+    /// See also [`Ui::dnd_drag_source`], which also shows the dragged contents under the cursor.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// let response = ui.label("Drag me").interact(egui::Sense::drag());
+    /// response.dnd_set_payload(42_u32);
+    /// ```
+    pub fn dnd_set_payload<T: Any + Send + Sync>(&self, payload: T) {
+        let is_dragged = self.ctx.memory().is_being_dragged(self.id);
+        // Don't pick anything up until the mouse has moved, so that clicks stay clicks:
+        if is_dragged && !self.ctx.input().mouse.could_be_click {
+            self.ctx.memory().dnd_payload = Some(DragAndDropPayload(Arc::new(payload)));
+        }
+    }
+
+    /// A payload of type `T` is being dragged over this widget.
+    pub fn dnd_hover_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let payload = self.ctx.memory().dnd_payload::<T>()?;
+        if self.ctx.rect_contains_mouse(self.layer_id, self.rect) {
+            Some(payload)
+        } else {
+            None
+        }
+    }
+
+    /// A payload of type `T` was dropped on this widget this frame.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// let response = ui.label("Drop numbers here");
+    /// if let Some(number) = response.dnd_release_payload::<u32>() {
+    ///     // …
+    /// }
+    /// ```
+    pub fn dnd_release_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let payload = self.dnd_hover_payload()?;
+        if self.ctx.input().mouse.released {
+            self.ctx.memory().dnd_payload = None;
+            Some(payload)
+        } else {
+            None
+        }
     }
 }
 
@@ -210,9 +332,12 @@ impl Response {
             hovered: self.hovered || other.hovered,
             clicked: self.clicked || other.clicked,
             double_clicked: self.double_clicked || other.double_clicked,
+            secondary_clicked: self.secondary_clicked || other.secondary_clicked,
+            middle_clicked: self.middle_clicked || other.middle_clicked,
             active: self.active || other.active,
             has_kb_focus: self.has_kb_focus || other.has_kb_focus,
             lost_kb_focus: self.lost_kb_focus || other.lost_kb_focus,
+            changed: self.changed || other.changed,
         }
     }
 }
@@ -252,6 +377,131 @@ impl std::ops::BitOrAssign for Response {
 
 // ----------------------------------------------------------------------------
 
+/// What kind of widget is it?
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WidgetType {
+    Label,
+    Hyperlink,
+    TextEdit,
+    Button,
+    Checkbox,
+    RadioButton,
+    SelectableLabel,
+    ComboBox,
+    Slider,
+    DragValue,
+    ColorButton,
+    ImageButton,
+    CollapsingHeader,
+    /// A node in a [`TreeView`](crate::TreeView).
+    TreeNode,
+    /// An item in a [`menu`](crate::menu), or a button opening a submenu.
+    MenuItem,
+    Window,
+    /// A label with [`TextStyle::Heading`](crate::TextStyle::Heading).
+    Heading,
+    /// Anything else, e.g. a custom widget.
+    Other,
+}
+
+/// Describes a widget, e.g. so that a test can find it and check its state.
+///
+/// Set with [`Response::widget_info`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetInfo {
+    /// The type of widget this is.
+    pub typ: WidgetType,
+    /// The text on the widget, e.g. the text of a button or label.
+    pub label: Option<String>,
+    /// The contents of a text field.
+    pub text_value: Option<String>,
+    /// The value of a slider or `DragValue`.
+    pub value: Option<f64>,
+    /// Is the checkbox checked, or the radio button or selectable label selected?
+    pub selected: Option<bool>,
+    /// Is the collapsing header, combo box or window open?
+    pub expanded: Option<bool>,
+}
+
+impl WidgetInfo {
+    pub fn new(typ: WidgetType) -> Self {
+        Self {
+            typ,
+            label: None,
+            text_value: None,
+            value: None,
+            selected: None,
+            expanded: None,
+        }
+    }
+
+    pub fn labeled(typ: WidgetType, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..Self::new(typ)
+        }
+    }
+
+    /// Checkboxes, radio buttons and selectable labels.
+    pub fn selected(typ: WidgetType, selected: bool, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            selected: Some(selected),
+            ..Self::new(typ)
+        }
+    }
+
+    pub fn drag_value(value: f64) -> Self {
+        Self {
+            value: Some(value),
+            ..Self::new(WidgetType::DragValue)
+        }
+    }
+
+    pub fn slider(value: f64, label: impl Into<String>) -> Self {
+        let label: String = label.into();
+        Self {
+            label: if label.is_empty() { None } else { Some(label) },
+            value: Some(value),
+            ..Self::new(WidgetType::Slider)
+        }
+    }
+
+    pub fn text_edit(text_value: impl Into<String>) -> Self {
+        Self {
+            text_value: Some(text_value.into()),
+            ..Self::new(WidgetType::TextEdit)
+        }
+    }
+}
+
+/// A widget (or other interactive area) that was laid out during a frame.
+///
+/// Only recorded by tools that ask for it, like [`crate::testing::Harness`]
+/// and the [`crate::accessibility`] tree.
+#[derive(Clone, Debug)]
+pub struct WidgetRecord {
+    pub id: Id,
+    pub layer_id: LayerId,
+    pub rect: Rect,
+    pub sense: Sense,
+    pub hovered: bool,
+    pub clicked: bool,
+    pub active: bool,
+    pub has_kb_focus: bool,
+    /// Set by the widget with [`Response::widget_info`].
+    pub info: Option<WidgetInfo>,
+}
+
+impl WidgetRecord {
+    /// The label of the widget, if it has one.
+    pub fn label(&self) -> Option<&str> {
+        self.info.as_ref()?.label.as_deref()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// What sort of interaction is a widget sensitive to?
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 // #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -261,6 +511,14 @@ pub struct Sense {
 
     /// sliders, windows, scroll bars, scroll areas ...
     pub drag: bool,
+
+    /// Can the widget be given keyboard focus, e.g. with Tab?
+    /// Buttons, sliders, text edits ...
+    pub focusable: bool,
+
+    /// Does the widget take text input when it has keyboard focus?
+    /// If so, Space and Enter are typed into it instead of clicking it.
+    pub text_input: bool,
 }
 
 impl Sense {
@@ -269,6 +527,8 @@ impl Sense {
         Self {
             click: false,
             drag: false,
+            focusable: false,
+            text_input: false,
         }
     }
 
@@ -277,27 +537,52 @@ impl Sense {
         Sense::hover()
     }
 
-    /// Sense clicks and hover, but not drags.
+    /// Sense clicks and hover, but not drags. Can be given keyboard focus.
     pub fn click() -> Self {
         Self {
             click: true,
             drag: false,
+            focusable: true,
+            text_input: false,
         }
     }
 
-    /// Sense drags and hover, but not clicks.
+    /// Sense drags and hover, but not clicks. Can not be given keyboard focus.
     pub fn drag() -> Self {
         Self {
             click: false,
             drag: true,
+            focusable: false,
+            text_input: false,
         }
     }
 
-    /// Sense both clicks, drags and hover (e.g. a slider or window).
+    /// Sense both clicks, drags and hover (e.g. a slider or window). Can be given keyboard focus.
     pub fn click_and_drag() -> Self {
         Self {
             click: true,
             drag: true,
+            focusable: true,
+            text_input: false,
+        }
+    }
+
+    /// Don't allow giving this keyboard focus,
+    /// e.g. for windows and scroll bars that can't do anything useful with it.
+    #[must_use]
+    pub fn not_focusable(self) -> Self {
+        Self {
+            focusable: false,
+            ..self
+        }
+    }
+
+    /// Mark this as a widget that takes text input, like a `TextEdit`.
+    #[must_use]
+    pub fn text_input(self) -> Self {
+        Self {
+            text_input: true,
+            ..self
         }
     }
 
@@ -307,6 +592,8 @@ impl Sense {
         Self {
             click: self.click | other.click,
             drag: self.drag | other.drag,
+            focusable: self.focusable | other.focusable,
+            text_input: self.text_input | other.text_input,
         }
     }
 }
@@ -1,6 +1,6 @@
 // #![warn(missing_docs)]
 
-use std::{hash::Hash, sync::Arc};
+use std::{any::Any, hash::Hash, sync::Arc};
 
 use crate::{color::*, containers::*, layout::*, mutex::MutexGuard, paint::*, widgets::*, *};
 
@@ -46,6 +46,12 @@ pub struct Ui {
 
     /// Sizes/bounds and cursor used by `Layout`.
     region: Region,
+
+    /// If set, widgets are placed in the cells of a [`Grid`] instead of following `layout`.
+    grid: Option<grid::GridLayout>,
+
+    /// If set, this `Ui` is the inside of a menu (or of a `Ui` in a menu).
+    menu: Option<menu::MenuScope>,
 }
 
 impl Ui {
@@ -63,6 +69,8 @@ impl Ui {
             style,
             layout,
             region,
+            grid: None,
+            menu: None,
         }
     }
 
@@ -77,9 +85,26 @@ impl Ui {
             style: self.style.clone(),
             layout,
             region,
+            grid: None,
+            menu: self.menu,
         }
     }
 
+    /// Like [`Self::child_ui`], but the `Id` of the child (and so the automatic `Id`s of the widgets in it)
+    /// comes from `id_source` instead of from how many widgets were added before it.
+    pub(crate) fn child_ui_with_id_source(
+        &mut self,
+        max_rect: Rect,
+        layout: Layout,
+        id_source: impl Hash,
+    ) -> Self {
+        let id = self.id.with(id_source);
+        let mut child_ui = self.child_ui(max_rect, layout);
+        child_ui.id = id;
+        child_ui.next_auto_id = id.with("auto").value();
+        child_ui
+    }
+
     /// Empty `Ui` for use in tests.
     pub fn __test() -> Self {
         let mut ctx = CtxRef::default();
@@ -340,7 +365,11 @@ impl Ui {
     /// A small size should be interpreted as "as little as possible".
     /// An infinite size should be interpreted as "as much as you want".
     pub fn available_size(&self) -> Vec2 {
-        self.layout.available_size(&self.region)
+        if let Some(grid) = &self.grid {
+            grid.available_rect(&self.region).size()
+        } else {
+            self.layout.available_size(&self.region)
+        }
     }
 
     pub fn available_width(&self) -> f32 {
@@ -349,27 +378,33 @@ impl Ui {
 
     /// In case of a wrapping layout, how much space is left on this row/column?
     pub fn available_size_before_wrap(&self) -> Vec2 {
-        self.layout.available_size_before_wrap(&self.region)
+        self.available_rect_before_wrap().size()
     }
 
     /// This is like `available_size_before_wrap()`, but will never be infinite.
     /// Use this for components that want to grow without bounds (but shouldn't).
     /// In most layouts the next widget will be put in the top left corner of this `Rect`.
     pub fn available_size_before_wrap_finite(&self) -> Vec2 {
-        self.layout
-            .available_rect_before_wrap_finite(&self.region)
-            .size()
+        self.available_rect_before_wrap_finite().size()
     }
 
     pub fn available_rect_before_wrap(&self) -> Rect {
-        self.layout.available_rect_before_wrap(&self.region)
+        if let Some(grid) = &self.grid {
+            grid.available_rect(&self.region)
+        } else {
+            self.layout.available_rect_before_wrap(&self.region)
+        }
     }
 
     /// This is like `available_rect_before_wrap()`, but will never be infinite.
     /// Use this for components that want to grow without bounds (but shouldn't).
     /// In most layouts the next widget will be put in the top left corner of this `Rect`.
     pub fn available_rect_before_wrap_finite(&self) -> Rect {
-        self.layout.available_rect_before_wrap_finite(&self.region)
+        if let Some(grid) = &self.grid {
+            grid.available_rect_finite(&self.region)
+        } else {
+            self.layout.available_rect_before_wrap_finite(&self.region)
+        }
     }
 }
 
@@ -388,6 +423,11 @@ impl Ui {
         Id::new(self.next_auto_id)
     }
 
+    /// The `Id` that the next call to [`Self::allocate_space`] will return.
+    pub(crate) fn next_auto_id(&self) -> Id {
+        Id::new(self.next_auto_id.wrapping_add(1))
+    }
+
     pub(crate) fn auto_id_with<IdSource>(&self, id_source: IdSource) -> Id
     where
         IdSource: Hash + std::fmt::Debug,
@@ -534,33 +574,57 @@ impl Ui {
     /// Reserve this much space and move the cursor.
     /// Returns where to put the widget.
     fn allocate_space_impl(&mut self, desired_size: Vec2) -> Rect {
-        let item_spacing = self.style().spacing.item_spacing;
-        let outer_child_rect = self
-            .layout
-            .next_space(&self.region, desired_size, item_spacing);
-        let inner_child_rect = self.layout.justify_or_align(outer_child_rect, desired_size);
-
-        self.layout.advance_after_outer_rect(
-            &mut self.region,
-            outer_child_rect,
-            inner_child_rect,
-            item_spacing,
-        );
-        self.region.expand_to_include_rect(inner_child_rect);
-
+        let outer_child_rect = self.next_space(desired_size);
+        let inner_child_rect = self.justify_or_align(outer_child_rect, desired_size);
+        self.advance_after_rects(outer_child_rect, inner_child_rect);
         inner_child_rect
     }
 
     pub(crate) fn advance_cursor_after_rect(&mut self, rect: Rect) -> Id {
-        let item_spacing = self.style().spacing.item_spacing;
-        self.layout
-            .advance_after_outer_rect(&mut self.region, rect, rect, item_spacing);
-        self.region.expand_to_include_rect(rect);
+        self.advance_after_rects(rect, rect);
 
         self.next_auto_id = self.next_auto_id.wrapping_add(1);
         Id::new(self.next_auto_id)
     }
 
+    /// Where to put the next widget of the given size: the next grid cell, or wherever the `Layout` says.
+    /// This is what you then pass to `advance_after_rects`.
+    fn next_space(&self, desired_size: Vec2) -> Rect {
+        if let Some(grid) = &self.grid {
+            grid.next_cell(self.region.cursor, desired_size)
+        } else {
+            let item_spacing = self.style().spacing.item_spacing;
+            self.layout
+                .next_space(&self.region, desired_size, item_spacing)
+        }
+    }
+
+    /// Apply justify or alignment after calling `next_space`.
+    fn justify_or_align(&self, outer_rect: Rect, desired_size: Vec2) -> Rect {
+        if let Some(grid) = &self.grid {
+            grid.align_size_within_rect(desired_size, outer_rect)
+        } else {
+            self.layout.justify_or_align(outer_rect, desired_size)
+        }
+    }
+
+    /// Advance the cursor (or move to the next grid cell) after a widget was added,
+    /// and make sure the `Ui` includes it.
+    fn advance_after_rects(&mut self, outer_rect: Rect, inner_rect: Rect) {
+        if let Some(grid) = &mut self.grid {
+            grid.advance(&mut self.region.cursor, outer_rect, inner_rect);
+        } else {
+            let item_spacing = self.style().spacing.item_spacing;
+            self.layout.advance_after_outer_rect(
+                &mut self.region,
+                outer_rect,
+                inner_rect,
+                item_spacing,
+            );
+        }
+        self.region.expand_to_include_rect(inner_rect);
+    }
+
     pub(crate) fn cursor(&self) -> Pos2 {
         self.region.cursor
     }
@@ -574,23 +638,14 @@ impl Ui {
         desired_size: Vec2,
         add_contents: impl FnOnce(&mut Self) -> R,
     ) -> (R, Response) {
-        let item_spacing = self.style().spacing.item_spacing;
-        let outer_child_rect = self
-            .layout
-            .next_space(&self.region, desired_size, item_spacing);
-        let inner_child_rect = self.layout.justify_or_align(outer_child_rect, desired_size);
+        let outer_child_rect = self.next_space(desired_size);
+        let inner_child_rect = self.justify_or_align(outer_child_rect, desired_size);
 
         let mut child_ui = self.child_ui(inner_child_rect, self.layout);
         let ret = add_contents(&mut child_ui);
         let final_child_rect = child_ui.region.min_rect;
 
-        self.layout.advance_after_outer_rect(
-            &mut self.region,
-            outer_child_rect.union(final_child_rect),
-            final_child_rect,
-            item_spacing,
-        );
-        self.region.expand_to_include_rect(final_child_rect);
+        self.advance_after_rects(outer_child_rect.union(final_child_rect), final_child_rect);
 
         let response = self.interact(final_child_rect, child_ui.id, Sense::hover());
         (ret, response)
@@ -621,9 +676,12 @@ impl Ui {
     /// });
     /// ```
     pub fn scroll_to_cursor(&mut self, align: Align) {
-        let scroll_y = self.region.cursor.y;
+        let cursor = self.region.cursor;
 
-        self.ctx().frame_state().scroll_target = Some((scroll_y, align));
+        let mut frame_state = self.ctx().frame_state();
+        for d in 0..2 {
+            frame_state.scroll_target[d] = Some((cursor[d], align));
+        }
     }
 }
 
@@ -732,9 +790,10 @@ impl Ui {
         selected_value: Value,
         text: impl Into<String>,
     ) -> Response {
-        let response = self.radio(*current_value == selected_value, text);
-        if response.clicked {
+        let mut response = self.radio(*current_value == selected_value, text);
+        if response.clicked && *current_value != selected_value {
             *current_value = selected_value;
+            response.changed = true;
         }
         response
     }
@@ -754,9 +813,10 @@ impl Ui {
         selected_value: Value,
         text: impl Into<String>,
     ) -> Response {
-        let response = self.selectable_label(*current_value == selected_value, text);
-        if response.clicked {
+        let mut response = self.selectable_label(*current_value == selected_value, text);
+        if response.clicked && *current_value != selected_value {
             *current_value = selected_value;
+            response.changed = true;
         }
         response
     }
@@ -890,6 +950,112 @@ impl Ui {
         })
     }
 
+    /// Make the contents draggable, carrying `payload` along to a [`Self::dnd_drop_zone`].
+    ///
+    /// While being dragged, the contents are painted on top of everything else and follow the mouse cursor.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// let id = egui::Id::new("item_7");
+    /// ui.dnd_drag_source(id, 7_usize, |ui| {
+    ///     ui.label("Item 7");
+    /// });
+    /// ```
+    pub fn dnd_drag_source<T: Any + Send + Sync, R>(
+        &mut self,
+        id: Id,
+        payload: T,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        let is_being_dragged =
+            self.memory().is_being_dragged(id) && !self.input().mouse.could_be_click;
+
+        let (ret, response) = if is_being_dragged {
+            self.output().cursor_icon = CursorIcon::Grabbing;
+
+            // Paint the contents to a layer on top of everything else:
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let (ret, response) = self.with_layer_id(layer_id, add_contents);
+
+            // …and move them to the mouse. Anything on the tooltip layer can't be interacted with,
+            // so it doesn't matter that the contents aren't where we laid them out.
+            if let Some(mouse_pos) = self.input().mouse.pos {
+                let delta = mouse_pos - response.rect.center();
+                self.ctx().translate_layer(layer_id, delta);
+            }
+            (ret, response)
+        } else {
+            self.wrap(add_contents)
+        };
+
+        let response = self.interact(response.rect, id, Sense::drag());
+        if response.hovered && !is_being_dragged {
+            self.output().cursor_icon = CursorIcon::Grab;
+        }
+        response.dnd_set_payload(payload);
+        (ret, response)
+    }
+
+    /// A region which accepts drag-and-drop payloads of type `T` (see [`Self::dnd_drag_source`]).
+    ///
+    /// The region is highlighted while a payload of type `T` is being dragged.
+    /// Returns the payload on the frame it is dropped here.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// let (_, _response, dropped) = ui.dnd_drop_zone::<usize, _>(|ui| {
+    ///     ui.label("Drop items here");
+    /// });
+    /// if let Some(item) = dropped {
+    ///     // move item `*item` here
+    /// }
+    /// ```
+    pub fn dnd_drop_zone<T: Any + Send + Sync, R>(
+        &mut self,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response, Option<Arc<T>>) {
+        let margin = Vec2::splat(4.0);
+
+        let outer_rect_bounds = self.available_rect_before_wrap();
+        let inner_rect = outer_rect_bounds.shrink2(margin);
+        let where_to_put_background = self.painter().add(PaintCmd::Noop);
+        let mut content_ui = self.child_ui(inner_rect, *self.layout());
+        let ret = add_contents(&mut content_ui);
+        let outer_rect =
+            Rect::from_min_max(outer_rect_bounds.min, content_ui.min_rect().max + margin);
+        let response = self.allocate_response(outer_rect.size(), Sense::hover());
+
+        let (is_anything_being_dragged, can_accept) = {
+            let memory = self.memory();
+            (
+                memory.dnd_payload.is_some(),
+                memory.dnd_payload::<T>().is_some(),
+            )
+        };
+        let is_hovered = response.dnd_hover_payload::<T>().is_some();
+
+        let visuals = &self.style().visuals.widgets;
+        let style = if is_hovered {
+            visuals.active
+        } else if is_anything_being_dragged && !can_accept {
+            visuals.disabled
+        } else {
+            visuals.inactive
+        };
+        self.painter().set(
+            where_to_put_background,
+            PaintCmd::Rect {
+                corner_radius: style.corner_radius,
+                fill: style.bg_fill,
+                stroke: style.bg_stroke,
+                rect: response.rect,
+            },
+        );
+
+        let dropped = response.dnd_release_payload();
+        (ret, response, dropped)
+    }
+
     #[deprecated = "Use `ui.allocate_ui` instead"]
     pub fn add_custom_contents(
         &mut self,
@@ -1113,10 +1279,7 @@ impl Ui {
         let mut child_ui = self.child_ui(self.available_rect_before_wrap(), layout);
         let ret = add_contents(&mut child_ui);
         let rect = child_ui.min_rect();
-        let item_spacing = self.style().spacing.item_spacing;
-        self.layout
-            .advance_after_outer_rect(&mut self.region, rect, rect, item_spacing);
-        self.region.expand_to_include_rect(rect);
+        self.advance_after_rects(rect, rect);
         (ret, self.interact(rect, child_ui.id, Sense::hover()))
     }
 
@@ -1173,6 +1336,42 @@ impl Ui {
 
 // ----------------------------------------------------------------------------
 
+/// # Grid
+impl Ui {
+    /// Move to the next row in a [`Grid`].
+    ///
+    /// Does nothing if this `Ui` is not the inside of a `Grid`.
+    pub fn end_row(&mut self) {
+        if let Some(grid) = &mut self.grid {
+            grid.end_row(&mut self.region.cursor, &self.painter);
+        }
+    }
+
+    pub(crate) fn set_grid(&mut self, grid: grid::GridLayout) {
+        self.grid = Some(grid);
+    }
+
+    pub(crate) fn save_grid(&mut self) {
+        if let Some(grid) = &self.grid {
+            grid.save();
+        }
+    }
+}
+
+/// # Menus
+impl Ui {
+    pub(crate) fn set_menu_scope(&mut self, scope: menu::MenuScope) {
+        self.menu = Some(scope);
+    }
+
+    /// The menu this `Ui` is in (if any).
+    pub(crate) fn menu_scope(&self) -> Option<menu::MenuScope> {
+        self.menu
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// ## Debug stuff
 impl Ui {
     /// Shows where the next widget is going to be placed
@@ -1180,3 +1379,70 @@ impl Ui {
         self.layout.debug_paint_cursor(&self.region, &self.painter);
     }
 }
+
+#[test]
+fn test_drag_and_drop() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut dropped_numbers = vec![];
+    let mut dropped_strings = vec![];
+    let hovering = std::cell::Cell::new(false);
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.dnd_drag_source(Id::new("source"), 7_usize, |ui| {
+                ui.label("Seven");
+            });
+            ui.allocate_space(vec2(0.0, 50.0));
+            let (_, response, dropped) = ui.dnd_drop_zone::<usize, _>(|ui| {
+                ui.label("Numbers");
+            });
+            hovering.set(response.dnd_hover_payload::<usize>().is_some());
+            dropped_numbers.extend(dropped.map(|n| *n));
+            ui.allocate_space(vec2(0.0, 50.0));
+            let (_, _, dropped) = ui.dnd_drop_zone::<String, _>(|ui| {
+                ui.label("Strings");
+            });
+            dropped_strings.extend(dropped.map(|s| (*s).clone()));
+        });
+    };
+
+    harness.run(&mut app);
+    let source = harness.expect_widget("Seven").rect.center();
+    let numbers = harness.expect_widget("Numbers").rect.center();
+    let strings = harness.expect_widget("Strings").rect.center();
+
+    // A click is not a drag:
+    harness.click_at(source, &mut app);
+    assert!(harness.ctx().memory().dnd_payload::<usize>().is_none());
+
+    // Dropping on a zone for another type does nothing:
+    harness.move_mouse_to(source);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(strings);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert_eq!(
+        harness.ctx().memory().dnd_payload::<usize>().as_deref(),
+        Some(&7)
+    );
+    assert!(harness.ctx().memory().dnd_payload::<String>().is_none());
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert!(harness.ctx().memory().dnd_payload::<usize>().is_none());
+
+    // Dropping on a compatible zone delivers the payload:
+    harness.move_mouse_to(source);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(numbers);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert!(hovering.get());
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    harness.run(&mut app);
+    assert!(!hovering.get());
+    assert_eq!(dropped_numbers, vec![7]);
+    assert!(dropped_strings.is_empty());
+}
@@ -140,3 +140,12 @@ where
         Self::new(self.lock().clone())
     }
 }
+
+impl<T> std::fmt::Debug for Mutex<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Mutex").field(&*self.lock()).finish()
+    }
+}
@@ -0,0 +1,225 @@
+//! Viewports: extra native windows that are painted by the same [`CtxRef`].
+//!
+//! All viewports share one [`Context`], so they share [`Memory`], fonts and styles,
+//! and a widget in one viewport can affect a widget in another in the same frame.
+//! Each viewport has its own screen rectangle, panels and paint commands.
+//!
+//! The viewport the integration created first is [`ViewportId::root()`].
+//! Everything not shown inside of a [`Viewport`] ends up there.
+//!
+//! ```
+//! # let mut ctx = egui::CtxRef::default();
+//! # ctx.begin_frame(Default::default());
+//! # let ctx = &ctx;
+//! # let mut show_tools = true;
+//! use egui::viewport::Viewport;
+//!
+//! Viewport::new("tools", "Tools")
+//!     .inner_size(egui::vec2(300.0, 200.0))
+//!     .open(&mut show_tools)
+//!     .show(ctx, |ctx| {
+//!         egui::CentralPanel::default().show(ctx, |ui| {
+//!             ui.label("This is in its own native window");
+//!         });
+//!     });
+//! ```
+//!
+//! Integrations that support viewports open a native window for each [`ViewportOutput`] in
+//! [`Output::viewports`], and tell egui about it in [`RawInput::viewports`].
+//! Other integrations ignore them, so the contents of the viewports are not shown.
+
+use std::hash::Hash;
+
+use crate::{paint::PaintCmd, *};
+
+/// Identifies a [`Viewport`] (a native window).
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportId(Id);
+
+impl Default for ViewportId {
+    fn default() -> Self {
+        Self::root()
+    }
+}
+
+impl ViewportId {
+    /// The native window created by the integration.
+    pub fn root() -> Self {
+        Self(Id::background())
+    }
+
+    pub fn new(id_source: impl Hash) -> Self {
+        Self(Id::new(id_source).with("viewport"))
+    }
+
+    /// The layer that panels are painted to in this viewport.
+    pub fn background_layer(self) -> LayerId {
+        if self == Self::root() {
+            LayerId::background()
+        } else {
+            LayerId::new(Order::Background, Id::background().with(self))
+        }
+    }
+}
+
+/// What kind of native window a [`Viewport`] wants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewportSettings {
+    pub title: String,
+    /// In points. `None` means the integration decides.
+    pub inner_size: Option<Vec2>,
+    pub resizable: bool,
+}
+
+/// A viewport shown this frame, sent to the integration in [`Output::viewports`].
+#[derive(Clone)]
+pub struct ViewportOutput {
+    pub id: ViewportId,
+    pub settings: ViewportSettings,
+    /// What to paint in the viewport. Use [`Context::tessellate`] to turn these into [`PaintJobs`].
+    pub paint_commands: Vec<(Rect, PaintCmd)>,
+    /// Like [`crate::Output::text_cursor_rect`], if the focused `TextEdit` is in this viewport.
+    pub text_cursor_rect: Option<Rect>,
+}
+
+/// What the integration tells egui about an open [`Viewport`], in [`RawInput::viewports`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewportInfo {
+    /// The inside of the native window, in points.
+    pub screen_rect: Rect,
+    /// The user asked to close the native window (e.g. with its close button).
+    pub close_requested: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Show some contents in a native window of its own.
+///
+/// Inside of the viewport you add panels, windows etc just as you would to the root viewport.
+/// See [`viewport`](crate::viewport) for an example.
+pub struct Viewport<'open> {
+    id: ViewportId,
+    settings: ViewportSettings,
+    open: Option<&'open mut bool>,
+}
+
+impl<'open> Viewport<'open> {
+    /// The `id_source` must be unique among viewports.
+    pub fn new(id_source: impl Hash, title: impl Into<String>) -> Self {
+        Self {
+            id: ViewportId::new(id_source),
+            settings: ViewportSettings {
+                title: title.into(),
+                inner_size: None,
+                resizable: true,
+            },
+            open: None,
+        }
+    }
+
+    /// The initial size of the inside of the native window, in points.
+    pub fn inner_size(mut self, inner_size: Vec2) -> Self {
+        self.settings.inner_size = Some(inner_size);
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.settings.resizable = resizable;
+        self
+    }
+
+    /// Call this to add a close-button to the native window.
+    /// `*open` is set to `false` when the user closes it.
+    /// If `*open` is `false`, the viewport is not shown.
+    pub fn open(mut self, open: &'open mut bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    pub fn show(self, ctx: &CtxRef, add_contents: impl FnOnce(&CtxRef)) {
+        let Self { id, settings, open } = self;
+
+        let info = ctx.input().viewports.get(&id).copied();
+        if let Some(open) = open {
+            if info.map_or(false, |info| info.close_requested) {
+                *open = false;
+            }
+            if !*open {
+                return;
+            }
+        }
+
+        let screen_rect = match info {
+            Some(info) => info.screen_rect,
+            None => {
+                // The integration hasn't opened the window yet:
+                ctx.request_repaint();
+                let size = settings.inner_size.unwrap_or_else(|| vec2(400.0, 300.0));
+                Rect::from_min_size(Default::default(), size)
+            }
+        };
+
+        ctx.frame_state().viewports.push((id, settings));
+        ctx.in_viewport(id, screen_rect, add_contents);
+    }
+}
+
+#[test]
+fn test_viewports() {
+    let mut harness = testing::Harness::new(vec2(600.0, 400.0));
+    let mut open = true;
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let _ = ui.button("Root");
+        });
+        Viewport::new("tools", "Tools")
+            .open(&mut open)
+            .show(ctx, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let _ = ui.button("Tool");
+                });
+            });
+    };
+
+    let output = harness.run(&mut app);
+    assert_eq!(output.viewports.len(), 1);
+    assert_eq!(output.viewports[0].settings.title, "Tools");
+    assert!(!output.viewports[0].paint_commands.is_empty());
+
+    // Both viewports have their origin in the top left corner:
+    let root = harness.expect_widget("Root").rect;
+    let tool = harness.expect_widget("Tool").rect;
+    assert_eq!(root.min, tool.min);
+
+    // The mouse is only in one viewport at a time:
+    let viewport_id = ViewportId::new("tools");
+    let info = ViewportInfo {
+        screen_rect: Rect::from_min_size(Default::default(), vec2(300.0, 200.0)),
+        close_requested: false,
+    };
+    harness.set_viewport(viewport_id, Some(info));
+    harness.set_mouse_viewport(viewport_id);
+    harness.move_mouse_to(tool.center());
+    harness.run(&mut app);
+    assert!(!harness.expect_widget("Root").hovered);
+    assert!(harness.expect_widget("Tool").hovered);
+
+    harness.set_mouse_viewport(ViewportId::root());
+    harness.run(&mut app);
+    assert!(harness.expect_widget("Root").hovered);
+    assert!(!harness.expect_widget("Tool").hovered);
+
+    // Closing the native window:
+    harness.set_viewport(
+        viewport_id,
+        Some(ViewportInfo {
+            close_requested: true,
+            ..info
+        }),
+    );
+    let output = harness.run(&mut app);
+    assert!(output.viewports.is_empty());
+    assert!(harness.widget("Tool").is_none());
+    assert!(!open);
+}
@@ -107,6 +107,7 @@ impl Widget for Button {
         }
 
         let response = ui.allocate_response(desired_size, sense);
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, &galley.text));
 
         if ui.clip_rect().intersects(response.rect) {
             let visuals = ui.style().interact(&response);
@@ -189,13 +190,15 @@ impl<'a> Widget for Checkbox<'a> {
         let mut desired_size = total_extra + galley.size;
         desired_size = desired_size.at_least(spacing.interact_size);
         desired_size.y = desired_size.y.max(icon_width);
-        let response = ui.allocate_response(desired_size, Sense::click());
+        let mut response = ui.allocate_response(desired_size, Sense::click());
         let rect = ui
             .layout()
             .align_size_within_rect(desired_size, response.rect);
         if response.clicked {
             *checked = !*checked;
+            response.changed = true;
         }
+        response.widget_info(|| WidgetInfo::selected(WidgetType::Checkbox, *checked, &galley.text));
 
         let visuals = ui.style().interact(&response);
         let text_cursor = pos2(
@@ -284,6 +287,8 @@ impl Widget for RadioButton {
         desired_size = desired_size.at_least(ui.style().spacing.interact_size);
         desired_size.y = desired_size.y.max(icon_width);
         let response = ui.allocate_response(desired_size, Sense::click());
+        response
+            .widget_info(|| WidgetInfo::selected(WidgetType::RadioButton, checked, &galley.text));
         let rect = ui
             .layout()
             .align_size_within_rect(desired_size, response.rect);
@@ -391,6 +396,7 @@ impl Widget for ImageButton {
         let button_padding = ui.style().spacing.button_padding;
         let desired_size = image.desired_size() + 2.0 * button_padding;
         let response = ui.allocate_response(desired_size, sense);
+        response.widget_info(|| WidgetInfo::new(WidgetType::ImageButton));
 
         if ui.clip_rect().intersects(response.rect) {
             let visuals = ui.style().interact(&response);
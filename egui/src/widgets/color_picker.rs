@@ -59,6 +59,7 @@ fn show_srgba(ui: &mut Ui, srgba: Color32, desired_size: Vec2) -> Response {
 fn color_button(ui: &mut Ui, color: Color32) -> Response {
     let desired_size = ui.style().spacing.interact_size;
     let response = ui.allocate_response(desired_size, Sense::click());
+    response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
     let visuals = ui.style().interact(&response);
     background_checkers(ui.painter(), response.rect);
     ui.painter().add(PaintCmd::Rect {
@@ -77,7 +78,7 @@ fn color_slider_1d(ui: &mut Ui, value: &mut f32, color_at: impl Fn(f32) -> Color
         ui.style().spacing.slider_width,
         ui.style().spacing.interact_size.y * 2.0,
     );
-    let response = ui.allocate_response(desired_size, Sense::click_and_drag());
+    let response = ui.allocate_response(desired_size, Sense::click_and_drag().not_focusable());
     let rect = response.rect;
 
     if response.active {
@@ -135,7 +136,7 @@ fn color_slider_2d(
     color_at: impl Fn(f32, f32) -> Color32,
 ) -> Response {
     let desired_size = Vec2::splat(ui.style().spacing.slider_width);
-    let response = ui.allocate_response(desired_size, Sense::click_and_drag());
+    let response = ui.allocate_response(desired_size, Sense::click_and_drag().not_focusable());
     let rect = response.rect;
 
     if response.active {
@@ -154,7 +154,7 @@ impl<'a> Widget for DragValue<'a> {
         let kb_edit_id = ui.auto_id_with("edit");
         let is_kb_editing = ui.memory().has_kb_focus(kb_edit_id);
 
-        if is_kb_editing {
+        let mut response = if is_kb_editing {
             let button_width = ui.style().spacing.interact_size.x;
             let mut value_text = ui.memory().temp_edit_string.take().unwrap_or(value_text);
             let response = ui.add(
@@ -184,9 +184,19 @@ impl<'a> Widget for DragValue<'a> {
                 value as f32, // Show full precision value on-hover. TODO: figure out f64 vs f32
                 suffix
             ));
+            let kb_steps = if response.has_kb_focus {
+                super::slider::arrow_key_steps(ui)
+            } else {
+                0.0
+            };
             if response.clicked {
                 ui.memory().request_kb_focus(kb_edit_id);
                 ui.memory().temp_edit_string = None; // Filled in next frame
+            } else if kb_steps != 0.0 {
+                let new_value = value + speed as f64 * kb_steps;
+                let new_value = round_to_decimals(new_value, auto_decimals);
+                let new_value = clamp(new_value, range);
+                set(&mut value_function, new_value);
             } else if response.active {
                 let mdelta = ui.input().mouse.delta;
                 let delta_points = mdelta.x - mdelta.y; // Increase to the right and up
@@ -201,6 +211,11 @@ impl<'a> Widget for DragValue<'a> {
                 }
             }
             response
-        }
+        };
+
+        let new_value = get(&mut value_function);
+        response.changed = new_value != value;
+        response.widget_info(|| WidgetInfo::drag_value(new_value));
+        response
     }
 }
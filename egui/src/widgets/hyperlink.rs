@@ -47,6 +47,7 @@ impl Widget for Hyperlink {
         let font = &ui.fonts()[text_style];
         let galley = font.layout_multiline(text, ui.available_width());
         let response = ui.allocate_response(galley.size, Sense::click());
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Hyperlink, &galley.text));
 
         if response.hovered {
             ui.ctx().output().cursor_icon = CursorIcon::PointingHand;
@@ -1,4 +1,7 @@
-use crate::{paint::Galley, *};
+use crate::{
+    paint::{Galley, LayoutJob},
+    *,
+};
 
 /// Static text.
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
@@ -8,6 +11,8 @@ pub struct Label {
     pub(crate) multiline: Option<bool>,
     pub(crate) text_style: Option<TextStyle>,
     pub(crate) text_color: Option<Color32>,
+    /// Rich text, with its own fonts and colors.
+    pub(crate) job: Option<LayoutJob>,
 }
 
 impl Label {
@@ -17,6 +22,27 @@ impl Label {
             multiline: None,
             text_style: None,
             text_color: None,
+            job: None,
+        }
+    }
+
+    /// Show rich text, where different parts of the text can have different fonts and colors.
+    ///
+    /// `text_style` and `text_color` are ignored for rich text.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// use egui::paint::{LayoutJob, TextFormat};
+    /// let body = TextFormat::text_style(ui.fonts(), egui::TextStyle::Body, egui::Color32::GRAY);
+    /// let mut job = LayoutJob::default();
+    /// job.append("This is ", body);
+    /// job.append("important", egui::paint::TextFormat { color: egui::Color32::RED, ..body });
+    /// ui.add(egui::Label::rich(job));
+    /// ```
+    pub fn rich(job: LayoutJob) -> Self {
+        Self {
+            job: Some(job.clone()),
+            ..Self::new(job.text)
         }
     }
 
@@ -64,6 +90,14 @@ impl Label {
     }
 
     pub fn layout_width(&self, ui: &Ui, max_width: f32) -> Galley {
+        if let Some(job) = &self.job {
+            let mut job = job.clone();
+            if self.is_multiline(ui) {
+                job.wrap_width = max_width;
+            }
+            return ui.fonts().layout_job(job);
+        }
+
         let text_style = self.text_style_or_default(ui.style());
         let font = &ui.fonts()[text_style];
         if self.is_multiline(ui) {
@@ -73,7 +107,36 @@ impl Label {
         }
     }
 
+    fn layout_with_indentation(
+        &self,
+        ui: &Ui,
+        first_row_indentation: f32,
+        max_width: f32,
+    ) -> Galley {
+        if let Some(job) = &self.job {
+            let mut job = job.clone();
+            job.first_row_indentation = first_row_indentation;
+            job.wrap_width = max_width;
+            return ui.fonts().layout_job(job);
+        }
+
+        let text_style = self.text_style_or_default(ui.style());
+        let font = &ui.fonts()[text_style];
+        font.layout_multiline_with_indentation_and_max_width(
+            self.text.clone(),
+            first_row_indentation,
+            max_width,
+        )
+    }
+
     pub fn font_height(&self, fonts: &paint::Fonts, style: &Style) -> f32 {
+        if let Some(job) = &self.job {
+            if let Some(section) = job.sections.first() {
+                return fonts
+                    .font(section.format.family, section.format.size)
+                    .row_height();
+            }
+        }
         let text_style = self.text_style_or_default(style);
         fonts[text_style].row_height()
     }
@@ -111,6 +174,12 @@ impl Label {
 
 impl Widget for Label {
     fn ui(self, ui: &mut Ui) -> Response {
+        let widget_type = if self.text_style_or_default(ui.style()) == TextStyle::Heading {
+            WidgetType::Heading
+        } else {
+            WidgetType::Label
+        };
+
         if self.is_multiline(ui)
             && ui.layout().main_dir() == Direction::LeftToRight
             && ui.layout().main_wrap()
@@ -121,13 +190,7 @@ impl Widget for Label {
             let max_width = ui.available_width();
             let first_row_indentation = max_width - ui.available_size_before_wrap().x;
 
-            let text_style = self.text_style_or_default(ui.style());
-            let font = &ui.fonts()[text_style];
-            let mut galley = font.layout_multiline_with_indentation_and_max_width(
-                self.text.clone(),
-                first_row_indentation,
-                max_width,
-            );
+            let mut galley = self.layout_with_indentation(ui, first_row_indentation, max_width);
 
             let pos = pos2(ui.min_rect().left(), ui.cursor().y);
 
@@ -153,11 +216,13 @@ impl Widget for Label {
                 total_response |= ui.interact(rect, id, Sense::hover());
             }
 
+            total_response.widget_info(|| WidgetInfo::labeled(widget_type, &galley.text));
             self.paint_galley(ui, pos, galley);
             total_response
         } else {
             let galley = self.layout(ui);
-            let response = ui.allocate_response(galley.size, Sense::click());
+            let response = ui.allocate_response(galley.size, Sense::click().not_focusable());
+            response.widget_info(|| WidgetInfo::labeled(widget_type, &galley.text));
             let rect = ui
                 .layout()
                 .align_size_within_rect(galley.size, response.rect);
@@ -167,6 +232,12 @@ impl Widget for Label {
     }
 }
 
+impl From<LayoutJob> for Label {
+    fn from(job: LayoutJob) -> Label {
+        Label::rich(job)
+    }
+}
+
 impl Into<Label> for &str {
     fn into(self) -> Label {
         Label::new(self)
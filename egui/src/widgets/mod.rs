@@ -14,6 +14,7 @@ mod drag_value;
 mod hyperlink;
 mod image;
 mod label;
+pub mod plot;
 mod selected_label;
 mod separator;
 mod slider;
@@ -44,3 +45,56 @@ pub fn reset_button<T: Default + PartialEq>(ui: &mut Ui, value: &mut T) {
         *value = def;
     }
 }
+
+#[test]
+fn test_response_changed() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Choice {
+        A,
+        B,
+    }
+
+    let mut checked = false;
+    let mut choice = Choice::A;
+    let mut slider_value = 50.0_f32;
+    let mut drag_value = 10.0_f32;
+    let mut changed = vec![];
+    let mut harness = testing::Harness::new(vec2(400.0, 300.0));
+    {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                let responses = [
+                    ui.checkbox(&mut checked, "Check"),
+                    ui.radio_value(&mut choice, Choice::A, "A"),
+                    ui.radio_value(&mut choice, Choice::B, "B"),
+                    ui.add(Slider::f32(&mut slider_value, 0.0..=100.0)),
+                    ui.add(DragValue::f32(&mut drag_value)),
+                ];
+                let names = ["Check", "A", "B", "Slide", "Drag"];
+                for (name, response) in names.iter().zip(&responses) {
+                    if response.changed {
+                        changed.push(*name);
+                    }
+                }
+            });
+        };
+        harness.run(&mut app);
+        harness.click("Check", &mut app);
+        harness.click("A", &mut app); // already selected
+        harness.click("B", &mut app);
+
+        // The slider and drag value are the last two widgets to get focus:
+        for _ in 0..4 {
+            harness.press_key(Key::Tab);
+            harness.run(&mut app);
+        }
+        harness.press_key(Key::ArrowRight);
+        harness.run(&mut app);
+        harness.press_key(Key::Tab);
+        harness.run(&mut app);
+        harness.press_key(Key::ArrowRight);
+        harness.run(&mut app);
+        harness.run(&mut app);
+    }
+    assert_eq!(changed, ["Check", "B", "Slide", "Drag"]);
+}
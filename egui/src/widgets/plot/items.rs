@@ -0,0 +1,187 @@
+use super::transform::{Bounds, ScreenTransform};
+use crate::*;
+
+/// A value in the value-space of the plot.
+///
+/// Uses `f64` for improved accuracy to enable plotting
+/// large values (e.g. unix time on x axis).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Value {
+    /// This is often something monotonically increasing, such as time, but doesn't have to be.
+    pub x: f64,
+    /// The value of the function at `x`.
+    pub y: f64,
+}
+
+impl Value {
+    pub fn new(x: impl Into<f64>, y: impl Into<f64>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SeriesKind {
+    /// Connect the values with a line.
+    Line,
+    /// A filled circle at each value.
+    Points,
+    /// A bar from zero up (or down) to each value.
+    Bars,
+}
+
+/// A named series of values to show in a [`Plot`](super::Plot),
+/// either as a line, as points or as bars.
+///
+/// ```
+/// use egui::plot::{Series, Value};
+/// let sin = (0..100).map(|i| {
+///     let x = i as f64 * 0.1;
+///     Value::new(x, x.sin())
+/// });
+/// let series = Series::line(sin).name("sin(x)").stroke_width(2.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    pub(crate) kind: SeriesKind,
+    pub(crate) name: String,
+    pub(crate) values: Vec<Value>,
+    /// `Color32::TRANSPARENT` means "pick one automatically".
+    pub(crate) color: Color32,
+    stroke_width: f32,
+    radius: f32,
+    bar_width: f64,
+}
+
+impl Series {
+    fn new(kind: SeriesKind, values: impl IntoIterator<Item = Value>) -> Self {
+        Self {
+            kind,
+            name: Default::default(),
+            values: values.into_iter().collect(),
+            color: Color32::TRANSPARENT,
+            stroke_width: 1.0,
+            radius: 2.0,
+            bar_width: 0.5,
+        }
+    }
+
+    /// Connect the values with straight lines.
+    pub fn line(values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Line, values)
+    }
+
+    /// Show each value as a small filled circle.
+    pub fn points(values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Points, values)
+    }
+
+    /// Show each value as a bar going from zero to the value.
+    pub fn bars(values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Bars, values)
+    }
+
+    /// Name of this series. Shown in the legend and when hovering a value.
+    ///
+    /// Series with the same name will be toggled together in the legend.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Default: a color picked automatically, different for each series.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Width of the line (for [`Series::line`]) and of the outline of each bar (for [`Series::bars`]).
+    pub fn stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// Radius of each point (for [`Series::points`]).
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Width of each bar (for [`Series::bars`]), in the units of the x axis.
+    pub fn bar_width(mut self, bar_width: f64) -> Self {
+        self.bar_width = bar_width;
+        self
+    }
+
+    pub(crate) fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::nothing();
+        for value in &self.values {
+            if self.kind == SeriesKind::Bars {
+                bounds.extend_with_x(value.x - 0.5 * self.bar_width);
+                bounds.extend_with_x(value.x + 0.5 * self.bar_width);
+                bounds.extend_with_y(0.0);
+                bounds.extend_with_y(value.y);
+            } else {
+                bounds.extend_with(value);
+            }
+        }
+        bounds
+    }
+
+    /// The value closest to the given screen position, and its squared distance.
+    pub(crate) fn closest(&self, pos: Pos2, transform: &ScreenTransform) -> Option<(Value, f32)> {
+        self.values
+            .iter()
+            .filter(|value| value.x.is_finite() && value.y.is_finite())
+            .map(|value| {
+                let value_pos = transform.position_from_value(value);
+                (*value, value_pos.distance_sq(pos))
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    pub(crate) fn paint(&self, painter: &Painter, transform: &ScreenTransform) {
+        let color = self.color;
+        let positions = self
+            .values
+            .iter()
+            .filter(|value| value.x.is_finite() && value.y.is_finite())
+            .map(|value| transform.position_from_value(value));
+
+        match self.kind {
+            SeriesKind::Line => {
+                let points: Vec<Pos2> = positions.collect();
+                if points.len() == 1 {
+                    painter.circle_filled(points[0], self.stroke_width / 2.0, color);
+                } else if points.len() > 1 {
+                    painter.add(PaintCmd::line(
+                        points,
+                        Stroke::new(self.stroke_width, color),
+                    ));
+                }
+            }
+            SeriesKind::Points => {
+                for pos in positions {
+                    painter.circle_filled(pos, self.radius, color);
+                }
+            }
+            SeriesKind::Bars => {
+                let fill: Color32 = Rgba::from(color).multiply(0.5).into();
+                let corner = |x: f64, y: f64| transform.position_from_value(&Value::new(x, y));
+                for value in &self.values {
+                    if !value.x.is_finite() || !value.y.is_finite() {
+                        continue;
+                    }
+                    let a = corner(value.x - 0.5 * self.bar_width, 0.0);
+                    let b = corner(value.x + 0.5 * self.bar_width, value.y);
+                    let rect = Rect::from_min_max(a.min(b), a.max(b));
+                    painter.rect(rect, 0.0, fill, Stroke::new(self.stroke_width, color));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,661 @@
+//! Simple plotting library: lines, points and bars with axes, a legend, zooming and panning.
+//!
+//! ```
+//! # let ui = &mut egui::Ui::__test();
+//! use egui::plot::{Plot, Series, Value};
+//! let sin = (0..1000).map(|i| {
+//!     let x = i as f64 * 0.01;
+//!     Value::new(x, x.sin())
+//! });
+//! ui.add(Plot::new("sin").series(Series::line(sin).name("sin(x)")));
+//! ```
+
+mod items;
+mod transform;
+
+use std::collections::HashSet;
+
+use crate::{color::Hsva, *};
+
+pub use items::{Series, Value};
+use transform::{Bounds, ScreenTransform};
+
+// ----------------------------------------------------------------------------
+
+/// Information about the plot that has to persist between frames.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    /// The bounds we showed last frame.
+    bounds: Bounds,
+
+    /// If `true`, the bounds are fit to the data each frame.
+    /// Turned off when the user zooms or pans, and back on when they double-click.
+    auto_bounds: bool,
+
+    /// Names of series that the user has hidden by clicking on them in the legend.
+    hidden_series: HashSet<String>,
+
+    /// Where the user started dragging out a zoom rectangle (if they are doing so).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    box_zoom_start: Option<Pos2>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            bounds: Bounds::nothing(),
+            auto_bounds: true,
+            hidden_series: Default::default(),
+            box_zoom_start: None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A 2D plot, e.g. a graph of a function.
+///
+/// The axes are fit to the data automatically.
+/// The user can pan the plot by dragging it,
+/// zoom in by dragging out a rectangle while holding down ctrl (cmd on Mac),
+/// and double-click to fit the plot to the data again.
+/// Clicking the name of a series in the legend hides or shows it.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// use egui::plot::{Plot, Series, Value};
+/// let squares = (0..10).map(|i| Value::new(i, i * i));
+/// let plot = Plot::new("squares")
+///     .series(Series::bars(squares.clone()).name("bars"))
+///     .series(Series::line(squares).name("line"))
+///     .height(200.0);
+/// ui.add(plot);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Plot {
+    id_source: Id,
+    series: Vec<Series>,
+    width: Option<f32>,
+    height: Option<f32>,
+    min_size: Vec2,
+    include_x: Vec<f64>,
+    include_y: Vec<f64>,
+    allow_zoom: bool,
+    allow_drag: bool,
+    show_legend: bool,
+}
+
+impl Plot {
+    /// Create a new `Plot` with a locally unique identifier.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            series: Default::default(),
+            width: None,
+            height: None,
+            min_size: Vec2::splat(64.0),
+            include_x: Default::default(),
+            include_y: Default::default(),
+            allow_zoom: true,
+            allow_drag: true,
+            show_legend: true,
+        }
+    }
+
+    /// Add a series of values, shown as a line, points or bars.
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Width of the plot. Default: fill the available width.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Height of the plot. Default: half of the width.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Always include this x value when fitting the plot to the data.
+    pub fn include_x(mut self, x: impl Into<f64>) -> Self {
+        self.include_x.push(x.into());
+        self
+    }
+
+    /// Always include this y value when fitting the plot to the data.
+    pub fn include_y(mut self, y: impl Into<f64>) -> Self {
+        self.include_y.push(y.into());
+        self
+    }
+
+    /// Can the user zoom in by dragging out a rectangle while holding down ctrl/cmd,
+    /// or by pinching on a touch screen? Default: `true`.
+    pub fn allow_zoom(mut self, allow_zoom: bool) -> Self {
+        self.allow_zoom = allow_zoom;
+        self
+    }
+
+    /// Can the user pan the plot by dragging it? Default: `true`.
+    pub fn allow_drag(mut self, allow_drag: bool) -> Self {
+        self.allow_drag = allow_drag;
+        self
+    }
+
+    /// Show the names of the series in the top right corner. Default: `true`.
+    pub fn legend(mut self, show_legend: bool) -> Self {
+        self.show_legend = show_legend;
+        self
+    }
+}
+
+impl Widget for Plot {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            id_source,
+            mut series,
+            width,
+            height,
+            min_size,
+            include_x,
+            include_y,
+            allow_zoom,
+            allow_drag,
+            show_legend,
+        } = self;
+
+        let plot_id = ui.make_persistent_id(id_source);
+        let mut state = ui.memory().plot.get(&plot_id).cloned().unwrap_or_default();
+
+        let size = {
+            let width = width
+                .unwrap_or_else(|| ui.available_size_before_wrap_finite().x)
+                .at_least(min_size.x);
+            let height = height.unwrap_or(width / 2.0).at_least(min_size.y);
+            vec2(width, height)
+        };
+        let response = ui.allocate_response(size, Sense::click_and_drag().not_focusable());
+        let frame = response.rect;
+
+        for (i, series) in series.iter_mut().enumerate() {
+            if series.color == Color32::TRANSPARENT {
+                series.color = auto_color(i);
+            }
+        }
+
+        let legend = if show_legend {
+            Legend::new(ui, frame, &series, &state.hidden_series)
+        } else {
+            Legend::default()
+        };
+
+        if response.clicked {
+            if let Some(name) = ui.input().mouse.pos.and_then(|pos| legend.entry_at(pos)) {
+                if !state.hidden_series.remove(name) {
+                    state.hidden_series.insert(name.to_owned());
+                }
+            }
+        }
+
+        series.retain(|series| !state.hidden_series.contains(&series.name));
+
+        if response.double_clicked {
+            state.auto_bounds = true;
+        }
+
+        if state.auto_bounds || !state.bounds.is_valid() {
+            let mut bounds = Bounds::nothing();
+            for &x in &include_x {
+                bounds.extend_with_x(x);
+            }
+            for &y in &include_y {
+                bounds.extend_with_y(y);
+            }
+            for series in &series {
+                let series_bounds = series.bounds();
+                for d in 0..2 {
+                    bounds.min[d] = bounds.min[d].min(series_bounds.min[d]);
+                    bounds.max[d] = bounds.max[d].max(series_bounds.max[d]);
+                }
+            }
+            bounds.add_relative_margin(0.05);
+            bounds.make_valid();
+            state.bounds = bounds;
+        }
+
+        if frame.width() <= 0.0 || frame.height() <= 0.0 {
+            ui.memory().plot.insert(plot_id, state);
+            return response;
+        }
+
+        let mut transform = ScreenTransform::new(frame, state.bounds);
+
+        // Zooming and panning:
+        let (mouse_pos, mouse_delta, mouse_pressed, command, multi_touch) = {
+            let input = ui.input();
+            (
+                input.mouse.pos,
+                input.mouse.delta,
+                input.mouse.pressed,
+                input.modifiers.command,
+                input.touch.multi_touch(),
+            )
+        };
+        let multi_touch = multi_touch
+            .filter(|_| (allow_drag || allow_zoom) && (response.active || response.hovered));
+        if let Some(multi_touch) = multi_touch {
+            // Pinch to zoom, pan with two fingers:
+            if allow_drag {
+                transform.translate_bounds(multi_touch.translation_delta);
+            }
+            if allow_zoom {
+                transform.zoom(multi_touch.zoom_delta, multi_touch.center);
+            }
+            state.bounds = *transform.bounds();
+            state.auto_bounds = false;
+            state.box_zoom_start = None;
+        } else if response.active {
+            if allow_zoom && mouse_pressed && command {
+                state.box_zoom_start = mouse_pos;
+            }
+            if allow_drag && state.box_zoom_start.is_none() && mouse_delta != Vec2::zero() {
+                transform.translate_bounds(mouse_delta);
+                state.bounds = *transform.bounds();
+                state.auto_bounds = false;
+            }
+        } else if let Some(start) = state.box_zoom_start.take() {
+            if let Some(end) = mouse_pos {
+                let (a, b) = (
+                    transform.value_from_position(start),
+                    transform.value_from_position(end),
+                );
+                let mut bounds = Bounds::nothing();
+                bounds.extend_with(&a);
+                bounds.extend_with(&b);
+                // Ignore accidental tiny rectangles:
+                if (end - start).x.abs() > 4.0 && (end - start).y.abs() > 4.0 && bounds.is_valid() {
+                    state.bounds = bounds;
+                    state.auto_bounds = false;
+                    transform = ScreenTransform::new(frame, state.bounds);
+                }
+            }
+        }
+
+        // Painting:
+        let painter = ui.painter().sub_region(frame);
+        let visuals = &ui.style().visuals;
+        painter.rect(
+            frame,
+            2.0,
+            visuals.dark_bg_color,
+            visuals.widgets.noninteractive.bg_stroke,
+        );
+
+        for d in 0..2 {
+            paint_axis(ui, &painter, &transform, d);
+        }
+
+        for series in &series {
+            series.paint(&painter, &transform);
+        }
+
+        if let Some(start) = state.box_zoom_start {
+            if let Some(end) = mouse_pos {
+                let rect = Rect::from_min_max(start.min(end), start.max(end));
+                let selection = visuals.selection;
+                let fill = Rgba::from(selection.bg_fill).multiply(0.25);
+                painter.rect(rect, 0.0, fill, selection.stroke);
+            }
+        } else if response.hovered && !response.active {
+            if let Some(mouse_pos) = mouse_pos {
+                if frame.contains(mouse_pos) && legend.entry_at(mouse_pos).is_none() {
+                    paint_crosshair(ui, &painter, &transform, &series, mouse_pos);
+                }
+            }
+        }
+
+        legend.paint(ui, &painter, mouse_pos);
+
+        ui.memory().plot.insert(plot_id, state);
+        response
+    }
+}
+
+/// A different color for each series, chosen using the golden ratio so neighbors are easy to tell apart.
+fn auto_color(index: usize) -> Color32 {
+    let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
+    let h = index as f32 * golden_ratio;
+    Hsva::new(h.fract(), 0.85, 0.5, 1.0).into()
+}
+
+/// The distance between grid lines: 1, 2 or 5 times a power of ten,
+/// making sure the lines are at least `min_step` apart.
+fn grid_step(min_step: f64) -> f64 {
+    let magnitude = 10.0_f64.powf(min_step.log10().floor());
+    for &multiple in &[1.0, 2.0, 5.0] {
+        if multiple * magnitude >= min_step {
+            return multiple * magnitude;
+        }
+    }
+    10.0 * magnitude
+}
+
+/// How many decimals we need to tell apart values that are `step` apart.
+fn decimals_for_step(step: f64) -> usize {
+    (-step.log10().floor()).max(0.0) as usize
+}
+
+/// Paint the grid lines and tick labels of one axis, `d` (0 = x, 1 = y).
+fn paint_axis(ui: &Ui, painter: &Painter, transform: &ScreenTransform, d: usize) {
+    // Labels on the x axis need more room, since they are wider than they are high:
+    let min_spacing_in_points = if d == 0 { 80.0 } else { 40.0 };
+    let step = grid_step(transform.dvalue_dpos(d).abs() * min_spacing_in_points);
+    if !step.is_finite() || step <= 0.0 {
+        return;
+    }
+    let decimals = decimals_for_step(step);
+
+    let text_color = ui.style().visuals.text_color();
+    let grid_color = Rgba::from(text_color).multiply(0.15);
+    let axis_color = Rgba::from(text_color).multiply(0.5);
+
+    let frame = *transform.frame();
+    let bounds = transform.bounds();
+    let first = (bounds.min[d] / step).ceil() as i64;
+    let last = (bounds.max[d] / step).floor() as i64;
+
+    for i in first..=last {
+        let value_main = i as f64 * step;
+        let value = if d == 0 {
+            Value::new(value_main, 0.0)
+        } else {
+            Value::new(0.0, value_main)
+        };
+        let pos = transform.position_from_value(&value);
+
+        let color = if i == 0 { axis_color } else { grid_color };
+        let (line, text_pos) = if d == 0 {
+            (
+                [pos2(pos.x, frame.top()), pos2(pos.x, frame.bottom())],
+                pos2(pos.x + 2.0, frame.bottom() - 2.0),
+            )
+        } else {
+            (
+                [pos2(frame.left(), pos.y), pos2(frame.right(), pos.y)],
+                pos2(frame.left() + 2.0, pos.y - 2.0),
+            )
+        };
+        painter.line_segment(line, Stroke::new(1.0, color));
+
+        let text = format!("{:.*}", decimals, value_main);
+        painter.text(
+            text_pos,
+            (Align::Min, Align::Max),
+            text,
+            TextStyle::Small,
+            text_color,
+        );
+    }
+}
+
+/// Show lines through the value closest to the mouse, and what that value is.
+fn paint_crosshair(
+    ui: &Ui,
+    painter: &Painter,
+    transform: &ScreenTransform,
+    series: &[Series],
+    mouse_pos: Pos2,
+) {
+    let snap_radius = 64.0;
+
+    let closest = series
+        .iter()
+        .filter_map(|series| {
+            series
+                .closest(mouse_pos, transform)
+                .map(|(value, dist_sq)| (series, value, dist_sq))
+        })
+        .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+        .filter(|(_, _, dist_sq)| *dist_sq <= snap_radius * snap_radius);
+
+    let text_color = ui.style().visuals.text_color();
+    let line_color = Rgba::from(text_color).multiply(0.5);
+
+    let (pos, value, prefix) = if let Some((series, value, _)) = closest {
+        let pos = transform.position_from_value(&value);
+        painter.circle_stroke(pos, 4.0, Stroke::new(1.5, series.color));
+        let prefix = if series.name.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", series.name)
+        };
+        (pos, value, prefix)
+    } else {
+        (
+            mouse_pos,
+            transform.value_from_position(mouse_pos),
+            String::new(),
+        )
+    };
+
+    let frame = *transform.frame();
+    painter.line_segment(
+        [pos2(frame.left(), pos.y), pos2(frame.right(), pos.y)],
+        Stroke::new(1.0, line_color),
+    );
+    painter.line_segment(
+        [pos2(pos.x, frame.top()), pos2(pos.x, frame.bottom())],
+        Stroke::new(1.0, line_color),
+    );
+
+    let x_decimals = decimals_for_step(transform.dvalue_dpos(0).abs());
+    let y_decimals = decimals_for_step(transform.dvalue_dpos(1).abs());
+    let text = format!(
+        "{}x = {:.*}\ny = {:.*}",
+        prefix, x_decimals, value.x, y_decimals, value.y
+    );
+    painter.text(
+        pos + vec2(6.0, -6.0),
+        (Align::Min, Align::Max),
+        text,
+        TextStyle::Body,
+        text_color,
+    );
+}
+
+// ----------------------------------------------------------------------------
+
+/// The list of series names in the top right corner of the plot.
+struct Legend {
+    /// Name, color, is it hidden and where is it?
+    entries: Vec<(String, Color32, bool, Rect)>,
+    rect: Rect,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            rect: Rect::nothing(),
+        }
+    }
+}
+
+impl Legend {
+    fn new(ui: &Ui, frame: Rect, series: &[Series], hidden_series: &HashSet<String>) -> Self {
+        let text_style = TextStyle::Body;
+        let font = &ui.fonts()[text_style];
+        let row_height = font.row_height();
+        let swatch_size = row_height * 0.6;
+        let margin = 4.0;
+
+        let mut names: Vec<(&String, Color32)> = vec![];
+        for series in series {
+            if !series.name.is_empty() && names.iter().all(|(name, _)| *name != &series.name) {
+                names.push((&series.name, series.color));
+            }
+        }
+        if names.is_empty() {
+            return Self::default();
+        }
+
+        let width = names
+            .iter()
+            .map(|(name, _)| font.layout_single_line((*name).clone()).size.x)
+            .fold(0.0, f32::max)
+            + swatch_size
+            + 3.0 * margin;
+        let height = names.len() as f32 * row_height + 2.0 * margin;
+        let rect = Rect::from_min_size(
+            frame.right_top() + vec2(-width - margin, margin),
+            vec2(width, height),
+        );
+
+        let entries = names
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, color))| {
+                let entry_rect = Rect::from_min_size(
+                    rect.min + vec2(0.0, margin + i as f32 * row_height),
+                    vec2(width, row_height),
+                );
+                let hidden = hidden_series.contains(name);
+                (name.clone(), color, hidden, entry_rect)
+            })
+            .collect();
+
+        Self { entries, rect }
+    }
+
+    /// The name of the series under the mouse (if any).
+    fn entry_at(&self, pos: Pos2) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(_, _, _, rect)| rect.contains(pos))
+            .map(|(name, _, _, _)| name.as_str())
+    }
+
+    fn paint(&self, ui: &Ui, painter: &Painter, mouse_pos: Option<Pos2>) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let visuals = &ui.style().visuals;
+        let text_style = TextStyle::Body;
+        let margin = 4.0;
+
+        painter.rect(
+            self.rect,
+            2.0,
+            visuals.widgets.noninteractive.bg_fill,
+            visuals.widgets.noninteractive.bg_stroke,
+        );
+
+        for (name, color, hidden, rect) in &self.entries {
+            let hovered = matches!(mouse_pos, Some(pos) if rect.contains(pos));
+            let text_color = if hovered {
+                visuals.widgets.hovered.text_color()
+            } else if *hidden {
+                Rgba::from(visuals.text_color()).multiply(0.5).into()
+            } else {
+                visuals.text_color()
+            };
+
+            let swatch_size = rect.height() * 0.6;
+            let swatch = Rect::from_center_size(
+                pos2(rect.left() + margin + swatch_size / 2.0, rect.center().y),
+                Vec2::splat(swatch_size),
+            );
+            if *hidden {
+                painter.rect_stroke(swatch, 2.0, Stroke::new(1.0, *color));
+            } else {
+                painter.rect_filled(swatch, 2.0, *color);
+            }
+
+            painter.text(
+                pos2(swatch.right() + margin, rect.center().y),
+                (Align::Min, Align::Center),
+                name,
+                text_style,
+                text_color,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_plot_drag_and_zoom() {
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            let line = Series::line(vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)]);
+            ui.add(Plot::new("plot").series(line).width(200.0).height(100.0));
+        });
+    };
+    let plot_state = |harness: &crate::testing::Harness| {
+        harness.ctx().memory().plot.values().next().unwrap().clone()
+    };
+    harness.run(&mut app);
+    let auto = plot_state(&harness);
+    assert!(auto.auto_bounds);
+    let frame = Rect::from_min_size(pos2(8.0, 8.0), vec2(200.0, 100.0)); // inside the `CentralPanel` margin
+    let transform = ScreenTransform::new(frame, auto.bounds);
+    // The data fills the plot, with a 5% margin:
+    for d in 0..2 {
+        assert!((auto.bounds.min[d] + 0.05).abs() < 1e-9);
+        assert!((auto.bounds.max[d] - 1.05).abs() < 1e-9);
+    }
+
+    // Drag the contents 20 points to the right:
+    harness.move_mouse_to(frame.center());
+    harness.run(&mut app);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(frame.center() + vec2(20.0, 0.0));
+    harness.run(&mut app);
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    let dragged = plot_state(&harness);
+    assert!(!dragged.auto_bounds);
+    let dx = 20.0 * transform.dvalue_dpos(0);
+    assert!((dragged.bounds.min[0] - (auto.bounds.min[0] - dx)).abs() < 1e-6);
+    assert!((dragged.bounds.max[0] - (auto.bounds.max[0] - dx)).abs() < 1e-6);
+    assert_eq!(dragged.bounds.range(1), auto.bounds.range(1));
+
+    // Ctrl-drag a rectangle to zoom in on it:
+    let transform = ScreenTransform::new(frame, dragged.bounds);
+    let (start, end) = (frame.left_top() + vec2(50.0, 20.0), frame.center());
+    harness.move_mouse_to(start);
+    harness.set_modifiers(Modifiers {
+        ctrl: true,
+        command: true,
+        ..Default::default()
+    });
+    harness.run(&mut app);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(end);
+    harness.run(&mut app);
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    harness.set_modifiers(Default::default());
+    harness.run(&mut app);
+    let zoomed = plot_state(&harness);
+    let (a, b) = (
+        transform.value_from_position(start),
+        transform.value_from_position(end),
+    );
+    assert_eq!(zoomed.bounds.min, [a.x, b.y]);
+    assert_eq!(zoomed.bounds.max, [b.x, a.y]);
+
+    // Double-click to fit the data again:
+    harness.click_at(frame.center(), &mut app);
+    harness.click_at(frame.center(), &mut app);
+    harness.run(&mut app);
+    assert_eq!(plot_state(&harness).bounds, auto.bounds);
+}
@@ -0,0 +1,217 @@
+use std::ops::RangeInclusive;
+
+use super::items::Value;
+use crate::*;
+
+/// 2D bounding box of `f64` precision.
+///
+/// The range of data values we show.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct Bounds {
+    pub min: [f64; 2],
+    pub max: [f64; 2],
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self::nothing()
+    }
+}
+
+impl Bounds {
+    /// An inverted bounding box, ready to be extended with values.
+    pub fn nothing() -> Self {
+        Self {
+            min: [f64::INFINITY; 2],
+            max: [-f64::INFINITY; 2],
+        }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.min[0].is_finite()
+            && self.min[1].is_finite()
+            && self.max[0].is_finite()
+            && self.max[1].is_finite()
+    }
+
+    /// Is the range non-empty (and finite) along both axes?
+    pub fn is_valid(&self) -> bool {
+        self.is_finite() && self.width() > 0.0 && self.height() > 0.0
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max[0] - self.min[0]
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max[1] - self.min[1]
+    }
+
+    pub fn range(&self, d: usize) -> RangeInclusive<f64> {
+        self.min[d]..=self.max[d]
+    }
+
+    pub fn extend_with(&mut self, value: &Value) {
+        self.extend_with_x(value.x);
+        self.extend_with_y(value.y);
+    }
+
+    pub fn extend_with_x(&mut self, x: f64) {
+        self.min[0] = self.min[0].min(x);
+        self.max[0] = self.max[0].max(x);
+    }
+
+    pub fn extend_with_y(&mut self, y: f64) {
+        self.min[1] = self.min[1].min(y);
+        self.max[1] = self.max[1].max(y);
+    }
+
+    /// Expand by the given fraction of the size on all sides.
+    pub fn add_relative_margin(&mut self, margin_fraction: f64) {
+        for d in 0..2 {
+            let margin = margin_fraction * (self.max[d] - self.min[d]);
+            self.min[d] -= margin;
+            self.max[d] += margin;
+        }
+    }
+
+    /// Make sure the range along each axis is not empty,
+    /// so that we have something to zoom and pan.
+    pub fn make_valid(&mut self) {
+        for d in 0..2 {
+            if !self.min[d].is_finite() || !self.max[d].is_finite() {
+                self.min[d] = -1.0;
+                self.max[d] = 1.0;
+            } else if self.max[d] - self.min[d] <= 0.0 {
+                let half_size = (0.5 * self.min[d].abs()).max(1.0);
+                let center = self.min[d];
+                self.min[d] = center - half_size;
+                self.max[d] = center + half_size;
+            }
+        }
+    }
+
+    pub fn translate(&mut self, delta: [f64; 2]) {
+        for (d, delta) in delta.iter().enumerate() {
+            self.min[d] += delta;
+            self.max[d] += delta;
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Maps between data values and screen positions.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ScreenTransform {
+    /// The screen rectangle.
+    frame: Rect,
+    /// The plot bounds.
+    bounds: Bounds,
+}
+
+impl ScreenTransform {
+    pub fn new(frame: Rect, bounds: Bounds) -> Self {
+        Self { frame, bounds }
+    }
+
+    pub fn frame(&self) -> &Rect {
+        &self.frame
+    }
+
+    pub fn bounds(&self) -> &Bounds {
+        &self.bounds
+    }
+
+    /// The screen range of the given axis, with `y` going up.
+    fn frame_range(&self, d: usize) -> RangeInclusive<f64> {
+        if d == 0 {
+            self.frame.left() as f64..=self.frame.right() as f64
+        } else {
+            self.frame.bottom() as f64..=self.frame.top() as f64
+        }
+    }
+
+    pub fn position_from_value(&self, value: &Value) -> Pos2 {
+        pos2(
+            remap(value.x, self.bounds.range(0), self.frame_range(0)) as f32,
+            remap(value.y, self.bounds.range(1), self.frame_range(1)) as f32,
+        )
+    }
+
+    pub fn value_from_position(&self, pos: Pos2) -> Value {
+        Value::new(
+            remap(pos.x as f64, self.frame_range(0), self.bounds.range(0)),
+            remap(pos.y as f64, self.frame_range(1), self.bounds.range(1)),
+        )
+    }
+
+    /// How many data units one point (logical pixel) corresponds to along the given axis.
+    pub fn dvalue_dpos(&self, d: usize) -> f64 {
+        let frame_size = self.frame.size()[d] as f64;
+        (self.bounds.max[d] - self.bounds.min[d]) / frame_size
+    }
+
+    /// Move the bounds so that the contents follow the mouse by `delta_pos` points.
+    pub fn translate_bounds(&mut self, delta_pos: Vec2) {
+        self.bounds.translate([
+            -delta_pos.x as f64 * self.dvalue_dpos(0),
+            delta_pos.y as f64 * self.dvalue_dpos(1),
+        ]);
+    }
+
+    /// Zoom in (`zoom_factor > 1`) or out (`zoom_factor < 1`),
+    /// keeping the value at the screen position `center` in place.
+    pub fn zoom(&mut self, zoom_factor: f32, center: Pos2) {
+        let center = self.value_from_position(center);
+        let zoom_factor = zoom_factor as f64;
+        for (d, center) in [center.x, center.y].iter().enumerate() {
+            self.bounds.min[d] = center + (self.bounds.min[d] - center) / zoom_factor;
+            self.bounds.max[d] = center + (self.bounds.max[d] - center) / zoom_factor;
+        }
+    }
+}
+
+#[test]
+fn test_screen_transform() {
+    let frame = Rect::from_min_size(pos2(100.0, 50.0), vec2(200.0, 100.0));
+    let bounds = Bounds {
+        min: [0.0, -1.0],
+        max: [4.0, 1.0],
+    };
+    let mut transform = ScreenTransform::new(frame, bounds);
+
+    // The y axis goes up on the plot, but down on the screen:
+    assert_eq!(
+        transform.position_from_value(&Value::new(0.0, -1.0)),
+        frame.left_bottom()
+    );
+    assert_eq!(
+        transform.position_from_value(&Value::new(4.0, 1.0)),
+        frame.right_top()
+    );
+    assert_eq!(
+        transform.position_from_value(&Value::new(2.0, 0.0)),
+        frame.center()
+    );
+    assert_eq!(
+        transform.value_from_position(pos2(150.0, 75.0)),
+        Value::new(1.0, 0.5)
+    );
+    assert_eq!(transform.dvalue_dpos(0), 0.02);
+    assert_eq!(transform.dvalue_dpos(1), 0.02);
+
+    // Dragging right and down moves the contents with the mouse:
+    transform.translate_bounds(vec2(50.0, 25.0));
+    assert_eq!(transform.bounds().min, [-1.0, -0.5]);
+    assert_eq!(transform.bounds().max, [3.0, 1.5]);
+
+    // Zooming keeps the value under the zoom center in place:
+    let center = pos2(150.0, 75.0);
+    let value_at_center = transform.value_from_position(center);
+    transform.zoom(2.0, center);
+    assert_eq!(transform.value_from_position(center), value_at_center);
+    assert_eq!(transform.bounds().width(), 2.0);
+    assert_eq!(transform.bounds().height(), 1.0);
+}
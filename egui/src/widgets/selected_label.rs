@@ -34,6 +34,9 @@ impl Widget for SelectableLabel {
         let mut desired_size = total_extra + galley.size;
         desired_size = desired_size.at_least(ui.style().spacing.interact_size);
         let response = ui.allocate_response(desired_size, Sense::click());
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::SelectableLabel, selected, &galley.text)
+        });
 
         let text_cursor = pos2(
             response.rect.min.x + button_padding.x,
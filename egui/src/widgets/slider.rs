@@ -266,6 +266,24 @@ impl<'a> Slider<'a> {
             }
         }
 
+        if response.has_kb_focus {
+            let steps = arrow_key_steps(ui);
+            if steps != 0.0 {
+                let value = self.get_value();
+                let new_value = if self.max_decimals == Some(0) {
+                    // Integer slider: one step per key press (towards the right end of the slider).
+                    let (start, end) = (*self.range.start(), *self.range.end());
+                    let new_value = value + steps * (end - start).signum();
+                    clamp(new_value, start.min(end)..=start.max(end))
+                } else {
+                    // Move one percent of the way along the slider per key press:
+                    let normalized = normalized_from_value(value, self.range(), &self.spec);
+                    value_from_normalized(normalized + 0.01 * steps, self.range(), &self.spec)
+                };
+                self.set_value(new_value);
+            }
+        }
+
         // Paint it:
         {
             let value = self.get_value();
@@ -379,13 +397,14 @@ impl<'a> Slider<'a> {
 
 impl<'a> Widget for Slider<'a> {
     fn ui(mut self, ui: &mut Ui) -> Response {
+        let old_value = self.get_value();
         let text_style = TextStyle::Button;
         let font = &ui.fonts()[text_style];
         let height = font
             .row_height()
             .at_least(ui.style().spacing.interact_size.y);
 
-        if self.text.is_some() {
+        let mut response = if self.text.is_some() {
             ui.horizontal(|ui| {
                 let slider_response = self.allocate_slider_space(ui, height);
                 self.slider_ui(ui, &slider_response);
@@ -399,8 +418,34 @@ impl<'a> Widget for Slider<'a> {
             let response = self.allocate_slider_space(ui, height);
             self.slider_ui(ui, &response);
             response
+        };
+
+        let value = self.get_value();
+        response.changed = value != old_value;
+        response
+            .widget_info(|| WidgetInfo::slider(value, self.text.as_deref().unwrap_or_default()));
+        response
+    }
+}
+
+/// How many steps the arrow keys asks us to move this frame:
+/// right and up increases, left and down decreases.
+pub(crate) fn arrow_key_steps(ui: &Ui) -> f64 {
+    let input = ui.input();
+    let mut steps = 0.0;
+    for event in input.unconsumed_events() {
+        if let Event::Key {
+            key, pressed: true, ..
+        } = event
+        {
+            match key {
+                Key::ArrowRight | Key::ArrowUp => steps += 1.0,
+                Key::ArrowLeft | Key::ArrowDown => steps -= 1.0,
+                _ => {}
+            }
         }
     }
+    steps
 }
 
 // ----------------------------------------------------------------------------
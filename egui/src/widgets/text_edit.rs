@@ -8,6 +8,56 @@ pub(crate) struct State {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     undoer: Undoer<(CCursorPair, String)>,
+
+    /// The text being composed with an IME, if any.
+    /// It is shown at the cursor, but not inserted until the composition ends.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ime_preedit: Option<String>,
+
+    /// What the [`TextEdit::layouter`] returned last, so we only call it again when its input changes.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cached_layout: Option<CachedLayout>,
+
+    /// Set by a double- or triple-click: dragging then extends the selection
+    /// by whole words or paragraphs from this first selected range.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    drag_selection: Option<(SelectionUnit, [CCursor; 2])>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SelectionUnit {
+    Word,
+    Paragraph,
+}
+
+/// The inputs to the [`TextEdit::layouter`], and what it returned for them.
+#[derive(Clone)]
+struct CachedLayout {
+    text: String,
+    wrap_width: f32,
+    fonts: std::sync::Arc<Fonts>,
+    style: Style,
+    layouter_state: u64,
+    galley: Galley,
+}
+
+impl CachedLayout {
+    fn is_valid(&self, ui: &Ui, text: &str, wrap_width: f32, layouter_state: u64) -> bool {
+        self.text == text
+            && self.wrap_width == wrap_width
+            && self.layouter_state == layouter_state
+            && std::sync::Arc::ptr_eq(&self.fonts, &ui.ctx().fonts_arc())
+            && self.style == *ui.style()
+    }
+}
+
+impl std::fmt::Debug for CachedLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedLayout")
+            .field("text", &self.text)
+            .field("wrap_width", &self.wrap_width)
+            .finish()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -118,12 +168,18 @@ impl CCursorPair {
 #[derive(Debug)]
 pub struct TextEdit<'t> {
     text: &'t mut String,
+    layouter: Option<Layouter<'t>>,
+    layouter_state: u64,
     id: Option<Id>,
     id_source: Option<Id>,
     text_style: Option<TextStyle>,
     text_color: Option<Color32>,
+    hint_text: String,
     multiline: bool,
+    password: bool,
     enabled: bool,
+    char_limit: usize,
+    char_filter: Option<fn(char) -> bool>,
     desired_width: Option<f32>,
     desired_height_rows: usize,
 }
@@ -138,12 +194,18 @@ impl<'t> TextEdit<'t> {
     pub fn singleline(text: &'t mut String) -> Self {
         TextEdit {
             text,
+            layouter: None,
+            layouter_state: 0,
             id: None,
             id_source: None,
             text_style: None,
             text_color: None,
+            hint_text: Default::default(),
             multiline: false,
+            password: false,
             enabled: true,
+            char_limit: usize::MAX,
+            char_filter: None,
             desired_width: None,
             desired_height_rows: 1,
         }
@@ -153,12 +215,18 @@ impl<'t> TextEdit<'t> {
     pub fn multiline(text: &'t mut String) -> Self {
         TextEdit {
             text,
+            layouter: None,
+            layouter_state: 0,
             id: None,
             id_source: None,
             text_style: None,
             text_color: None,
+            hint_text: Default::default(),
             multiline: true,
+            password: false,
             enabled: true,
+            char_limit: usize::MAX,
+            char_filter: None,
             desired_width: None,
             desired_height_rows: 4,
         }
@@ -190,6 +258,33 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Show a faint hint text when the `TextEdit` is empty, e.g. `"Search…"`.
+    pub fn hint_text(mut self, hint_text: impl Into<String>) -> Self {
+        self.hint_text = hint_text.into();
+        self
+    }
+
+    /// If `true`, every character is shown as a `•`, and the text cannot be copied or cut.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Allow at most this many characters (not bytes). Longer input is cut short.
+    pub fn char_limit(mut self, char_limit: usize) -> Self {
+        self.char_limit = char_limit;
+        self
+    }
+
+    /// Only let the user type characters for which this returns `true`,
+    /// e.g. `.char_filter(|c| c.is_ascii_digit())`.
+    ///
+    /// This only filters new input, not the text you pass in.
+    pub fn char_filter(mut self, char_filter: fn(char) -> bool) -> Self {
+        self.char_filter = Some(char_filter);
+        self
+    }
+
     /// Default is `true`. If set to `false` then you cannot edit the text.
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -202,6 +297,41 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Lay out the text yourself, e.g. to highlight syntax with different colors and fonts.
+    ///
+    /// The layouter is given the text and the width to wrap it at (infinity for singleline),
+    /// and must return a [`Galley`] of exactly that text, e.g. from [`Fonts::layout_job`].
+    /// It is only called again when the text, the wrap width, the fonts or the [`Style`] changes,
+    /// or whatever you pass to [`Self::layouter_state`].
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// # let mut code = String::new();
+    /// use egui::paint::{LayoutJob, TextFormat};
+    /// let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+    ///     let mut job = LayoutJob::default();
+    ///     for (i, word) in text.split_inclusive(' ').enumerate() {
+    ///         let color = if i % 2 == 0 { egui::Color32::RED } else { egui::Color32::BLUE };
+    ///         let format = TextFormat::text_style(ui.fonts(), egui::TextStyle::Monospace, color);
+    ///         job.append(word, format);
+    ///     }
+    ///     job.wrap_width = wrap_width;
+    ///     ui.fonts().layout_job(job)
+    /// };
+    /// ui.add(egui::TextEdit::multiline(&mut code).layouter(&mut layouter));
+    /// ```
+    pub fn layouter(mut self, layouter: &'t mut dyn FnMut(&Ui, &str, f32) -> Galley) -> Self {
+        self.layouter = Some(Layouter(layouter));
+        self
+    }
+
+    /// Anything else the [`Self::layouter`] depends on, e.g. the syntax highlighting theme.
+    /// The layouter is called again when this changes.
+    pub fn layouter_state(mut self, state: impl std::hash::Hash) -> Self {
+        self.layouter_state = crate::util::cache::hash(state);
+        self
+    }
+
     /// Set the number of rows to show by default.
     /// The default for singleline text is `1`.
     /// The default for multiline text is `4`.
@@ -215,57 +345,94 @@ impl<'t> Widget for TextEdit<'t> {
     fn ui(self, ui: &mut Ui) -> Response {
         let TextEdit {
             text,
+            mut layouter,
+            layouter_state,
             id,
             id_source,
             text_style,
             text_color,
+            hint_text,
             multiline,
+            password,
             enabled,
+            char_limit,
+            char_filter,
             desired_width,
             desired_height_rows,
         } = self;
 
         let text_style = text_style.unwrap_or_else(|| ui.style().body_text_style);
-        let font = &ui.fonts()[text_style];
-        let line_spacing = font.row_height();
+        let text_color = text_color
+            .or(ui.style().visuals.override_text_color)
+            // .unwrap_or_else(|| ui.style().interact(&response).text_color()); // too bright
+            .unwrap_or_else(|| ui.style().visuals.widgets.inactive.text_color());
+        let line_spacing = ui.fonts()[text_style].row_height();
         let available_width = ui.available_width();
-        let mut galley = if multiline {
-            font.layout_multiline(text.clone(), available_width)
+        let format = TextFormat::text_style(ui.fonts(), text_style, text_color);
+        let layout_job = |ui: &Ui, mut job: LayoutJob| {
+            if multiline {
+                job.wrap_width = available_width;
+            } else {
+                job.break_on_newline = false;
+            }
+            ui.fonts().layout_job(job)
+        };
+        let wrap_width = if multiline {
+            available_width
         } else {
-            font.layout_single_line(text.clone())
+            f32::INFINITY
+        };
+        let mut layout = |ui: &Ui, text: &str, cached: &mut Option<CachedLayout>| {
+            if password {
+                layout_job(ui, LayoutJob::single_section(mask(text), format))
+            } else if let Some(Layouter(layouter)) = &mut layouter {
+                match cached {
+                    Some(cached) if cached.is_valid(ui, text, wrap_width, layouter_state) => {}
+                    _ => {
+                        *cached = Some(CachedLayout {
+                            text: text.to_owned(),
+                            wrap_width,
+                            fonts: ui.ctx().fonts_arc(),
+                            style: ui.style().clone(),
+                            layouter_state,
+                            galley: layouter(ui, text, wrap_width),
+                        })
+                    }
+                }
+                cached.as_ref().unwrap().galley.clone()
+            } else {
+                layout_job(ui, LayoutJob::single_section(text.to_owned(), format))
+            }
         };
-
-        let desired_width = desired_width.unwrap_or_else(|| ui.style().spacing.text_edit_width);
-        let desired_height = (desired_height_rows.at_least(1) as f32) * line_spacing;
-        let desired_size = vec2(
-            galley.size.x.max(desired_width.min(available_width)),
-            galley.size.y.max(desired_height),
-        );
-        let (auto_id, rect) = ui.allocate_space(desired_size);
 
         let id = id.unwrap_or_else(|| {
             if let Some(id_source) = id_source {
                 ui.make_persistent_id(id_source)
             } else {
-                auto_id // Since we are only storing the cursor, perfect persistence Id not super important
+                ui.next_auto_id() // Since we are only storing the cursor, perfect persistence Id not super important
             }
         });
         let mut state = ui.memory().text_edit.get(&id).cloned().unwrap_or_default();
 
+        let mut galley = layout(ui, text, &mut state.cached_layout);
+
+        let desired_width = desired_width.unwrap_or_else(|| ui.style().spacing.text_edit_width);
+        let desired_height = (desired_height_rows.at_least(1) as f32) * line_spacing;
+        let desired_size = vec2(
+            galley.size.x.max(desired_width.min(available_width)),
+            galley.size.y.max(desired_height),
+        );
+        let (_, rect) = ui.allocate_space(desired_size);
+
         let sense = if enabled {
-            Sense::click_and_drag()
+            Sense::click_and_drag().text_input()
         } else {
             Sense::hover()
         };
         let response = ui.interact(rect, id, sense);
 
-        if enabled {
-            ui.memory().interested_in_kb_focus(id);
-        }
-
         if enabled {
             if let Some(mouse_pos) = ui.input().mouse.pos {
-                // TODO: triple-click to select whole paragraph
                 // TODO: drag selected text to either move or clone (ctrl on windows, alt on mac)
 
                 let cursor_at_mouse = galley.cursor_from_pos(mouse_pos - response.rect.min);
@@ -275,17 +442,23 @@ impl<'t> Widget for TextEdit<'t> {
                     paint_cursor_end(ui, response.rect.min, &galley, &cursor_at_mouse);
                 }
 
-                if response.hovered && response.double_clicked {
-                    // Select word:
-                    let center = cursor_at_mouse;
-                    let ccursorp = select_word_at(text, center.ccursor);
-                    state.cursorp = Some(CursorPair {
-                        primary: galley.from_ccursor(ccursorp.primary),
-                        secondary: galley.from_ccursor(ccursorp.secondary),
-                    });
-                } else if response.hovered && ui.input().mouse.pressed {
+                if response.hovered && ui.input().mouse.pressed {
                     ui.memory().request_kb_focus(id);
-                    if ui.input().modifiers.shift {
+                    let unit = match ui.input().mouse.press_count {
+                        0 | 1 => None,
+                        2 => Some(SelectionUnit::Word),
+                        _ => Some(SelectionUnit::Paragraph),
+                    };
+                    state.drag_selection = None;
+                    if let Some(unit) = unit {
+                        let [min, max] =
+                            unit_range_at(unit, text, &galley, &cursor_at_mouse, password);
+                        state.cursorp = Some(CursorPair::two(
+                            galley.from_ccursor(min),
+                            galley.from_ccursor(max),
+                        ));
+                        state.drag_selection = Some((unit, [min, max]));
+                    } else if ui.input().modifiers.shift {
                         if let Some(cursorp) = &mut state.cursorp {
                             cursorp.primary = cursor_at_mouse;
                         } else {
@@ -296,7 +469,30 @@ impl<'t> Widget for TextEdit<'t> {
                     }
                 } else if ui.input().mouse.down && response.active {
                     if let Some(cursorp) = &mut state.cursorp {
-                        cursorp.primary = cursor_at_mouse;
+                        if let Some((unit, [anchor_min, anchor_max])) = state.drag_selection {
+                            // Extend the selection by whole words/paragraphs, away from the first one:
+                            let [min, max] =
+                                unit_range_at(unit, text, &galley, &cursor_at_mouse, password);
+                            *cursorp = if min.index < anchor_min.index {
+                                CursorPair {
+                                    primary: galley.from_ccursor(min),
+                                    secondary: galley.from_ccursor(anchor_max),
+                                }
+                            } else {
+                                CursorPair {
+                                    primary: galley.from_ccursor(if max.index > anchor_max.index {
+                                        max
+                                    } else {
+                                        anchor_max
+                                    }),
+                                    secondary: galley.from_ccursor(anchor_min),
+                                }
+                            };
+                        } else {
+                            cursorp.primary = cursor_at_mouse;
+                        }
+
+                        scroll_to_drag_selection(ui, &response, &galley, cursorp, mouse_pos);
                     }
                 }
             }
@@ -315,6 +511,7 @@ impl<'t> Widget for TextEdit<'t> {
             ui.output().cursor_icon = CursorIcon::Text;
         }
 
+        let mut changed = false;
         if ui.memory().has_kb_focus(id) && enabled {
             let mut cursorp = state
                 .cursorp
@@ -339,8 +536,16 @@ impl<'t> Widget for TextEdit<'t> {
                 .undoer
                 .feed_state(ui.input().time, &(cursorp.as_ccursorp(), text.clone()));
 
-            for event in &ui.input().events {
+            let text_before = text.clone();
+
+            for event in ui.input().unconsumed_events() {
                 let did_mutate_text = match event {
+                    Event::Key { .. } if state.ime_preedit.is_some() => {
+                        None // The IME is using the keys
+                    }
+                    Event::Copy | Event::Cut if password => {
+                        None // Don't leak the password
+                    }
                     Event::Copy => {
                         if cursorp.is_empty() {
                             ui.ctx().output().copied_text = text.clone();
@@ -364,9 +569,8 @@ impl<'t> Widget for TextEdit<'t> {
                             && text_to_insert != "\n"
                             && text_to_insert != "\r"
                         {
-                            let mut ccursor = delete_selected(text, &cursorp);
-                            insert_text(&mut ccursor, text, text_to_insert);
-                            Some(CCursorPair::one(ccursor))
+                            let text_to_insert = filter_chars(text_to_insert, char_filter);
+                            insert_limited(text, &cursorp, &text_to_insert, char_limit)
                         } else {
                             None
                         }
@@ -377,9 +581,7 @@ impl<'t> Widget for TextEdit<'t> {
                         ..
                     } => {
                         if multiline {
-                            let mut ccursor = delete_selected(text, &cursorp);
-                            insert_text(&mut ccursor, text, "\n");
-                            Some(CCursorPair::one(ccursor))
+                            insert_limited(text, &cursorp, "\n", char_limit)
                         } else {
                             // Common to end input with enter
                             ui.memory().surrender_kb_focus(id);
@@ -417,17 +619,26 @@ impl<'t> Widget for TextEdit<'t> {
                         modifiers,
                     } => on_key_press(&mut cursorp, text, &galley, *key, modifiers),
 
-                    Event::Key { .. } => None,
+                    Event::CompositionStart => {
+                        state.ime_preedit = Some(String::new());
+                        None
+                    }
+                    Event::CompositionUpdate(preedit) => {
+                        state.ime_preedit = Some(preedit.clone());
+                        None
+                    }
+                    Event::CompositionEnd(committed) => {
+                        state.ime_preedit = None;
+                        let committed = filter_chars(committed, char_filter);
+                        insert_limited(text, &cursorp, &committed, char_limit)
+                    }
+
+                    Event::Key { .. } | Event::Touch { .. } => None,
                 };
 
                 if let Some(new_ccursorp) = did_mutate_text {
                     // Layout again to avoid frame delay, and to keep `text` and `galley` in sync.
-                    let font = &ui.fonts()[text_style];
-                    galley = if multiline {
-                        font.layout_multiline(text.clone(), available_width)
-                    } else {
-                        font.layout_single_line(text.clone())
-                    };
+                    galley = layout(ui, text, &mut state.cached_layout);
 
                     // Set cursorp using new galley:
                     cursorp = CursorPair {
@@ -441,6 +652,8 @@ impl<'t> Widget for TextEdit<'t> {
             state
                 .undoer
                 .feed_state(ui.input().time, &(cursorp.as_ccursorp(), text.clone()));
+
+            changed = *text != text_before;
         }
 
         {
@@ -455,24 +668,61 @@ impl<'t> Widget for TextEdit<'t> {
             });
         }
 
-        if ui.memory().has_kb_focus(id) {
-            if let Some(cursorp) = state.cursorp {
-                paint_cursor_selection(ui, response.rect.min, &galley, &cursorp);
-                paint_cursor_end(ui, response.rect.min, &galley, &cursorp.primary);
+        if !ui.memory().has_kb_focus(id) {
+            state.ime_preedit = None;
+        } else if let Some(cursorp) = state.cursorp {
+            let cursor_rect = galley
+                .pos_from_cursor(&cursorp.primary)
+                .translate(response.rect.min.to_vec2());
+            let viewport_id = ui.ctx().viewport_id();
+            ui.ctx().frame_state().text_cursor_rect = Some((viewport_id, cursor_rect));
+
+            match state.ime_preedit.as_deref() {
+                Some(preedit) if !preedit.is_empty() => {
+                    // Show the text being composed underlined at the cursor,
+                    // without adding it to `text` yet.
+                    // It is laid out like the rest of the text (so with the layouter, if any),
+                    // but not cached, since it only lives until the composition ends:
+                    let ccursor = cursorp.primary.ccursor;
+                    let mut composed = text.clone();
+                    composed.insert_str(byte_index_from_char_index(text, ccursor.index), preedit);
+                    galley = layout(ui, &composed, &mut None);
+                    let preedit_range = CursorPair {
+                        primary: galley.from_ccursor(ccursor + preedit.chars().count()),
+                        secondary: galley.from_ccursor(ccursor),
+                    };
+                    paint_underline(ui, response.rect.min, &galley, &preedit_range, text_color);
+                    paint_cursor_end(ui, response.rect.min, &galley, &preedit_range.primary);
+                }
+                _ => {
+                    paint_cursor_selection(ui, response.rect.min, &galley, &cursorp);
+                    paint_cursor_end(ui, response.rect.min, &galley, &cursorp.primary);
+                }
             }
         }
 
-        let text_color = text_color
-            .or(ui.style().visuals.override_text_color)
-            // .unwrap_or_else(|| ui.style().interact(&response).text_color()); // too bright
-            .unwrap_or_else(|| ui.style().visuals.widgets.inactive.text_color());
+        if text.is_empty() && !hint_text.is_empty() && state.ime_preedit.is_none() {
+            let hint_color = ui.style().visuals.widgets.disabled.text_color();
+            let hint_format = TextFormat::text_style(ui.fonts(), text_style, hint_color);
+            let hint_galley = layout_job(ui, LayoutJob::single_section(hint_text, hint_format));
+            ui.painter()
+                .galley(response.rect.min, hint_galley, text_style, hint_color);
+        }
+
         ui.painter()
             .galley(response.rect.min, galley, text_style, text_color);
 
         ui.memory().text_edit.insert(id, state);
 
+        if password {
+            response.widget_info(|| WidgetInfo::text_edit(mask(text)));
+        } else {
+            response.widget_info(|| WidgetInfo::text_edit(text.as_str()));
+        }
+
         Response {
             lost_kb_focus: ui.memory().lost_kb_focus(id), // we may have lost it during the course of this function
+            changed,
             ..response
         }
     }
@@ -480,6 +730,51 @@ impl<'t> Widget for TextEdit<'t> {
 
 // ----------------------------------------------------------------------------
 
+/// See [`TextEdit::layouter`].
+struct Layouter<'t>(&'t mut dyn FnMut(&Ui, &str, f32) -> Galley);
+
+impl std::fmt::Debug for Layouter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Layouter")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// When selecting beyond the edge of the enclosing [`ScrollArea`], scroll it along.
+fn scroll_to_drag_selection(
+    ui: &Ui,
+    response: &Response,
+    galley: &Galley,
+    cursorp: &CursorPair,
+    mouse_pos: Pos2,
+) {
+    let cursor_rect = galley
+        .pos_from_cursor(&cursorp.primary)
+        .translate(response.rect.min.to_vec2());
+    let clip_rect = ui.clip_rect();
+    let mut scrolling = false;
+    for d in 0..2 {
+        let scroll_target = if mouse_pos[d] < clip_rect.min[d] {
+            Some((cursor_rect.min[d], Align::Min))
+        } else if mouse_pos[d] > clip_rect.max[d] {
+            Some((cursor_rect.max[d], Align::Max))
+        } else {
+            None
+        };
+        if scroll_target.is_some() {
+            ui.ctx().frame_state().scroll_target[d] = scroll_target;
+            scrolling = true;
+        }
+    }
+    if scrolling {
+        // Keep scrolling even if the mouse stands still:
+        ui.ctx().request_repaint();
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 fn paint_cursor_selection(ui: &mut Ui, pos: Pos2, galley: &Galley, cursorp: &CursorPair) {
     let color = ui.style().visuals.selection.bg_fill;
     if cursorp.is_empty() {
@@ -511,6 +806,31 @@ fn paint_cursor_selection(ui: &mut Ui, pos: Pos2, galley: &Galley, cursorp: &Cur
     }
 }
 
+/// Underline the text between the two cursors, e.g. the text being composed with an IME.
+fn paint_underline(ui: &mut Ui, pos: Pos2, galley: &Galley, cursorp: &CursorPair, color: Color32) {
+    let [min, max] = cursorp.sorted();
+    let min = min.rcursor;
+    let max = max.rcursor;
+
+    for ri in min.row..=max.row {
+        let row = &galley.rows[ri];
+        let left = if ri == min.row {
+            row.x_offset(min.column)
+        } else {
+            row.min_x()
+        };
+        let right = if ri == max.row {
+            row.x_offset(max.column)
+        } else {
+            row.max_x()
+        };
+        ui.painter().line_segment(
+            [pos + vec2(left, row.y_max), pos + vec2(right, row.y_max)],
+            (1.0, color),
+        );
+    }
+}
+
 fn paint_cursor_end(ui: &mut Ui, pos: Pos2, galley: &Galley, cursor: &Cursor) {
     let stroke = ui.style().visuals.selection.stroke;
 
@@ -558,6 +878,40 @@ fn byte_index_from_char_index(s: &str, char_index: usize) -> usize {
     s.len()
 }
 
+/// What a password looks like on screen: one `•` per character.
+fn mask(text: &str) -> String {
+    "•".repeat(text.chars().count())
+}
+
+fn filter_chars(text: &str, char_filter: Option<fn(char) -> bool>) -> String {
+    match char_filter {
+        Some(char_filter) => text.chars().filter(|&c| char_filter(c)).collect(),
+        None => text.to_owned(),
+    }
+}
+
+/// Replace the selection with as much of `text_to_insert` as fits within `char_limit`.
+///
+/// Returns `None` if nothing could be inserted, in which case `text` is left as is.
+fn insert_limited(
+    text: &mut String,
+    cursorp: &CursorPair,
+    text_to_insert: &str,
+    char_limit: usize,
+) -> Option<CCursorPair> {
+    let [min, max] = cursorp.sorted();
+    let num_chars_kept = text.chars().count() - (max.ccursor.index - min.ccursor.index);
+    let room = char_limit.saturating_sub(num_chars_kept);
+    let byte_end = byte_index_from_char_index(text_to_insert, room);
+    let text_to_insert = &text_to_insert[..byte_end];
+    if text_to_insert.is_empty() {
+        return None;
+    }
+    let mut ccursor = delete_selected(text, cursorp);
+    insert_text(&mut ccursor, text, text_to_insert);
+    Some(CCursorPair::one(ccursor))
+}
+
 fn insert_text(ccursor: &mut CCursor, text: &mut String, text_to_insert: &str) {
     let mut char_it = text.chars();
     let mut new_text = String::with_capacity(text.len() + text_to_insert.len());
@@ -626,11 +980,7 @@ fn delete_paragraph_before_cursor(
     cursorp: &CursorPair,
 ) -> CCursor {
     let [min, max] = cursorp.sorted();
-    let min = galley.from_pcursor(PCursor {
-        paragraph: min.pcursor.paragraph,
-        offset: 0,
-        prefer_next_row: true,
-    });
+    let [min, _] = paragraph_range_at(galley, &min);
     if min.ccursor == max.ccursor {
         delete_previous_char(text, min.ccursor)
     } else {
@@ -644,11 +994,7 @@ fn delete_paragraph_after_cursor(
     cursorp: &CursorPair,
 ) -> CCursor {
     let [min, max] = cursorp.sorted();
-    let max = galley.from_pcursor(PCursor {
-        paragraph: max.pcursor.paragraph,
-        offset: usize::MAX, // end of paragraph
-        prefer_next_row: false,
-    });
+    let [_, max] = paragraph_range_at(galley, &max);
     if min.ccursor == max.ccursor {
         delete_next_char(text, min.ccursor)
     } else {
@@ -801,6 +1147,47 @@ fn move_single_cursor(cursor: &mut Cursor, galley: &Galley, key: Key, modifiers:
 
 // ----------------------------------------------------------------------------
 
+/// The word or paragraph at `cursor`, as `[min, max]`.
+///
+/// In a password everything is selected, so we don't reveal where the words are.
+fn unit_range_at(
+    unit: SelectionUnit,
+    text: &str,
+    galley: &Galley,
+    cursor: &Cursor,
+    password: bool,
+) -> [CCursor; 2] {
+    if password {
+        return [CCursor::default(), galley.end().ccursor];
+    }
+    match unit {
+        SelectionUnit::Word => {
+            let ccursorp = select_word_at(text, cursor.ccursor);
+            [ccursorp.secondary, ccursorp.primary]
+        }
+        SelectionUnit::Paragraph => {
+            let [min, max] = paragraph_range_at(galley, cursor);
+            [min.ccursor, max.ccursor]
+        }
+    }
+}
+
+/// The start and end of the paragraph `cursor` is in, not including the newline.
+fn paragraph_range_at(galley: &Galley, cursor: &Cursor) -> [Cursor; 2] {
+    let paragraph = cursor.pcursor.paragraph;
+    let min = galley.from_pcursor(PCursor {
+        paragraph,
+        offset: 0,
+        prefer_next_row: true,
+    });
+    let max = galley.from_pcursor(PCursor {
+        paragraph,
+        offset: usize::MAX, // end of paragraph
+        prefer_next_row: false,
+    });
+    [min, max]
+}
+
 fn select_word_at(text: &str, ccursor: CCursor) -> CCursorPair {
     if ccursor.index == 0 {
         CCursorPair::two(ccursor, ccursor_next_word(text, ccursor))
@@ -873,3 +1260,265 @@ fn next_word_boundary_char_index(it: impl Iterator<Item = char>, mut index: usiz
 fn is_word_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || c == '_'
 }
+
+#[test]
+fn test_ime_composition() {
+    let mut text = "ab".to_owned();
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut texts = vec![];
+    {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.text_edit_singleline(&mut text);
+            });
+            texts.push(text.clone());
+        };
+        harness.run(&mut app);
+        let rect = harness
+            .widgets_of_type(WidgetType::TextEdit)
+            .next()
+            .unwrap()
+            .rect;
+        harness.click_at(rect.center(), &mut app);
+        harness.press_key(Key::End);
+        harness.run(&mut app);
+        let cursor_rect = harness.output().text_cursor_rect.unwrap();
+        assert!(rect.expand(1.0).contains(cursor_rect.center()));
+
+        harness.event(Event::CompositionStart);
+        harness.event(Event::CompositionUpdate("にほん".to_owned()));
+        harness.run(&mut app);
+        harness.press_key(Key::Backspace); // goes to the IME, not the text
+        harness.event(Event::CompositionUpdate("日本".to_owned()));
+        harness.run(&mut app);
+        assert_eq!(harness.output().text_cursor_rect, Some(cursor_rect));
+        harness.event(Event::CompositionEnd("日本".to_owned()));
+        harness.run(&mut app);
+    }
+    let len = texts.len();
+    assert_eq!(texts[len - 3..], ["ab", "ab", "ab日本"]);
+}
+
+#[test]
+fn test_layouter() {
+    let mut code = "let x = 1;".to_owned();
+    let mut calls = 0;
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    {
+        let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+            calls += 1;
+            let mut job = LayoutJob::default();
+            for (i, word) in text.split_inclusive(' ').enumerate() {
+                let (text_style, color) = if i == 0 {
+                    (TextStyle::Heading, Color32::RED)
+                } else {
+                    (TextStyle::Monospace, Color32::WHITE)
+                };
+                job.append(word, TextFormat::text_style(ui.fonts(), text_style, color));
+            }
+            job.wrap_width = wrap_width;
+            ui.fonts().layout_job(job)
+        };
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(TextEdit::multiline(&mut code).layouter(&mut layouter));
+            });
+        };
+        harness.run(&mut app);
+        harness.run(&mut app);
+        let rect = harness
+            .widgets_of_type(WidgetType::TextEdit)
+            .next()
+            .unwrap()
+            .rect;
+        harness.click_at(rect.left_top() + vec2(1.0, 1.0), &mut app);
+        harness.press_key(Key::End);
+        harness.type_text("2");
+        harness.run(&mut app);
+        harness.run(&mut app);
+    }
+    assert_eq!(code, "let x = 1;2");
+    assert_eq!(
+        calls, 2,
+        "The layouter should only be called when the text changes"
+    );
+}
+
+#[test]
+fn test_layouter_is_called_again_when_its_inputs_change() {
+    let mut code = "let x = 1;".to_owned();
+    let theme = std::cell::Cell::new("dark");
+    let mut laid_out = vec![];
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    {
+        let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+            laid_out.push(text.to_owned());
+            let format = TextFormat::text_style(ui.fonts(), TextStyle::Monospace, Color32::WHITE);
+            let mut job = LayoutJob::single_section(text.to_owned(), format);
+            job.wrap_width = wrap_width;
+            ui.fonts().layout_job(job)
+        };
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut code)
+                        .layouter(&mut layouter)
+                        .layouter_state(theme.get()),
+                );
+            });
+        };
+        harness.run(&mut app);
+        harness.run(&mut app);
+
+        theme.set("light");
+        harness.run(&mut app);
+
+        let mut style = (*harness.ctx().style()).clone();
+        style.visuals.dark_bg_color = Color32::WHITE;
+        harness.ctx().set_style(style);
+        harness.run(&mut app);
+
+        let mut fonts = FontDefinitions::default();
+        fonts
+            .family_and_size
+            .insert(TextStyle::Monospace, (FontFamily::Monospace, 20.0));
+        harness.ctx().set_fonts(fonts);
+        harness.run(&mut app);
+        harness.run(&mut app);
+
+        // The text being composed with an IME is laid out with the layouter too:
+        let rect = harness
+            .widgets_of_type(WidgetType::TextEdit)
+            .next()
+            .unwrap()
+            .rect;
+        harness.click_at(rect.left_top() + vec2(1.0, 1.0), &mut app);
+        harness.press_key(Key::End);
+        harness.event(Event::CompositionStart);
+        harness.event(Event::CompositionUpdate("2".to_owned()));
+        harness.run(&mut app);
+    }
+    assert_eq!(
+        laid_out,
+        vec![
+            "let x = 1;",
+            "let x = 1;",
+            "let x = 1;",
+            "let x = 1;",
+            "let x = 1;2"
+        ],
+        "Once at the start, then for the theme, style, fonts and composition"
+    );
+}
+
+#[test]
+fn test_password_limit_and_filter() {
+    let mut password = "hunter2".to_owned();
+    let mut pin = "12".to_owned();
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut changed = vec![];
+    {
+        let mut app = |ctx: &CtxRef| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(TextEdit::singleline(&mut password).password(true));
+                let response = ui.add(
+                    TextEdit::singleline(&mut pin)
+                        .hint_text("PIN")
+                        .char_limit(4)
+                        .char_filter(|c| c.is_ascii_digit()),
+                );
+                changed.push(response.changed);
+            });
+        };
+        harness.run(&mut app);
+        let text_edits: Vec<_> = harness.widgets_of_type(WidgetType::TextEdit).collect();
+        let password_info = text_edits[0].info.as_ref().unwrap();
+        assert_eq!(password_info.text_value.as_deref(), Some("•••••••"));
+        let (password_rect, pin_rect) = (text_edits[0].rect, text_edits[1].rect);
+
+        harness.click_at(password_rect.center(), &mut app);
+        harness.event(Event::Copy);
+        harness.event(Event::Cut);
+        harness.run(&mut app);
+        assert_eq!(harness.output().copied_text, "");
+
+        harness.click_at(pin_rect.center(), &mut app);
+        harness.press_key(Key::End);
+        harness.run(&mut app);
+        harness.type_text("a3b456");
+        harness.run(&mut app);
+        harness.run(&mut app);
+    }
+    assert_eq!(password, "hunter2");
+    assert_eq!(pin, "1234");
+    let len = changed.len();
+    assert_eq!(changed[len - 3..], [false, true, false]);
+}
+
+#[test]
+fn test_mouse_selection() {
+    let mut text = "one two three\nfour five".to_owned();
+    let mut long_text = (0..30)
+        .map(|i| format!("line {}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut harness = crate::testing::Harness::new(vec2(400.0, 300.0));
+    let mut app = |ctx: &CtxRef| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.text_edit_multiline(&mut text);
+            ScrollArea::from_max_height(60.0).show(ui, |ui| {
+                ui.text_edit_multiline(&mut long_text);
+            });
+        });
+    };
+    let copy = |harness: &mut crate::testing::Harness, app: &mut dyn FnMut(&CtxRef)| {
+        harness.event(Event::Copy);
+        harness.run(app);
+        harness.output().copied_text.clone()
+    };
+    harness.run(&mut app);
+    let rects: Vec<Rect> = harness
+        .widgets_of_type(WidgetType::TextEdit)
+        .map(|widget| widget.rect)
+        .collect();
+    let row_height = harness.ctx().fonts()[TextStyle::Body].row_height();
+    let first_row = rects[0].left_top() + vec2(2.0, 0.5 * row_height);
+    let second_row = first_row + vec2(0.0, row_height);
+
+    harness.click_at(first_row, &mut app);
+    harness.click_at(first_row, &mut app);
+    assert_eq!(copy(&mut harness, &mut app), "one");
+    harness.click_at(first_row, &mut app);
+    assert_eq!(copy(&mut harness, &mut app), "one two three");
+
+    // Dragging after a double-click extends the selection by whole words:
+    harness.advance_time(1.0);
+    harness.click_at(first_row, &mut app);
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(second_row);
+    harness.run(&mut app);
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    assert_eq!(copy(&mut harness, &mut app), "one two three\nfour");
+
+    // Selecting beyond the bottom of the `ScrollArea` scrolls it:
+    harness.advance_time(1.0);
+    harness.move_mouse_to(rects[1].left_top() + vec2(2.0, 0.5 * row_height));
+    harness.set_mouse_down(true);
+    harness.run(&mut app);
+    harness.move_mouse_to(rects[1].left_top() + vec2(2.0, 100.0));
+    for _ in 0..5 {
+        assert!(harness.run(&mut app).needs_repaint);
+    }
+    harness.set_mouse_down(false);
+    harness.run(&mut app);
+    let scrolled_rect = harness
+        .widgets_of_type(WidgetType::TextEdit)
+        .nth(1)
+        .unwrap()
+        .rect;
+    assert!(scrolled_rect.top() < rects[1].top());
+    let selected = copy(&mut harness, &mut app);
+    assert!(selected.contains("line 1\nline 2\nline 3\nline 4\nline 5\n"));
+}
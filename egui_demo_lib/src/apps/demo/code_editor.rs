@@ -0,0 +1,105 @@
+use egui::{
+    paint::{LayoutJob, TextFormat},
+    *,
+};
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CodeEditor {
+    code: String,
+}
+
+impl Default for CodeEditor {
+    fn default() -> Self {
+        Self {
+            code: r#"-- Greet everyone:
+local names = { "Ada", "Grace", 'Edsger' }
+for i, name in ipairs(names) do
+    if i > 1 then
+        print("Hello again, " .. name)
+    else
+        print("Hello, " .. name)
+    end
+end
+return 42
+"#
+            .to_owned(),
+        }
+    }
+}
+
+impl super::Demo for CodeEditor {
+    fn name(&self) -> &str {
+        "🖮 Code Editor"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(400.0, 300.0))
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for CodeEditor {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("A Lua editor, highlighted with a custom TextEdit::layouter.");
+        ui.label("The layouter only runs again when you change the code.");
+
+        let mut layouter = |ui: &Ui, code: &str, wrap_width: f32| {
+            let mut job = highlight(ui, code);
+            job.wrap_width = wrap_width;
+            ui.fonts().layout_job(job)
+        };
+
+        ScrollArea::auto_sized().show(ui, |ui| {
+            ui.add(
+                TextEdit::multiline(&mut self.code)
+                    .text_style(TextStyle::Monospace)
+                    .desired_width(f32::INFINITY)
+                    .layouter(&mut layouter),
+            );
+        });
+    }
+}
+
+/// A tiny Lua highlighter.
+fn highlight(ui: &Ui, code: &str) -> LayoutJob {
+    const KEYWORDS: &[&str] = &[
+        "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in",
+        "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+    ];
+    let format = |color| TextFormat::text_style(ui.fonts(), TextStyle::Monospace, color);
+
+    let mut job = LayoutJob::default();
+    let mut rest = code;
+    while let Some(first) = rest.chars().next() {
+        let (len, color) = if rest.starts_with("--") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            (len, Color32::GRAY)
+        } else if first == '"' || first == '\'' {
+            let len = rest[1..].find(first).map_or(rest.len(), |end| end + 2);
+            (len, Color32::from_rgb(170, 220, 120))
+        } else if first.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+                .unwrap_or(rest.len());
+            (len, Color32::from_rgb(240, 180, 100))
+        } else if first.is_alphabetic() || first == '_' {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            if KEYWORDS.contains(&&rest[..len]) {
+                (len, Color32::from_rgb(240, 120, 160))
+            } else {
+                (len, Color32::LIGHT_GRAY)
+            }
+        } else {
+            (first.len_utf8(), Color32::LIGHT_GRAY)
+        };
+        job.append(&rest[..len], format(color));
+        rest = &rest[len..];
+    }
+    job
+}
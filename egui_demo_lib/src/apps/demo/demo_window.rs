@@ -11,6 +11,7 @@ pub struct DemoWindow {
     scrolls: Scrolls,
     colors: ColorWidgets,
     layout: LayoutDemo,
+    grid: GridDemo,
     tree: Tree,
     box_painting: BoxPainting,
 }
@@ -24,6 +25,7 @@ impl Default for DemoWindow {
             widgets: Default::default(),
             colors: Default::default(),
             layout: Default::default(),
+            grid: Default::default(),
             tree: Tree::demo(),
             box_painting: Default::default(),
         }
@@ -48,6 +50,10 @@ impl DemoWindow {
             .default_open(false)
             .show(ui, |ui| self.layout.ui(ui));
 
+        CollapsingHeader::new("Grid")
+            .default_open(false)
+            .show(ui, |ui| self.grid.ui(ui));
+
         CollapsingHeader::new("Tree")
             .default_open(false)
             .show(ui, |ui| self.tree.ui(ui));
@@ -64,6 +70,26 @@ impl DemoWindow {
             });
         });
 
+        ui.collapsing("Toasts", |ui| {
+            ui.label("Notifications that go away by themselves (hover them to keep them):");
+            ui.horizontal(|ui| {
+                if ui.button("Info").clicked {
+                    ui.ctx()
+                        .toast(toasts::Toast::info("Here is some information"));
+                }
+                if ui.button("Success").clicked {
+                    ui.ctx().toast(toasts::Toast::success("Saved"));
+                }
+                if ui.button("Warning").clicked {
+                    ui.ctx().toast(toasts::Toast::warning("Low on disk space"));
+                }
+                if ui.button("Error").clicked {
+                    ui.ctx()
+                        .toast(toasts::Toast::error("Export failed").duration(8.0));
+                }
+            });
+        });
+
         CollapsingHeader::new("Test box rendering")
             .default_open(false)
             .show(ui, |ui| self.box_painting.ui(ui));
@@ -357,6 +383,66 @@ impl LayoutDemo {
 
 // ----------------------------------------------------------------------------
 
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct GridDemo {
+    striped: bool,
+    name: String,
+    age: i32,
+    volume: f32,
+    enabled: bool,
+}
+
+impl Default for GridDemo {
+    fn default() -> Self {
+        Self {
+            striped: true,
+            name: "Arthur".to_owned(),
+            age: 42,
+            volume: 0.5,
+            enabled: true,
+        }
+    }
+}
+
+impl GridDemo {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.striped, "Striped");
+
+        Grid::new("settings_grid")
+            .striped(self.striped)
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut self.name);
+                ui.end_row();
+
+                ui.label("Age:");
+                ui.add(DragValue::i32(&mut self.age));
+                ui.end_row();
+
+                ui.label("A much longer label:");
+                ui.add(Slider::f32(&mut self.volume, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Enabled:");
+                ui.checkbox(&mut self.enabled, "");
+                ui.end_row();
+
+                ui.label("Multiple widgets in a cell:");
+                ui.horizontal(|ui| {
+                    ui.label("You can group");
+                    let _ = ui.button("widgets");
+                    ui.label("with ui.horizontal");
+                });
+                ui.end_row();
+            });
+
+        ui.label("Each column is as wide as its widest cell.");
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Clone, Copy, PartialEq)]
 enum Action {
     Keep,
@@ -16,7 +16,13 @@ impl Default for Demos {
                 (false, Box::new(super::FontBook::default())),
                 (false, Box::new(super::Painting::default())),
                 (false, Box::new(super::DancingStrings::default())),
+                (false, Box::new(super::PlotDemo::default())),
                 (false, Box::new(super::DragAndDropDemo::default())),
+                (false, Box::new(super::TableDemo::default())),
+                (false, Box::new(super::TreeViewDemo::default())),
+                (false, Box::new(super::DockDemo::default())),
+                (false, Box::new(super::ViewportsDemo::default())),
+                (false, Box::new(super::CodeEditor::default())),
                 (false, Box::new(super::Tests::default())),
                 (false, Box::new(super::WindowOptions::default())),
             ],
@@ -55,37 +61,41 @@ impl DemoWindows {
     /// Show the app ui (menu bar and windows).
     /// `sidebar_ui` can be used to optionally show some things in the sidebar
     pub fn ui(&mut self, ctx: &CtxRef) {
-        egui::SidePanel::left("side_panel", 200.0).show(ctx, |ui| {
-            ui.heading("✒ Egui Demo");
+        egui::SidePanel::left("side_panel")
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.heading("✒ Egui Demo");
 
-            ui.separator();
+                ui.separator();
 
-            ScrollArea::auto_sized().show(ui, |ui| {
-                ui.label("Egui is an immediate mode GUI library written in Rust.");
-                ui.add(
-                    egui::Hyperlink::new("https://github.com/emilk/egui").text(" Egui home page"),
-                );
+                ScrollArea::auto_sized().show(ui, |ui| {
+                    ui.label("Egui is an immediate mode GUI library written in Rust.");
+                    ui.add(
+                        egui::Hyperlink::new("https://github.com/emilk/egui")
+                            .text(" Egui home page"),
+                    );
 
-                ui.label("Egui can be run on the web, or natively on 🐧");
+                    ui.label("Egui can be run on the web, or natively on 🐧");
 
-                ui.separator();
+                    ui.separator();
 
-                ui.heading("Windows:");
-                ui.indent("windows", |ui| {
-                    self.open_windows.checkboxes(ui);
-                    self.demos.checkboxes(ui);
-                });
+                    ui.heading("Windows:");
+                    ui.indent("windows", |ui| {
+                        self.open_windows.checkboxes(ui);
+                        self.demos.checkboxes(ui);
+                    });
 
-                ui.separator();
+                    ui.separator();
 
-                if ui.button("Organize windows").clicked {
-                    ui.ctx().memory().reset_areas();
-                }
+                    if ui.button("Organize windows").clicked {
+                        ui.ctx().memory().reset_areas();
+                    }
+                });
             });
-        });
 
+        let open_windows = &mut self.open_windows;
         egui::TopPanel::top("menu_bar").show(ctx, |ui| {
-            show_menu_bar(ui);
+            show_menu_bar(ui, open_windows);
         });
 
         self.windows(ctx);
@@ -242,21 +252,31 @@ impl OpenWindows {
     }
 }
 
-fn show_menu_bar(ui: &mut Ui) {
-    use egui::*;
+fn show_menu_bar(ui: &mut Ui, open_windows: &mut OpenWindows) {
+    use egui::{menu::MenuItem, *};
 
     menu::bar(ui, |ui| {
         menu::menu(ui, "File", |ui| {
-            if ui.button("Organize windows").clicked {
+            if ui.add(MenuItem::new("Organize windows")).clicked {
                 ui.ctx().memory().reset_areas();
             }
             if ui
-                .button("Clear Egui memory")
+                .add(MenuItem::new("Clear Egui memory"))
                 .on_hover_text("Forget scroll, collapsing headers etc")
                 .clicked
             {
                 *ui.ctx().memory() = Default::default();
             }
         });
+        menu::menu(ui, "View", |ui| {
+            menu::checkbox(ui, &mut open_windows.demo, "✨ Demo");
+            menu::submenu(ui, "Egui", |ui| {
+                menu::checkbox(ui, &mut open_windows.settings, "🔧 Settings");
+                menu::checkbox(ui, &mut open_windows.inspection, "🔍 Inspection");
+                menu::checkbox(ui, &mut open_windows.memory, "📝 Memory");
+            });
+            ui.separator();
+            menu::checkbox(ui, &mut open_windows.resize, "↔ Resize examples");
+        });
     });
 }
@@ -0,0 +1,95 @@
+use egui::*;
+
+/// The contents of the tabs, which live outside of the [`DockLayout`].
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Tabs {
+    notes: String,
+    counter: i32,
+}
+
+impl TabViewer for Tabs {
+    type Tab = String;
+
+    fn title(&mut self, tab: &String) -> String {
+        tab.clone()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut String) {
+        match tab.as_str() {
+            "Notes" => {
+                ui.add(TextEdit::multiline(&mut self.notes));
+            }
+            "Counter" => {
+                ui.horizontal(|ui| {
+                    if ui.button("-").clicked {
+                        self.counter -= 1;
+                    }
+                    ui.label(self.counter.to_string());
+                    if ui.button("+").clicked {
+                        self.counter += 1;
+                    }
+                });
+            }
+            _ => {
+                ui.label(format!("This is the {} tab.", tab));
+                ui.label("Drag the tabs around!");
+            }
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DockDemo {
+    layout: DockLayout<String>,
+    tabs: Tabs,
+}
+
+impl Default for DockDemo {
+    fn default() -> Self {
+        let layout = DockLayout::new(DockNode::split(
+            SplitDirection::Horizontal,
+            0.35,
+            DockNode::tabs(vec!["Outliner".to_owned(), "Counter".to_owned()]),
+            DockNode::split(
+                SplitDirection::Vertical,
+                0.6,
+                DockNode::tabs(vec!["Viewport".to_owned()]),
+                DockNode::tabs(vec!["Notes".to_owned(), "Log".to_owned()]),
+            ),
+        ));
+        Self {
+            layout,
+            tabs: Default::default(),
+        }
+    }
+}
+
+impl super::Demo for DockDemo {
+    fn name(&self) -> &str {
+        "🗀 Docking"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(500.0, 350.0))
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for DockDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label(
+            "Drag tabs between groups, to the edge of a group to split it, or outside to make it float.",
+        );
+        if ui.button("Reset layout").clicked {
+            self.layout = Self::default().layout;
+        }
+        ui.separator();
+
+        Dock::new("demo_dock").show(ui, &mut self.layout, &mut self.tabs);
+    }
+}
@@ -5,25 +5,33 @@
 // ----------------------------------------------------------------------------
 
 mod app;
+mod code_editor;
 mod dancing_strings;
 pub mod demo_window;
 mod demo_windows;
+mod dock_demo;
 mod drag_and_drop;
 mod font_book;
 pub mod font_contents_emoji;
 pub mod font_contents_ubuntu;
 mod painting;
+mod plot_demo;
 mod scrolls;
 mod sliders;
+mod table_demo;
 mod tests;
 pub mod toggle_switch;
+mod tree_view_demo;
+mod viewports_demo;
 mod widgets;
 mod window_options;
 
 pub use {
-    app::*, dancing_strings::DancingStrings, demo_window::DemoWindow, demo_windows::*,
-    drag_and_drop::*, font_book::FontBook, painting::Painting, scrolls::Scrolls, sliders::Sliders,
-    tests::Tests, widgets::Widgets, window_options::WindowOptions,
+    app::*, code_editor::CodeEditor, dancing_strings::DancingStrings, demo_window::DemoWindow,
+    demo_windows::*, dock_demo::DockDemo, drag_and_drop::*, font_book::FontBook,
+    painting::Painting, plot_demo::PlotDemo, scrolls::Scrolls, sliders::Sliders,
+    table_demo::TableDemo, tests::Tests, tree_view_demo::TreeViewDemo,
+    viewports_demo::ViewportsDemo, widgets::Widgets, window_options::WindowOptions,
 };
 
 // ----------------------------------------------------------------------------
@@ -0,0 +1,98 @@
+use egui::plot::{Plot, Series, Value};
+use egui::*;
+
+#[derive(PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PlotDemo {
+    animate: bool,
+    time: f64,
+    num_points: usize,
+    show_bars: bool,
+    show_legend: bool,
+}
+
+impl Default for PlotDemo {
+    fn default() -> Self {
+        Self {
+            animate: true,
+            time: 0.0,
+            num_points: 200,
+            show_bars: true,
+            show_legend: true,
+        }
+    }
+}
+
+impl super::Demo for PlotDemo {
+    fn name(&self) -> &str {
+        "🗠 Plot"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(400.0, 400.0))
+            .scroll(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl PlotDemo {
+    fn sin(&self) -> Series {
+        let time = self.time;
+        let n = self.num_points;
+        Series::line((0..=n).map(|i| {
+            let x = remap(i as f64, 0.0..=n as f64, -5.0..=5.0);
+            Value::new(x, (x + time).sin())
+        }))
+        .name("sin(x)")
+        .stroke_width(2.0)
+    }
+
+    fn cos_points(&self) -> Series {
+        let time = self.time;
+        Series::points((0..=40).map(|i| {
+            let x = remap(i as f64, 0.0..=40.0, -5.0..=5.0);
+            Value::new(x, 0.5 * (x + time).cos())
+        }))
+        .name("0.5 cos(x)")
+    }
+
+    fn bars(&self) -> Series {
+        Series::bars((-4..=4).map(|i| {
+            let x = i as f64;
+            Value::new(x, 0.25 * (0.5 * x + self.time).sin())
+        }))
+        .name("bars")
+        .bar_width(0.6)
+    }
+}
+
+impl super::View for PlotDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.animate, "animate");
+            ui.checkbox(&mut self.show_bars, "bars");
+            ui.checkbox(&mut self.show_legend, "legend");
+        });
+        ui.add(Slider::usize(&mut self.num_points, 2..=2000).text("points in line"));
+        ui.label("Drag to pan, ctrl/cmd + drag to zoom, double-click to reset.");
+
+        if self.animate {
+            ui.ctx().request_repaint();
+            self.time += ui.input().unstable_dt.at_most(1.0 / 30.0) as f64;
+        }
+
+        let mut plot = Plot::new("demo_plot")
+            .series(self.sin())
+            .series(self.cos_points())
+            .legend(self.show_legend);
+        if self.show_bars {
+            plot = plot.series(self.bars());
+        }
+        ui.add(plot);
+
+        egui::reset_button(ui, self);
+    }
+}
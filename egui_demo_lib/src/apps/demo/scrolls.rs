@@ -27,6 +27,35 @@ impl Scrolls {
 
         ui.separator();
 
+        ui.label("A huge list, where only the visible rows are laid out:");
+        let text_style = TextStyle::Body;
+        let row_height = ui.fonts()[text_style].row_height();
+        let num_rows = 100_000;
+        ScrollArea::from_max_height(200.0)
+            .id_source("huge_list")
+            .show_rows(ui, row_height, num_rows, |ui, row| {
+                ui.label(format!("Row {}/{}", row + 1, num_rows));
+            });
+
+        ui.separator();
+
+        ui.label("Scrolling in both directions (hold shift to scroll horizontally with the mouse wheel):");
+        ScrollArea::both()
+            .max_height(200.0)
+            .id_source("both_directions")
+            .show(ui, |ui| {
+                Grid::new("scroll_grid").show(ui, |ui| {
+                    for row in 0..50 {
+                        for col in 0..20 {
+                            ui.label(format!("Cell ({}, {})", col, row));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+        ui.separator();
+
         ui.horizontal(|ui| {
             ui.checkbox(&mut self.tracking, "Track")
                 .on_hover_text("The scroll position will track the selected item");
@@ -52,7 +81,7 @@ impl Scrolls {
                 let mut scroll_area = ScrollArea::from_max_height(200.0).id_source(i);
                 if scroll_offset {
                     self.tracking = false;
-                    scroll_area = scroll_area.scroll_offset(self.offset);
+                    scroll_area = scroll_area.vertical_scroll_offset(self.offset);
                 }
 
                 let (current_scroll, max_scroll) = scroll_area.show(col, |ui| {
@@ -0,0 +1,133 @@
+use egui::*;
+
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+struct Planet {
+    name: String,
+    /// In Earth masses.
+    mass: f64,
+    /// In days.
+    orbital_period: f64,
+}
+
+#[derive(PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TableDemo {
+    num_rows: usize,
+    striped: bool,
+    /// Column and ascending?
+    sorted_by: Option<(usize, bool)>,
+    selected: Option<usize>,
+    #[serde(skip)]
+    rows: Vec<Planet>,
+}
+
+impl Default for TableDemo {
+    fn default() -> Self {
+        Self {
+            num_rows: 1000,
+            striped: true,
+            sorted_by: None,
+            selected: None,
+            rows: vec![],
+        }
+    }
+}
+
+impl super::Demo for TableDemo {
+    fn name(&self) -> &str {
+        "☰ Table"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(400.0, 400.0))
+            .scroll(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl TableDemo {
+    /// Make up some planets, deterministically.
+    fn generate_rows(&mut self) {
+        self.rows = (0..self.num_rows)
+            .map(|i| {
+                let x = i as f64;
+                Planet {
+                    name: format!("Planet #{}", i + 1),
+                    mass: (x * 12.9898).sin().abs() * 300.0,
+                    orbital_period: (x * 78.233).cos().abs() * 50_000.0,
+                }
+            })
+            .collect();
+        self.sort();
+    }
+
+    fn sort(&mut self) {
+        if let Some((column, ascending)) = self.sorted_by {
+            self.rows.sort_by(|a, b| {
+                let ordering = match column {
+                    0 => a.name.cmp(&b.name),
+                    1 => a.mass.partial_cmp(&b.mass).unwrap(),
+                    _ => a.orbital_period.partial_cmp(&b.orbital_period).unwrap(),
+                };
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+    }
+}
+
+impl super::View for TableDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        if self.rows.len() != self.num_rows {
+            self.selected = None;
+            self.generate_rows();
+        }
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.striped, "striped");
+            ui.add(Slider::usize(&mut self.num_rows, 0..=100_000).text("rows"));
+        });
+        ui.label("Drag the borders in the header to resize the columns. Click a header to sort.");
+        if let Some(selected) = self.selected {
+            ui.label(format!("Selected: {}", self.rows[selected].name));
+        }
+        ui.separator();
+
+        let mut table = Table::new("planets")
+            .column("Name")
+            .column(TableColumn::new("Mass (Earths)").min_width(60.0))
+            .column(TableColumn::new("Orbital period (days)").min_width(60.0))
+            .striped(self.striped)
+            .selected_row(self.selected);
+        if let Some((column, ascending)) = self.sorted_by {
+            table = table.sorted_by(column, ascending);
+        }
+
+        let rows = &self.rows;
+        let response = table.show(ui, rows.len(), |ui, row, col| {
+            let planet = &rows[row];
+            match col {
+                0 => ui.label(&planet.name),
+                1 => ui.label(format!("{:.2}", planet.mass)),
+                _ => ui.label(format!("{:.1}", planet.orbital_period)),
+            };
+        });
+
+        if let Some(row) = response.clicked_row {
+            self.selected = Some(row);
+        }
+        if let Some(sort_request) = response.sort_request {
+            let selected = self.selected.map(|row| self.rows[row].clone());
+            self.sorted_by = Some((sort_request.column, sort_request.ascending));
+            self.sort();
+            // Keep the same planet selected:
+            self.selected = selected.and_then(|planet| self.rows.iter().position(|p| *p == planet));
+        }
+    }
+}
@@ -31,6 +31,9 @@ pub fn toggle(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
         *on = !*on;
     }
 
+    // Tell screen readers (and tests) what kind of widget this is:
+    response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Checkbox, *on, ""));
+
     // 4. Paint!
     // First let's ask for a simple animation from Egui.
     // Egui keeps track of changes in the boolean associated with the id and
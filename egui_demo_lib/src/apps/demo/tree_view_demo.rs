@@ -0,0 +1,154 @@
+use egui::*;
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+struct Node {
+    id: usize,
+    name: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(id: usize, name: &str, children: Vec<Node>) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            children,
+        }
+    }
+
+    /// Remove the node with the given id from among our descendants.
+    fn take(&mut self, id: usize) -> Option<Node> {
+        if let Some(index) = self.children.iter().position(|child| child.id == id) {
+            return Some(self.children.remove(index));
+        }
+        self.children.iter_mut().find_map(|child| child.take(id))
+    }
+
+    /// Insert `nodes` relative to the descendant `target`. Returns the nodes if `target` wasn't found.
+    fn insert(
+        &mut self,
+        target: usize,
+        position: DropPosition,
+        nodes: Vec<Node>,
+    ) -> Result<(), Vec<Node>> {
+        if let Some(index) = self.children.iter().position(|child| child.id == target) {
+            let index = match position {
+                DropPosition::Before => index,
+                DropPosition::After => index + 1,
+                DropPosition::Inside => {
+                    self.children[index].children.extend(nodes);
+                    return Ok(());
+                }
+            };
+            self.children.splice(index..index, nodes);
+            return Ok(());
+        }
+        let mut nodes = nodes;
+        for child in &mut self.children {
+            match child.insert(target, position, nodes) {
+                Ok(()) => return Ok(()),
+                Err(returned) => nodes = returned,
+            }
+        }
+        Err(nodes)
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TreeViewDemo {
+    /// The children of the invisible root are the top-level nodes.
+    root: Node,
+    selected: Vec<usize>,
+}
+
+impl Default for TreeViewDemo {
+    fn default() -> Self {
+        let root = Node::new(
+            0,
+            "root",
+            vec![
+                Node::new(
+                    1,
+                    "Scene",
+                    vec![
+                        Node::new(2, "Camera", vec![]),
+                        Node::new(3, "Sun", vec![]),
+                        Node::new(
+                            4,
+                            "Table",
+                            vec![
+                                Node::new(5, "Top", vec![]),
+                                Node::new(6, "Leg", vec![]),
+                                Node::new(7, "Leg", vec![]),
+                            ],
+                        ),
+                    ],
+                ),
+                Node::new(8, "Unused", vec![Node::new(9, "Teapot", vec![])]),
+            ],
+        );
+        Self {
+            root,
+            selected: vec![],
+        }
+    }
+}
+
+impl super::Demo for TreeViewDemo {
+    fn name(&self) -> &str {
+        "🌳 Tree View"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(250.0, 300.0))
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+fn add_nodes(tree: &mut TreeViewBuilder<'_, usize>, nodes: &[Node]) {
+    for node in nodes {
+        if node.children.is_empty() {
+            tree.leaf(node.id, &node.name);
+        } else {
+            tree.node(node.id, &node.name, |tree| add_nodes(tree, &node.children));
+        }
+    }
+}
+
+impl super::View for TreeViewDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label(
+            "Ctrl/cmd-click and shift-click to select several nodes. Drag nodes to move them.",
+        );
+        ui.label(format!("Selected: {:?}", self.selected));
+        ui.separator();
+
+        let root = &self.root;
+        let response = TreeView::new("scene_tree")
+            .default_open(true)
+            .show(ui, |tree| add_nodes(tree, &root.children));
+
+        for action in response.actions {
+            match action {
+                TreeAction::SetSelected(selected) => self.selected = selected,
+                TreeAction::Move {
+                    nodes,
+                    target,
+                    position,
+                } => {
+                    let nodes: Vec<Node> = nodes
+                        .into_iter()
+                        .filter_map(|id| self.root.take(id))
+                        .collect();
+                    if let Err(nodes) = self.root.insert(target, position, nodes) {
+                        self.root.children.extend(nodes); // Should never happen
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use egui::{viewport::Viewport, *};
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewportsDemo {
+    popped_out: bool,
+    counter: i32,
+}
+
+fn counter_ui(ui: &mut Ui, counter: &mut i32) {
+    ui.horizontal(|ui| {
+        if ui.button("-").clicked {
+            *counter -= 1;
+        }
+        ui.label(counter.to_string());
+        if ui.button("+").clicked {
+            *counter += 1;
+        }
+    });
+}
+
+impl super::Demo for ViewportsDemo {
+    fn name(&self) -> &str {
+        "🖵 Viewports"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| self.ui(ui));
+
+        if !*open {
+            return;
+        }
+
+        let Self {
+            popped_out,
+            counter,
+        } = self;
+        Viewport::new("demo_viewport", "Popped-out panel")
+            .inner_size(vec2(300.0, 150.0))
+            .open(popped_out)
+            .show(ctx, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.label("This panel is in a native window of its own.");
+                    ui.label("It shares its state with the main window:");
+                    counter_ui(ui, counter);
+                });
+            });
+    }
+}
+
+impl super::View for ViewportsDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("Viewports are extra native windows, painted by the same egui context.");
+        ui.label(
+            "They are only supported by some integrations (e.g. egui_glium, but not the web).",
+        );
+        ui.separator();
+        ui.checkbox(&mut self.popped_out, "Pop out the panel");
+        counter_ui(ui, &mut self.counter);
+        ui.add(crate::__egui_github_link_file!());
+    }
+}
@@ -24,6 +24,7 @@ pub struct Widgets {
     color: Color32,
     single_line_text_input: String,
     multiline_text_input: String,
+    password: String,
     toggle_switch: bool,
 }
 
@@ -38,6 +39,7 @@ impl Default for Widgets {
             color: (Rgba::new(0.0, 1.0, 0.5, 1.0) * 0.75).into(),
             single_line_text_input: "Hello World!".to_owned(),
             multiline_text_input: "Text can both be so wide that it needs a line break, but you can also add manual line break by pressing enter, creating new paragraphs.\nThis is the start of the next paragraph.\n\nClick me to edit me!".to_owned(),
+            password: String::new(),
             toggle_switch: false,
         }
     }
@@ -65,6 +67,8 @@ impl Widgets {
             ui.monospace("There is also a monospace font.");
         });
 
+        ui.label(rich_text_example(ui));
+
         let tooltip_ui = |ui: &mut Ui| {
             ui.heading("The name of the tooltip");
             ui.horizontal(|ui| {
@@ -91,12 +95,18 @@ impl Widgets {
         ui.checkbox(&mut self.button_enabled, "Button enabled");
 
         ui.horizontal(|ui| {
+            let count = &mut self.count;
             if ui
                 .add(Button::new("Click me").enabled(self.button_enabled))
-                .on_hover_text("This will just increase a counter.")
+                .on_hover_text("This will just increase a counter. Right-click for more.")
+                .context_menu(|ui| {
+                    if ui.button("Reset counter").clicked {
+                        *count = 0;
+                    }
+                })
                 .clicked
             {
-                self.count += 1;
+                *count += 1;
             }
             ui.label(format!("The button has been clicked {} times.", self.count));
         });
@@ -149,6 +159,16 @@ impl Widgets {
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Password:");
+            ui.add(
+                TextEdit::singleline(&mut self.password)
+                    .password(true)
+                    .hint_text("At most 16 characters")
+                    .char_limit(16),
+            );
+        });
+
         ui.label("Multiline text input:");
         ui.text_edit_multiline(&mut self.multiline_text_input);
 
@@ -156,3 +176,52 @@ impl Widgets {
         super::toggle_switch::demo(ui, &mut self.toggle_switch);
     }
 }
+
+fn rich_text_example(ui: &Ui) -> paint::LayoutJob {
+    use paint::{FontFamily, LayoutJob, TextFormat};
+
+    let color = ui.style().visuals.text_color();
+    let body = TextFormat::text_style(ui.fonts(), TextStyle::Body, color);
+    let mut job = LayoutJob::default();
+    job.append("Rich text can mix ", body);
+    job.append(
+        "colors",
+        TextFormat {
+            color: Color32::from_rgb(255, 160, 60),
+            ..body
+        },
+    );
+    job.append(", ", body);
+    job.append(
+        "inline code",
+        TextFormat {
+            background: ui.style().visuals.dark_bg_color,
+            ..TextFormat::text_style(ui.fonts(), TextStyle::Monospace, color)
+        },
+    );
+    job.append(", ", body);
+    job.append("big", TextFormat { size: 22.0, ..body });
+    job.append(" and ", body);
+    job.append(
+        "small",
+        TextFormat::simple(FontFamily::Proportional, 10.0, color),
+    );
+    job.append(" text, ", body);
+    job.append(
+        "underlined",
+        TextFormat {
+            underline: Stroke::new(1.0, color),
+            ..body
+        },
+    );
+    job.append(" and ", body);
+    job.append(
+        "struck out",
+        TextFormat {
+            strikethrough: Stroke::new(1.0, color),
+            ..body
+        },
+    );
+    job.append(" words, all wrapping as one paragraph.", body);
+    job
+}
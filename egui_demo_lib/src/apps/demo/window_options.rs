@@ -7,6 +7,8 @@ pub struct WindowOptions {
     collapsible: bool,
     resizable: bool,
     scroll: bool,
+    modal: bool,
+    close_on_escape: bool,
 }
 
 impl Default for WindowOptions {
@@ -17,6 +19,8 @@ impl Default for WindowOptions {
             collapsible: true,
             resizable: true,
             scroll: false,
+            modal: false,
+            close_on_escape: false,
         }
     }
 }
@@ -34,6 +38,8 @@ impl super::Demo for WindowOptions {
             collapsible,
             resizable,
             scroll,
+            modal,
+            close_on_escape,
         } = self.clone();
 
         use super::View;
@@ -44,6 +50,8 @@ impl super::Demo for WindowOptions {
             .collapsible(collapsible)
             .title_bar(title_bar)
             .scroll(scroll)
+            .modal(modal)
+            .close_on_escape(close_on_escape)
             .show(ctx, |ui| self.ui(ui));
     }
 }
@@ -58,6 +66,8 @@ impl super::View for WindowOptions {
             collapsible,
             resizable,
             scroll,
+            modal,
+            close_on_escape,
         } = self;
 
         ui.horizontal(|ui| {
@@ -68,6 +78,8 @@ impl super::View for WindowOptions {
         ui.checkbox(collapsible, "collapsible");
         ui.checkbox(resizable, "resizable");
         ui.checkbox(scroll, "scroll");
+        ui.checkbox(modal, "modal");
+        ui.checkbox(close_on_escape, "close_on_escape");
         ui.add(__egui_github_link_file!());
     }
 }
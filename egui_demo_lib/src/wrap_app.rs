@@ -95,11 +95,13 @@ impl epi::App for WrapApp {
         });
 
         self.backend_panel.update(ctx, frame);
-        if self.backend_panel.open || ctx.memory().everything_is_visible() {
-            egui::SidePanel::left("backend_panel", 150.0).show(ctx, |ui| {
-                self.backend_panel.ui(ui, frame);
+        let show_backend_panel = self.backend_panel.open || ctx.memory().everything_is_visible();
+        let backend_panel = &mut self.backend_panel;
+        egui::SidePanel::left("backend_panel")
+            .default_width(150.0)
+            .show_animated(ctx, show_backend_panel, |ui| {
+                backend_panel.ui(ui, frame);
             });
-        }
 
         for (anchor, app) in self.apps.iter_mut() {
             if anchor == self.selected_anchor || ctx.memory().everything_is_visible() {
@@ -1,4 +1,6 @@
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
+
+use egui::viewport::{ViewportId, ViewportInfo, ViewportOutput, ViewportSettings};
 
 use crate::{storage::WindowSettings, *};
 
@@ -38,11 +40,29 @@ impl epi::RepaintSignal for GliumRepaintSignal {
     }
 }
 
+type EventLoopWindowTarget = glutin::event_loop::EventLoopWindowTarget<RequestRepaintEvent>;
+
+fn build_display(
+    window_builder: glutin::window::WindowBuilder,
+    event_loop: &EventLoopWindowTarget,
+) -> glium::Display {
+    let context_builder = glutin::ContextBuilder::new()
+        .with_depth_buffer(0)
+        .with_srgb(true)
+        .with_stencil_buffer(0)
+        .with_vsync(true);
+
+    let gl_window = context_builder
+        .build_windowed(window_builder, event_loop)
+        .unwrap();
+    glium::Display::from_gl_window(gl_window).unwrap()
+}
+
 fn create_display(
     title: &str,
     window_settings: Option<WindowSettings>,
     is_resizable: bool,
-    event_loop: &glutin::event_loop::EventLoop<RequestRepaintEvent>,
+    event_loop: &EventLoopWindowTarget,
 ) -> glium::Display {
     let mut window_builder = glutin::window::WindowBuilder::new()
         .with_decorations(true)
@@ -54,13 +74,7 @@ fn create_display(
         window_builder = window_settings.initialize_size(window_builder);
     }
 
-    let context_builder = glutin::ContextBuilder::new()
-        .with_depth_buffer(0)
-        .with_srgb(true)
-        .with_stencil_buffer(0)
-        .with_vsync(true);
-
-    let display = glium::Display::new(window_builder, context_builder, &event_loop).unwrap();
+    let display = build_display(window_builder, event_loop);
 
     if let Some(window_settings) = &window_settings {
         window_settings.restore_positions(&display);
@@ -69,6 +83,116 @@ fn create_display(
     display
 }
 
+/// A native window opened for an [`egui::viewport::Viewport`].
+struct ViewportWindow {
+    display: glium::Display,
+    /// Each window has its own OpenGL context, so each needs its own textures.
+    painter: Painter,
+    settings: ViewportSettings,
+}
+
+impl ViewportWindow {
+    fn new(settings: ViewportSettings, event_loop: &EventLoopWindowTarget) -> Self {
+        let mut window_builder = glutin::window::WindowBuilder::new()
+            .with_decorations(true)
+            .with_resizable(settings.resizable)
+            .with_title(&settings.title)
+            .with_transparent(false);
+        if let Some(inner_size) = settings.inner_size {
+            window_builder = window_builder.with_inner_size(glutin::dpi::LogicalSize {
+                width: inner_size.x as f64,
+                height: inner_size.y as f64,
+            });
+        }
+
+        let display = build_display(window_builder, event_loop);
+        let painter = Painter::new(&display);
+        Self {
+            display,
+            painter,
+            settings,
+        }
+    }
+
+    fn update_settings(&mut self, settings: ViewportSettings) {
+        if self.settings.title != settings.title {
+            self.display.gl_window().window().set_title(&settings.title);
+        }
+        if self.settings.resizable != settings.resizable {
+            self.display
+                .gl_window()
+                .window()
+                .set_resizable(settings.resizable);
+        }
+        self.settings = settings;
+    }
+}
+
+/// Open and close native windows so that they match the viewports egui wants,
+/// and paint them.
+fn update_viewport_windows(
+    ctx: &egui::CtxRef,
+    clear_color: egui::Rgba,
+    viewports: Vec<ViewportOutput>,
+    windows: &mut HashMap<ViewportId, ViewportWindow>,
+    input_state: &mut GliumInputState,
+    event_loop: &EventLoopWindowTarget,
+) {
+    windows.retain(|id, _| viewports.iter().any(|viewport| viewport.id == *id));
+    input_state
+        .raw
+        .viewports
+        .retain(|id, _| windows.contains_key(id));
+    if !windows.contains_key(&input_state.raw.viewport_id) {
+        input_state.raw.viewport_id = ViewportId::root();
+    }
+
+    for viewport in viewports {
+        let ViewportOutput {
+            id,
+            settings,
+            paint_commands,
+            text_cursor_rect,
+        } = viewport;
+
+        let window = windows
+            .entry(id)
+            .or_insert_with(|| ViewportWindow::new(settings.clone(), event_loop));
+        window.update_settings(settings);
+
+        let pixels_per_point = input_state.raw.pixels_per_point.unwrap();
+        let screen_rect = Rect::from_min_size(
+            Default::default(),
+            screen_size_in_pixels(&window.display) / pixels_per_point,
+        );
+        let close_requested = input_state
+            .raw
+            .viewports
+            .get(&id)
+            .map_or(false, |info| info.close_requested);
+        input_state.raw.viewports.insert(
+            id,
+            ViewportInfo {
+                screen_rect,
+                close_requested,
+            },
+        );
+
+        if let Some(text_cursor_rect) = text_cursor_rect {
+            set_ime_position(&window.display, text_cursor_rect, pixels_per_point);
+        }
+
+        let paint_jobs = ctx.tessellate(paint_commands);
+        window.painter.paint_jobs(
+            &window.display,
+            pixels_per_point,
+            clear_color,
+            paint_jobs,
+            &ctx.texture(),
+        );
+    }
+}
+
 fn create_storage(app_name: &str) -> Option<Box<dyn epi::Storage>> {
     if let Some(proj_dirs) = directories_next::ProjectDirs::from("", "", app_name) {
         let data_dir = proj_dirs.data_dir().to_path_buf();
@@ -137,6 +261,9 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
 
     let mut last_auto_save = Instant::now();
 
+    let root_window_id = display.gl_window().window().id();
+    let mut viewport_windows: HashMap<ViewportId, ViewportWindow> = Default::default();
+
     let http = std::sync::Arc::new(crate::http::GliumHttp {});
 
     if app.warm_up_enabled() {
@@ -169,7 +296,7 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
         // eprintln!("Warmed up in {} ms", warm_up_start.elapsed().as_millis())
     }
 
-    event_loop.run(move |event, _, control_flow| {
+    event_loop.run(move |event, event_loop, control_flow| {
         let mut redraw = || {
             let frame_start = Instant::now();
             input_state.raw.time = Some(start_time.elapsed().as_nanos() as f64 * 1e-9);
@@ -189,7 +316,7 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
             }
             .build();
             app.update(&ctx, &mut frame);
-            let (egui_output, paint_commands) = ctx.end_frame();
+            let (mut egui_output, paint_commands) = ctx.end_frame();
             let paint_jobs = ctx.tessellate(paint_commands);
 
             let frame_time = (Instant::now() - frame_start).as_secs_f64() as f32;
@@ -202,6 +329,19 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
                 &ctx.texture(),
             );
 
+            if let Some(text_cursor_rect) = egui_output.text_cursor_rect {
+                set_ime_position(&display, text_cursor_rect, ctx.pixels_per_point());
+            }
+
+            update_viewport_windows(
+                &ctx,
+                app.clear_color(),
+                std::mem::take(&mut egui_output.viewports),
+                &mut viewport_windows,
+                &mut input_state,
+                event_loop,
+            );
+
             {
                 let epi::backend::AppOutput {
                     quit,
@@ -234,7 +374,11 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
                 };
             }
 
-            handle_output(egui_output, &display, clipboard.as_mut());
+            // Set the cursor of the window the mouse is in:
+            let mouse_display = viewport_windows
+                .get(&input_state.raw.viewport_id)
+                .map_or(&display, |window| &window.display);
+            handle_output(egui_output, mouse_display, clipboard.as_mut());
 
             if let Some(storage) = &mut storage {
                 let now = Instant::now();
@@ -257,10 +401,37 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
             // See: https://github.com/rust-windowing/winit/issues/987
             // See: https://github.com/rust-windowing/winit/issues/1619
             glutin::event::Event::RedrawEventsCleared if cfg!(windows) => redraw(),
-            glutin::event::Event::RedrawRequested(_) if !cfg!(windows) => redraw(),
+            // All windows are painted together, when the root window is redrawn:
+            glutin::event::Event::RedrawRequested(window_id)
+                if !cfg!(windows) && window_id == root_window_id =>
+            {
+                redraw()
+            }
 
-            glutin::event::Event::WindowEvent { event, .. } => {
-                input_to_egui(event, clipboard.as_mut(), &mut input_state, control_flow);
+            glutin::event::Event::WindowEvent { window_id, event } => {
+                let viewport_id = viewport_windows
+                    .iter()
+                    .find(|(_, window)| window.display.gl_window().window().id() == window_id)
+                    .map_or_else(ViewportId::root, |(id, _)| *id);
+
+                use glutin::event::WindowEvent::*;
+                match event {
+                    CloseRequested if viewport_id != ViewportId::root() => {
+                        // Let the app decide if the viewport should close:
+                        if let Some(info) = input_state.raw.viewports.get_mut(&viewport_id) {
+                            info.close_requested = true;
+                        }
+                    }
+                    CursorLeft { .. } if viewport_id != input_state.raw.viewport_id => {
+                        // The mouse has already moved into another of our windows.
+                    }
+                    _ => {
+                        if let CursorMoved { .. } = event {
+                            input_state.raw.viewport_id = viewport_id;
+                        }
+                        input_to_egui(event, clipboard.as_mut(), &mut input_state, control_flow);
+                    }
+                }
                 display.gl_window().window().request_redraw(); // TODO: ask Egui if the events warrants a repaint instead
             }
             glutin::event::Event::LoopDestroyed => {
@@ -28,6 +28,8 @@ pub use clipboard::ClipboardContext; // TODO: remove
 
 pub struct GliumInputState {
     pub raw: egui::RawInput,
+    /// The finger that moves the mouse, if any.
+    touch_mouse_id: Option<u64>,
 }
 
 impl GliumInputState {
@@ -37,6 +39,7 @@ impl GliumInputState {
                 pixels_per_point: Some(pixels_per_point),
                 ..Default::default()
             },
+            touch_mouse_id: None,
         }
     }
 }
@@ -50,8 +53,11 @@ pub fn input_to_egui(
     use glutin::event::WindowEvent::*;
     match event {
         CloseRequested | Destroyed => *control_flow = ControlFlow::Exit,
-        MouseInput { state, .. } => {
-            input_state.raw.mouse_down = state == glutin::event::ElementState::Pressed;
+        MouseInput { state, button, .. } => {
+            if let Some(button) = translate_mouse_button(button) {
+                input_state.raw.mouse_down[button as usize] =
+                    state == glutin::event::ElementState::Pressed;
+            }
         }
         CursorMoved {
             position: pos_in_pixels,
@@ -65,6 +71,43 @@ pub fn input_to_egui(
         CursorLeft { .. } => {
             input_state.raw.mouse_pos = None;
         }
+        Touch(glutin::event::Touch {
+            phase,
+            location: pos_in_pixels,
+            force,
+            id,
+            ..
+        }) => {
+            use glutin::event::TouchPhase as Phase;
+            let pos = pos2(
+                pos_in_pixels.x as f32 / input_state.raw.pixels_per_point.unwrap(),
+                pos_in_pixels.y as f32 / input_state.raw.pixels_per_point.unwrap(),
+            );
+            input_state.raw.events.push(Event::Touch {
+                id: TouchId(id),
+                phase: match phase {
+                    Phase::Started => TouchPhase::Start,
+                    Phase::Moved => TouchPhase::Move,
+                    Phase::Ended => TouchPhase::End,
+                    Phase::Cancelled => TouchPhase::Cancel,
+                },
+                pos,
+                force: force.map(|force| force.normalized() as f32),
+            });
+
+            // The first finger also moves the mouse:
+            if phase == Phase::Started && input_state.touch_mouse_id.is_none() {
+                input_state.touch_mouse_id = Some(id);
+            }
+            if input_state.touch_mouse_id == Some(id) {
+                let down = matches!(phase, Phase::Started | Phase::Moved);
+                input_state.raw.mouse_pos = Some(pos);
+                input_state.raw.mouse_down[egui::MouseButton::Primary as usize] = down;
+                if !down {
+                    input_state.touch_mouse_id = None;
+                }
+            }
+        }
         ReceivedCharacter(ch) => {
             if printable_char(ch)
                 && !input_state.raw.modifiers.ctrl
@@ -177,22 +220,97 @@ pub fn translate_virtual_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         Right => Key::ArrowRight,
         Down => Key::ArrowDown,
         Back => Key::Backspace,
-        Return => Key::Enter,
+        Return | NumpadEnter => Key::Enter,
         Tab => Key::Tab,
         Space => Key::Space,
 
+        Minus | NumpadSubtract => Key::Minus,
+        Equals | NumpadEquals => Key::Equals,
+        Plus | NumpadAdd => Key::Plus,
+        Comma | NumpadComma => Key::Comma,
+        Period | NumpadDecimal => Key::Period,
+        Semicolon => Key::Semicolon,
+        Slash | NumpadDivide => Key::Slash,
+        Backslash => Key::Backslash,
+        Apostrophe => Key::Quote,
+        Grave => Key::Backtick,
+        LBracket => Key::OpenBracket,
+        RBracket => Key::CloseBracket,
+
+        Key0 | Numpad0 => Key::Num0,
+        Key1 | Numpad1 => Key::Num1,
+        Key2 | Numpad2 => Key::Num2,
+        Key3 | Numpad3 => Key::Num3,
+        Key4 | Numpad4 => Key::Num4,
+        Key5 | Numpad5 => Key::Num5,
+        Key6 | Numpad6 => Key::Num6,
+        Key7 | Numpad7 => Key::Num7,
+        Key8 | Numpad8 => Key::Num8,
+        Key9 | Numpad9 => Key::Num9,
+
         A => Key::A,
+        B => Key::B,
+        C => Key::C,
+        D => Key::D,
+        E => Key::E,
+        F => Key::F,
+        G => Key::G,
+        H => Key::H,
+        I => Key::I,
+        J => Key::J,
         K => Key::K,
+        L => Key::L,
+        M => Key::M,
+        N => Key::N,
+        O => Key::O,
+        P => Key::P,
+        Q => Key::Q,
+        R => Key::R,
+        S => Key::S,
+        T => Key::T,
         U => Key::U,
+        V => Key::V,
         W => Key::W,
+        X => Key::X,
+        Y => Key::Y,
         Z => Key::Z,
 
+        F1 => Key::F1,
+        F2 => Key::F2,
+        F3 => Key::F3,
+        F4 => Key::F4,
+        F5 => Key::F5,
+        F6 => Key::F6,
+        F7 => Key::F7,
+        F8 => Key::F8,
+        F9 => Key::F9,
+        F10 => Key::F10,
+        F11 => Key::F11,
+        F12 => Key::F12,
+        F13 => Key::F13,
+        F14 => Key::F14,
+        F15 => Key::F15,
+        F16 => Key::F16,
+        F17 => Key::F17,
+        F18 => Key::F18,
+        F19 => Key::F19,
+        F20 => Key::F20,
+
         _ => {
             return None;
         }
     })
 }
 
+pub fn translate_mouse_button(button: glutin::event::MouseButton) -> Option<egui::MouseButton> {
+    match button {
+        glutin::event::MouseButton::Left => Some(egui::MouseButton::Primary),
+        glutin::event::MouseButton::Right => Some(egui::MouseButton::Secondary),
+        glutin::event::MouseButton::Middle => Some(egui::MouseButton::Middle),
+        glutin::event::MouseButton::Other(_) => None,
+    }
+}
+
 pub fn translate_cursor(cursor_icon: egui::CursorIcon) -> glutin::window::CursorIcon {
     match cursor_icon {
         CursorIcon::Default => glutin::window::CursorIcon::Default,
@@ -232,6 +350,17 @@ pub fn handle_output(
         .set_cursor_icon(translate_cursor(output.cursor_icon));
 }
 
+/// Show the IME candidate window (for e.g. Chinese and Japanese text entry) below the text cursor.
+pub fn set_ime_position(display: &glium::Display, text_cursor_rect: Rect, pixels_per_point: f32) {
+    display
+        .gl_window()
+        .window()
+        .set_ime_position(glutin::dpi::PhysicalPosition {
+            x: text_cursor_rect.left() * pixels_per_point,
+            y: text_cursor_rect.bottom() * pixels_per_point,
+        });
+}
+
 pub fn init_clipboard() -> Option<ClipboardContext> {
     match ClipboardContext::new() {
         Ok(clipboard) => Some(clipboard),
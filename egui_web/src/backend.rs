@@ -94,6 +94,10 @@ pub struct WebInput {
     pub is_touch: bool,
 
     pub raw: egui::RawInput,
+
+    /// The keys we have sent a press for, but no release yet.
+    /// Released when we lose focus, since we won't get their keyup events.
+    pub keys_down: std::collections::HashSet<egui::Key>,
 }
 
 impl WebInput {
@@ -220,6 +224,7 @@ impl AppRunner {
         self.app.update(egui_ctx, &mut frame);
         let (egui_output, paint_jobs) = self.web_backend.end_frame()?;
         handle_output(&egui_output);
+        move_text_agent(self.canvas_id(), egui_output.text_cursor_rect);
 
         {
             let epi::backend::AppOutput {
@@ -252,6 +257,7 @@ fn start_runner(app_runner: AppRunner) -> Result<AppRunnerRef, JsValue> {
     let runner_ref = AppRunnerRef(Arc::new(Mutex::new(app_runner)));
     install_canvas_events(&runner_ref)?;
     install_document_events(&runner_ref)?;
+    install_text_agent(&runner_ref)?;
     repaint_every_ms(&runner_ref, 1000)?; // just in case. TODO: make it a parameter
     paint_and_schedule(runner_ref.clone())?;
     Ok(runner_ref)
@@ -93,6 +93,15 @@ pub fn pos_from_mouse_event(canvas_id: &str, event: &web_sys::MouseEvent) -> egu
     }
 }
 
+pub fn button_from_mouse_event(event: &web_sys::MouseEvent) -> Option<egui::MouseButton> {
+    match event.button() {
+        0 => Some(egui::MouseButton::Primary),
+        1 => Some(egui::MouseButton::Middle),
+        2 => Some(egui::MouseButton::Secondary),
+        _ => None,
+    }
+}
+
 pub fn pos_from_touch_event(event: &web_sys::TouchEvent) -> egui::Pos2 {
     let t = event.touches().get(0).unwrap();
     egui::Pos2 {
@@ -101,6 +110,21 @@ pub fn pos_from_touch_event(event: &web_sys::TouchEvent) -> egui::Pos2 {
     }
 }
 
+/// Tell egui about the fingers that changed in this touch event.
+pub fn push_touches(input: &mut WebInput, phase: egui::TouchPhase, event: &web_sys::TouchEvent) {
+    let touches = event.changed_touches();
+    for i in 0..touches.length() {
+        if let Some(touch) = touches.get(i) {
+            input.raw.events.push(egui::Event::Touch {
+                id: egui::TouchId(touch.identifier() as u64),
+                phase,
+                pos: egui::pos2(touch.page_x() as f32, touch.page_y() as f32),
+                force: Some(touch.force()),
+            });
+        }
+    }
+}
+
 pub fn canvas_size_in_points(canvas_id: &str) -> egui::Vec2 {
     let canvas = canvas_element(canvas_id).unwrap();
     let pixels_per_point = native_pixels_per_point();
@@ -199,6 +223,9 @@ pub fn handle_output(output: &egui::Output) {
         open_url,
         copied_text,
         needs_repaint: _, // handled elsewhere
+        accessibility_tree: _,
+        text_cursor_rect: _, // handled by `move_text_agent`
+        viewports: _,        // We only have the one canvas
     } = output;
 
     set_cursor_icon(*cursor_icon);
@@ -311,8 +338,27 @@ fn should_ignore_key(key: &str) -> bool {
         )
 }
 
-/// Web sends all all keys as strings, so it is up to us to figure out if it is
-/// a real text input or the name of a key.
+/// Translate a [`web_sys::KeyboardEvent`] to a [`egui::Key`].
+///
+/// We go by the layout-aware `KeyboardEvent.key`, so that e.g. ctrl-Z is [`egui::Key::Z`]
+/// on all keyboard layouts, like the native integrations do.
+/// If `key` is a shifted symbol we don't know (e.g. `"!"` for shift-1),
+/// we fall back to the physical `KeyboardEvent.code` of the digit or punctuation key.
+pub fn translate_key_event(event: &web_sys::KeyboardEvent) -> Option<egui::Key> {
+    let key = event.key();
+    translate_key(&key).or_else(|| {
+        let is_symbol = key.chars().count() == 1 && !key.chars().any(char::is_alphanumeric);
+        if is_symbol {
+            translate_symbol_code(&event.code())
+        } else {
+            None
+        }
+    })
+}
+
+/// Translate a `KeyboardEvent.key` (e.g. `"Enter"` or `"a"`) to a [`egui::Key`].
+///
+/// Letters are matched case-insensitively, so that shift doesn't change the key.
 pub fn translate_key(key: &str) -> Option<egui::Key> {
     match key {
         "ArrowDown" => Some(egui::Key::ArrowDown),
@@ -323,18 +369,116 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
         "Delete" => Some(egui::Key::Delete),
         "End" => Some(egui::Key::End),
         "Enter" => Some(egui::Key::Enter),
-        "Space" => Some(egui::Key::Space),
+        " " | "Space" => Some(egui::Key::Space),
         "Esc" | "Escape" => Some(egui::Key::Escape),
         "Help" | "Insert" => Some(egui::Key::Insert),
         "Home" => Some(egui::Key::Home),
         "PageDown" => Some(egui::Key::PageDown),
         "PageUp" => Some(egui::Key::PageUp),
         "Tab" => Some(egui::Key::Tab),
+
+        "-" => Some(egui::Key::Minus),
+        "=" => Some(egui::Key::Equals),
+        "+" => Some(egui::Key::Plus),
+        "," => Some(egui::Key::Comma),
+        "." => Some(egui::Key::Period),
+        ";" => Some(egui::Key::Semicolon),
+        "/" => Some(egui::Key::Slash),
+        "\\" => Some(egui::Key::Backslash),
+        "'" => Some(egui::Key::Quote),
+        "`" => Some(egui::Key::Backtick),
+        "[" => Some(egui::Key::OpenBracket),
+        "]" => Some(egui::Key::CloseBracket),
+
+        "0" => Some(egui::Key::Num0),
+        "1" => Some(egui::Key::Num1),
+        "2" => Some(egui::Key::Num2),
+        "3" => Some(egui::Key::Num3),
+        "4" => Some(egui::Key::Num4),
+        "5" => Some(egui::Key::Num5),
+        "6" => Some(egui::Key::Num6),
+        "7" => Some(egui::Key::Num7),
+        "8" => Some(egui::Key::Num8),
+        "9" => Some(egui::Key::Num9),
+
         "a" | "A" => Some(egui::Key::A),
+        "b" | "B" => Some(egui::Key::B),
+        "c" | "C" => Some(egui::Key::C),
+        "d" | "D" => Some(egui::Key::D),
+        "e" | "E" => Some(egui::Key::E),
+        "f" | "F" => Some(egui::Key::F),
+        "g" | "G" => Some(egui::Key::G),
+        "h" | "H" => Some(egui::Key::H),
+        "i" | "I" => Some(egui::Key::I),
+        "j" | "J" => Some(egui::Key::J),
         "k" | "K" => Some(egui::Key::K),
+        "l" | "L" => Some(egui::Key::L),
+        "m" | "M" => Some(egui::Key::M),
+        "n" | "N" => Some(egui::Key::N),
+        "o" | "O" => Some(egui::Key::O),
+        "p" | "P" => Some(egui::Key::P),
+        "q" | "Q" => Some(egui::Key::Q),
+        "r" | "R" => Some(egui::Key::R),
+        "s" | "S" => Some(egui::Key::S),
+        "t" | "T" => Some(egui::Key::T),
         "u" | "U" => Some(egui::Key::U),
+        "v" | "V" => Some(egui::Key::V),
         "w" | "W" => Some(egui::Key::W),
+        "x" | "X" => Some(egui::Key::X),
+        "y" | "Y" => Some(egui::Key::Y),
         "z" | "Z" => Some(egui::Key::Z),
+
+        "F1" => Some(egui::Key::F1),
+        "F2" => Some(egui::Key::F2),
+        "F3" => Some(egui::Key::F3),
+        "F4" => Some(egui::Key::F4),
+        "F5" => Some(egui::Key::F5),
+        "F6" => Some(egui::Key::F6),
+        "F7" => Some(egui::Key::F7),
+        "F8" => Some(egui::Key::F8),
+        "F9" => Some(egui::Key::F9),
+        "F10" => Some(egui::Key::F10),
+        "F11" => Some(egui::Key::F11),
+        "F12" => Some(egui::Key::F12),
+        "F13" => Some(egui::Key::F13),
+        "F14" => Some(egui::Key::F14),
+        "F15" => Some(egui::Key::F15),
+        "F16" => Some(egui::Key::F16),
+        "F17" => Some(egui::Key::F17),
+        "F18" => Some(egui::Key::F18),
+        "F19" => Some(egui::Key::F19),
+        "F20" => Some(egui::Key::F20),
+        _ => None,
+    }
+}
+
+/// Translate the physical `KeyboardEvent.code` of a digit or punctuation key
+/// (e.g. `"Digit1"`) to a [`egui::Key`].
+fn translate_symbol_code(code: &str) -> Option<egui::Key> {
+    match code {
+        "Minus" | "NumpadSubtract" => Some(egui::Key::Minus),
+        "Equal" | "NumpadEqual" => Some(egui::Key::Equals),
+        "NumpadAdd" => Some(egui::Key::Plus),
+        "Comma" | "NumpadComma" => Some(egui::Key::Comma),
+        "Period" | "NumpadDecimal" => Some(egui::Key::Period),
+        "Semicolon" => Some(egui::Key::Semicolon),
+        "Slash" | "NumpadDivide" => Some(egui::Key::Slash),
+        "Backslash" => Some(egui::Key::Backslash),
+        "Quote" => Some(egui::Key::Quote),
+        "Backquote" => Some(egui::Key::Backtick),
+        "BracketLeft" => Some(egui::Key::OpenBracket),
+        "BracketRight" => Some(egui::Key::CloseBracket),
+
+        "Digit0" | "Numpad0" => Some(egui::Key::Num0),
+        "Digit1" | "Numpad1" => Some(egui::Key::Num1),
+        "Digit2" | "Numpad2" => Some(egui::Key::Num2),
+        "Digit3" | "Numpad3" => Some(egui::Key::Num3),
+        "Digit4" | "Numpad4" => Some(egui::Key::Num4),
+        "Digit5" | "Numpad5" => Some(egui::Key::Num5),
+        "Digit6" | "Numpad6" => Some(egui::Key::Num6),
+        "Digit7" | "Numpad7" => Some(egui::Key::Num7),
+        "Digit8" | "Numpad8" => Some(egui::Key::Num8),
+        "Digit9" | "Numpad9" => Some(egui::Key::Num9),
         _ => None,
     }
 }
@@ -392,9 +536,10 @@ fn install_document_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
 
             let key = event.key();
 
-            if let Some(key) = translate_key(&key) {
+            if let Some(egui_key) = translate_key_event(&event) {
+                runner_lock.input.keys_down.insert(egui_key);
                 runner_lock.input.raw.events.push(egui::Event::Key {
-                    key,
+                    key: egui_key,
                     pressed: true,
                     modifiers,
                 });
@@ -437,7 +582,8 @@ fn install_document_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             let mut runner_lock = runner_ref.0.lock();
             let modifiers = modifiers_from_event(&event);
             runner_lock.input.raw.modifiers = modifiers;
-            if let Some(key) = translate_key(&event.key()) {
+            if let Some(key) = translate_key_event(&event) {
+                runner_lock.input.keys_down.remove(&key);
                 runner_lock.input.raw.events.push(egui::Event::Key {
                     key,
                     pressed: false,
@@ -450,6 +596,29 @@ fn install_document_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         closure.forget();
     }
 
+    {
+        // blur
+        // We don't get the keyup events for keys that are released while we don't have focus,
+        // so release everything now or the keys will stick.
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move || {
+            let mut runner_lock = runner_ref.0.lock();
+            let modifiers = egui::Modifiers::default();
+            runner_lock.input.raw.modifiers = modifiers;
+            let keys_down = std::mem::take(&mut runner_lock.input.keys_down);
+            for key in keys_down {
+                runner_lock.input.raw.events.push(egui::Event::Key {
+                    key,
+                    pressed: false,
+                    modifiers,
+                });
+            }
+            runner_lock.needs_repaint.set_true();
+        }) as Box<dyn FnMut()>);
+        window.add_event_listener_with_callback("blur", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
     #[cfg(web_sys_unstable_apis)]
     {
         // paste
@@ -505,6 +674,81 @@ fn install_document_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
     Ok(())
 }
 
+/// The id of the hidden `<input>` that has the keyboard focus while egui wants text input,
+/// so that the browser sends us IME composition events (and shows the virtual keyboard on mobile).
+const TEXT_AGENT_ID: &str = "egui_text_agent";
+
+fn install_text_agent(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
+    use wasm_bindgen::JsCast;
+    let document = web_sys::window().unwrap().document().unwrap();
+    let input = document
+        .create_element("input")?
+        .dyn_into::<web_sys::HtmlInputElement>()?;
+    input.set_id(TEXT_AGENT_ID);
+    let style = input.style();
+    style.set_property("position", "fixed")?;
+    style.set_property("width", "1px")?;
+    style.set_property("height", "1px")?;
+    style.set_property("opacity", "0")?;
+    style.set_property("border", "none")?;
+    style.set_property("pointer-events", "none")?;
+    document.body().unwrap().append_child(&input)?;
+
+    {
+        let runner_ref = runner_ref.clone();
+        let input_clone = input.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::CompositionEvent| {
+            let mut runner_lock = runner_ref.0.lock();
+            let data = event.data().unwrap_or_default();
+            let event = match event.type_().as_str() {
+                "compositionstart" => egui::Event::CompositionStart,
+                "compositionupdate" => egui::Event::CompositionUpdate(data),
+                _ => {
+                    input_clone.set_value("");
+                    egui::Event::CompositionEnd(data)
+                }
+            };
+            runner_lock.input.raw.events.push(event);
+            runner_lock.needs_repaint.set_true();
+        }) as Box<dyn FnMut(_)>);
+        for event_name in &["compositionstart", "compositionupdate", "compositionend"] {
+            input.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        }
+        closure.forget();
+    }
+
+    Ok(())
+}
+
+/// Focus the text agent while egui wants text input,
+/// and put it at the text cursor so that the IME candidate window shows up there.
+pub fn move_text_agent(canvas_id: &str, text_cursor_rect: Option<egui::Rect>) -> Option<()> {
+    use wasm_bindgen::JsCast;
+    let document = web_sys::window()?.document()?;
+    let input = document
+        .get_element_by_id(TEXT_AGENT_ID)?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?;
+    let focused = document
+        .active_element()
+        .map_or(false, |element| element.id() == TEXT_AGENT_ID);
+
+    if let Some(rect) = text_cursor_rect {
+        let canvas_rect = canvas_element(canvas_id)?.get_bounding_client_rect();
+        let style = input.style();
+        let left = canvas_rect.left() as f32 + rect.left();
+        let top = canvas_rect.top() as f32 + rect.bottom();
+        style.set_property("left", &format!("{}px", left)).ok()?;
+        style.set_property("top", &format!("{}px", top)).ok()?;
+        if !focused {
+            input.focus().ok()?;
+        }
+    } else if focused {
+        input.blur().ok()?;
+    }
+    Some(())
+}
+
 /// Repaint at least every `ms` milliseconds.
 fn repaint_every_ms(runner_ref: &AppRunnerRef, milliseconds: i32) -> Result<(), JsValue> {
     assert!(milliseconds >= 0);
@@ -550,7 +794,9 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             if !runner_lock.input.is_touch {
                 runner_lock.input.raw.mouse_pos =
                     Some(pos_from_mouse_event(runner_lock.canvas_id(), &event));
-                runner_lock.input.raw.mouse_down = true;
+                if let Some(button) = button_from_mouse_event(&event) {
+                    runner_lock.input.raw.mouse_down[button as usize] = true;
+                }
                 runner_lock.logic().unwrap(); // in case we get "mouseup" the same frame. TODO: handle via events instead
                 runner_lock.needs_repaint.set_true();
                 event.stop_propagation();
@@ -586,7 +832,9 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             if !runner_lock.input.is_touch {
                 runner_lock.input.raw.mouse_pos =
                     Some(pos_from_mouse_event(runner_lock.canvas_id(), &event));
-                runner_lock.input.raw.mouse_down = false;
+                if let Some(button) = button_from_mouse_event(&event) {
+                    runner_lock.input.raw.mouse_down[button as usize] = false;
+                }
                 runner_lock.needs_repaint.set_true();
                 event.stop_propagation();
                 event.prevent_default();
@@ -596,6 +844,16 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         closure.forget();
     }
 
+    {
+        // Egui shows its own context menus, so don't let the browser show one on top:
+        let event_name = "contextmenu";
+        let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
     {
         let event_name = "mouseleave";
         let runner_ref = runner_ref.clone();
@@ -618,8 +876,9 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
+            push_touches(&mut runner_lock.input, egui::TouchPhase::Start, &event);
             runner_lock.input.raw.mouse_pos = Some(pos_from_touch_event(&event));
-            runner_lock.input.raw.mouse_down = true;
+            runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = true;
             runner_lock.needs_repaint.set_true();
             event.stop_propagation();
             event.prevent_default();
@@ -634,6 +893,7 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
+            push_touches(&mut runner_lock.input, egui::TouchPhase::Move, &event);
             runner_lock.input.raw.mouse_pos = Some(pos_from_touch_event(&event));
             runner_lock.needs_repaint.set_true();
             event.stop_propagation();
@@ -649,7 +909,8 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
-            runner_lock.input.raw.mouse_down = false; // First release mouse to click...
+            push_touches(&mut runner_lock.input, egui::TouchPhase::End, &event);
+            runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = false; // First release mouse to click...
             runner_lock.logic().unwrap(); // ...do the clicking... (TODO: handle via events instead)
             runner_lock.input.raw.mouse_pos = None; // ...remove hover effect
             runner_lock.needs_repaint.set_true();
@@ -660,6 +921,23 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         closure.forget();
     }
 
+    {
+        let event_name = "touchcancel";
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock.input.is_touch = true;
+            push_touches(&mut runner_lock.input, egui::TouchPhase::Cancel, &event);
+            runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = false;
+            runner_lock.input.raw.mouse_pos = None;
+            runner_lock.needs_repaint.set_true();
+            event.stop_propagation();
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
     {
         let event_name = "wheel";
         let runner_ref = runner_ref.clone();